@@ -3,9 +3,11 @@
 
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::Key;
-use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::update::{DuplicateDocuments, IndexDocumentsMethod};
 use meilisearch_types::settings::Unchecked;
-use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task, TaskId};
+use meilisearch_types::tasks::{
+    Details, IndexSwap, IndexingLimits, KindWithContent, Status, Task, TaskId,
+};
 use meilisearch_types::InstanceUid;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
@@ -95,6 +97,12 @@ pub enum KindDump {
         primary_key: Option<String>,
         method: IndexDocumentsMethod,
         documents_count: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        indexing_limits: Option<IndexingLimits>,
+        #[serde(default, skip_serializing_if = "is_default")]
+        duplicate_documents: DuplicateDocuments,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        version_field: Option<String>,
         allow_index_creation: bool,
     },
     DocumentDeletion {
@@ -151,6 +159,10 @@ impl From<Task> for TaskDump {
     }
 }
 
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
 impl From<KindWithContent> for KindDump {
     fn from(kind: KindWithContent) -> Self {
         match kind {
@@ -158,12 +170,18 @@ impl From<KindWithContent> for KindDump {
                 primary_key,
                 method,
                 documents_count,
+                indexing_limits,
+                duplicate_documents,
+                version_field,
                 allow_index_creation,
                 ..
             } => KindDump::DocumentImport {
                 primary_key,
                 method,
                 documents_count,
+                indexing_limits,
+                duplicate_documents,
+                version_field,
                 allow_index_creation,
             },
             KindWithContent::DocumentDeletion { documents_ids, .. } => {
@@ -265,7 +283,13 @@ pub(crate) mod test {
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
+            case_sensitive: Setting::NotSet,
+            languages: Setting::NotSet,
+            searchable_numbers: Setting::NotSet,
+            tie_breaking_sort: Setting::NotSet,
+            encrypted_attributes: Setting::NotSet,
             synonyms: Setting::NotSet,
+            bidirectional_synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::Set(FacetingSettings {
@@ -275,6 +299,7 @@ pub(crate) mod test {
                 ),
             }),
             pagination: Setting::NotSet,
+            pinned_in_memory: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -292,11 +317,17 @@ pub(crate) mod test {
                         allow_index_creation: true,
                         primary_key: Some(S("bone")),
                         documents_count: 12,
+                        indexing_limits: None,
+                        duplicate_documents: Default::default(),
+                        version_field: None,
                     },
                     canceled_by: None,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 12,
                         indexed_documents: Some(10),
+                        duplicated_documents: None,
+                        rejected_documents: None,
+                        generated_ids: None,
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -315,11 +346,17 @@ pub(crate) mod test {
                         allow_index_creation: true,
                         primary_key: None,
                         documents_count: 2,
+                        indexing_limits: None,
+                        duplicate_documents: Default::default(),
+                        version_field: None,
                     },
                     canceled_by: None,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 2,
                         indexed_documents: None,
+                        duplicated_documents: None,
+                        rejected_documents: None,
+                        generated_ids: None,
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -360,6 +397,7 @@ pub(crate) mod test {
                 expires_at: Some(datetime!(4130-03-14 12:21 UTC)),
                 created_at: datetime!(1960-11-15 0:00 UTC),
                 updated_at: datetime!(2022-11-10 0:00 UTC),
+                last_used_at: None,
             },
             Key {
                 description: Some(S("The master key for everything and even the doggos")),
@@ -370,6 +408,7 @@ pub(crate) mod test {
                 expires_at: None,
                 created_at: datetime!(0000-01-01 00:01 UTC),
                 updated_at: datetime!(1964-05-04 17:25 UTC),
+                last_used_at: None,
             },
             Key {
                 description: Some(S("The useless key to for nothing nor the doggos")),
@@ -380,6 +419,7 @@ pub(crate) mod test {
                 expires_at: None,
                 created_at: datetime!(400-02-29 0:00 UTC),
                 updated_at: datetime!(1024-02-29 0:00 UTC),
+                last_used_at: None,
             },
         ]
     }
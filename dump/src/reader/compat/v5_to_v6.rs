@@ -94,6 +94,9 @@ impl CompatV5ToV6 {
                         } => v6::Kind::DocumentImport {
                             primary_key,
                             documents_count: documents_count as u64,
+                            indexing_limits: None,
+                            duplicate_documents: Default::default(),
+                            version_field: None,
                             method: match merge_strategy {
                                 v5::tasks::IndexDocumentsMethod::ReplaceDocuments => {
                                     v6::milli::update::IndexDocumentsMethod::ReplaceDocuments
@@ -132,11 +135,16 @@ impl CompatV5ToV6 {
                             v6::Details::DocumentAdditionOrUpdate {
                                 received_documents: received_documents as u64,
                                 indexed_documents,
+                                duplicated_documents: None,
+                                rejected_documents: None,
+                                generated_ids: None,
                             }
                         }
-                        v5::Details::Settings { settings } => {
-                            v6::Details::SettingsUpdate { settings: Box::new(settings.into()) }
-                        }
+                        v5::Details::Settings { settings } => v6::Details::SettingsUpdate {
+                            settings: Box::new(settings.into()),
+                            reindexed_documents: None,
+                            changed_settings: None,
+                        },
                         v5::Details::IndexInfo { primary_key } => {
                             v6::Details::IndexInfo { primary_key }
                         }
@@ -188,6 +196,7 @@ impl CompatV5ToV6 {
                 expires_at: key.expires_at,
                 created_at: key.created_at,
                 updated_at: key.updated_at,
+                last_used_at: None,
             })
         })))
     }
@@ -343,7 +352,13 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             non_separator_tokens: v6::Setting::NotSet,
             separator_tokens: v6::Setting::NotSet,
             dictionary: v6::Setting::NotSet,
+            case_sensitive: v6::Setting::NotSet,
+            languages: v6::Setting::NotSet,
+            searchable_numbers: v6::Setting::NotSet,
+            tie_breaking_sort: v6::Setting::NotSet,
+            encrypted_attributes: v6::Setting::NotSet,
             synonyms: settings.synonyms.into(),
+            bidirectional_synonyms: v6::Setting::NotSet,
             distinct_attribute: settings.distinct_attribute.into(),
             typo_tolerance: match settings.typo_tolerance {
                 v5::Setting::Set(typo) => v6::Setting::Set(v6::TypoTolerance {
@@ -377,6 +392,7 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
             },
+            pinned_in_memory: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
@@ -175,4 +175,22 @@ mod test {
         expected.sort();
         assert_eq!(all_uuids, expected);
     }
+
+    #[test]
+    fn failed_persist_leaves_no_trace() {
+        let dir = TempDir::new().unwrap();
+        let fs = FileStore::new(dir.path()).unwrap();
+        let (uuid, mut file) = fs.new_update().unwrap();
+        file.write_all(b"Hello world").unwrap();
+
+        // a directory takes the place of the update file, the rename can't succeed
+        let path = fs.get_update_path(uuid);
+        std::fs::create_dir(&path).unwrap();
+        assert!(file.persist().is_err());
+        std::fs::remove_dir(&path).unwrap();
+
+        // the temporary file has been removed with the failed update
+        assert_eq!(dir.path().read_dir().unwrap().count(), 0);
+        assert!(fs.get_update(uuid).is_err());
+    }
 }
@@ -7,10 +7,11 @@ The main function of the autobatcher is [`next_autobatch`].
 
 use std::ops::ControlFlow::{self, Break, Continue};
 
+use meilisearch_types::milli::update::DuplicateDocuments;
 use meilisearch_types::milli::update::IndexDocumentsMethod::{
     self, ReplaceDocuments, UpdateDocuments,
 };
-use meilisearch_types::tasks::TaskId;
+use meilisearch_types::tasks::{IndexingLimits, TaskId};
 
 use crate::KindWithContent;
 
@@ -546,6 +547,10 @@ pub fn autobatch(
     let mut enqueued = enqueued.into_iter();
     let (id, kind) = enqueued.next()?;
 
+    // the document additions of a batch are all indexed with the same indexing limits and
+    // handle their duplicate documents and their versions the same way.
+    let mut batch_indexing_options = document_indexing_options(&kind);
+
     // index_exist will keep track of if the index should exist at this point after the tasks we batched.
     let mut index_exist = index_already_exists;
 
@@ -558,6 +563,14 @@ pub fn autobatch(
     index_exist |= must_create_index;
 
     for (id, kind) in enqueued {
+        if let Some(indexing_options) = document_indexing_options(&kind) {
+            if *batch_indexing_options.get_or_insert_with(|| indexing_options.clone())
+                != indexing_options
+            {
+                break;
+            }
+        }
+
         acc = match acc.accumulate(id, kind.into(), index_exist, primary_key) {
             Continue(acc) => acc,
             Break(acc) => return Some((acc, must_create_index)),
@@ -567,6 +580,22 @@ pub fn autobatch(
     Some((acc, must_create_index))
 }
 
+/// Returns the indexing limits, the handling of the duplicate documents and the version field
+/// of a document addition, `None` for the other kinds of task.
+fn document_indexing_options(
+    kind: &KindWithContent,
+) -> Option<(Option<IndexingLimits>, DuplicateDocuments, Option<String>)> {
+    match kind {
+        KindWithContent::DocumentAdditionOrUpdate {
+            indexing_limits,
+            duplicate_documents,
+            version_field,
+            ..
+        } => Some((*indexing_limits, *duplicate_documents, version_field.clone())),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use meilisearch_types::tasks::IndexSwap;
@@ -598,10 +627,58 @@ mod tests {
             method,
             content_file: Uuid::new_v4(),
             documents_count: 0,
+            indexing_limits: None,
+            duplicate_documents: Default::default(),
+            version_field: None,
             allow_index_creation,
         }
     }
 
+    fn doc_imp_with_limits(max_memory: usize) -> KindWithContent {
+        KindWithContent::DocumentAdditionOrUpdate {
+            index_uid: String::from("doggo"),
+            primary_key: None,
+            method: ReplaceDocuments,
+            content_file: Uuid::new_v4(),
+            documents_count: 0,
+            indexing_limits: Some(IndexingLimits {
+                max_memory: Some(max_memory),
+                max_threads: None,
+            }),
+            duplicate_documents: Default::default(),
+            version_field: None,
+            allow_index_creation: true,
+        }
+    }
+
+    fn doc_imp_with_duplicates(duplicate_documents: DuplicateDocuments) -> KindWithContent {
+        KindWithContent::DocumentAdditionOrUpdate {
+            index_uid: String::from("doggo"),
+            primary_key: None,
+            method: ReplaceDocuments,
+            content_file: Uuid::new_v4(),
+            documents_count: 0,
+            indexing_limits: None,
+            duplicate_documents,
+            version_field: None,
+            allow_index_creation: true,
+        }
+    }
+
+    fn doc_imp_with_version(version_field: &str) -> KindWithContent {
+        KindWithContent::DocumentAdditionOrUpdate {
+            index_uid: String::from("doggo"),
+            primary_key: None,
+            method: ReplaceDocuments,
+            content_file: Uuid::new_v4(),
+            documents_count: 0,
+            indexing_limits: None,
+            duplicate_documents: Default::default(),
+            version_field: Some(version_field.to_string()),
+            allow_index_creation: true,
+        }
+    }
+
     fn doc_del() -> KindWithContent {
         KindWithContent::DocumentDeletion {
             index_uid: String::from("doggo"),
@@ -900,6 +977,33 @@ mod tests {
         debug_snapshot!(autobatch_from(false, None, [doc_del(), doc_imp(UpdateDocuments, true, None)]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
     }
 
+    #[test]
+    fn document_addition_with_different_indexing_limits() {
+        // document additions are only batched together if they share the same indexing limits
+        debug_snapshot!(autobatch_from(true, None, [doc_imp_with_limits(1024), doc_imp_with_limits(1024)]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0, 1] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp_with_limits(1024), doc_imp_with_limits(2048)]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp_with_limits(1024), doc_imp(ReplaceDocuments, true, None)]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), doc_imp_with_limits(1024)]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp_with_limits(1024), settings(true), doc_imp_with_limits(1024)]), @"Some((SettingsAndDocumentOperation { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0, 2] }, true))");
+    }
+
+    #[test]
+    fn document_addition_with_different_duplicate_documents() {
+        // document additions are only batched together if they handle their duplicates the same way
+        debug_snapshot!(autobatch_from(true, None, [doc_imp_with_duplicates(DuplicateDocuments::KeepFirst), doc_imp_with_duplicates(DuplicateDocuments::KeepFirst)]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0, 1] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp_with_duplicates(DuplicateDocuments::KeepFirst), doc_imp_with_duplicates(DuplicateDocuments::Reject)]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), doc_imp_with_duplicates(DuplicateDocuments::KeepFirst)]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), doc_imp_with_duplicates(DuplicateDocuments::Collapse)]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0, 1] }, true))");
+    }
+
+    #[test]
+    fn document_addition_with_different_version_fields() {
+        // document additions are only batched together if they use the same version field
+        debug_snapshot!(autobatch_from(true, None, [doc_imp_with_version("_version"), doc_imp_with_version("_version")]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0, 1] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp_with_version("_version"), doc_imp_with_version("rev")]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), doc_imp_with_version("_version")]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+    }
+
     #[test]
     fn autobatch_primary_key() {
         // ==> If I have a pk
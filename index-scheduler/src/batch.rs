@@ -17,32 +17,41 @@ tasks individally, but should be much faster since we are only performing
 one indexing operation.
 */
 
-use std::collections::{BTreeSet, HashSet};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::ffi::OsStr;
 use std::fmt;
 use std::fs::{self, File};
-use std::io::BufWriter;
+use std::io::{BufWriter, Seek};
+use std::sync::{Arc, Mutex};
 
 use dump::IndexMetadata;
 use log::{debug, error, info};
+use meilisearch_types::encryption::{EncryptionError, FieldCipher};
 use meilisearch_types::error::Code;
 use meilisearch_types::heed::{RoTxn, RwTxn};
-use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
+use meilisearch_types::milli::documents::{
+    obkv_to_object, DocumentsBatchBuilder, DocumentsBatchReader,
+};
 use meilisearch_types::milli::heed::CompactionOption;
 use meilisearch_types::milli::update::{
     DeleteDocuments, DocumentDeletionResult, IndexDocumentsConfig, IndexDocumentsMethod,
-    Settings as MilliSettings,
+    IndexerConfig, Setting, Settings as MilliSettings,
 };
 use meilisearch_types::milli::{self, Filter, BEU32};
 use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
-use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{
+    Details, IndexSwap, IndexingLimits, Kind, KindWithContent, Status, Task,
+};
 use meilisearch_types::{compression, Index, VERSION_FILE_NAME};
+use rayon::ThreadPool;
 use roaring::RoaringBitmap;
 use time::macros::format_description;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::autobatcher::{self, BatchKind};
+use crate::task_events::StagedEvents;
 use crate::utils::{self, swap_index_uid_in_task};
 use crate::{Error, IndexScheduler, ProcessingTasks, Result, TaskId};
 
@@ -237,6 +246,18 @@ impl IndexOperation {
     }
 }
 
+/// Serializes the settings into a JSON object keyed by the name of the settings,
+/// the settings that are not set are not part of the object.
+fn settings_to_json_object<T: serde::Serialize>(
+    settings: &Settings<T>,
+) -> Result<serde_json::Map<String, serde_json::Value>> {
+    match serde_json::to_value(settings) {
+        Ok(serde_json::Value::Object(object)) => Ok(object),
+        Ok(_) => Ok(serde_json::Map::new()),
+        Err(e) => Err(milli::Error::from(milli::InternalError::from(e)).into()),
+    }
+}
+
 impl fmt::Display for IndexOperation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -642,8 +663,10 @@ impl IndexScheduler {
                     };
 
                 let mut wtxn = self.env.write_txn()?;
+                let mut staged_events = self.task_events.staging();
                 let canceled_tasks_content_uuids = self.cancel_matched_tasks(
                     &mut wtxn,
+                    &mut staged_events,
                     task.uid,
                     matched_tasks,
                     previous_started_at,
@@ -668,6 +691,7 @@ impl IndexScheduler {
                 // files as it is not a breaking operation and we can safely continue our job.
                 match wtxn.commit() {
                     Ok(()) => {
+                        staged_events.publish();
                         for content_uuid in canceled_tasks_content_uuids {
                             if let Err(error) = self.delete_update_file(content_uuid) {
                                 error!(
@@ -764,11 +788,8 @@ impl IndexScheduler {
                 // 4. Snapshot the auth LMDB env
                 let dst = temp_snapshot_dir.path().join("auth");
                 fs::create_dir_all(&dst)?;
-                // TODO We can't use the open_auth_store_env function here but we should
-                let auth = milli::heed::EnvOpenOptions::new()
-                    .map_size(1024 * 1024 * 1024) // 1 GiB
-                    .max_dbs(2)
-                    .open(&self.auth_path)?;
+                let auth =
+                    meilisearch_auth::open_auth_store_env(&self.auth_path, self.auth_store_size)?;
                 auth.copy_to_path(dst.join("data.mdb"), CompactionOption::Enabled)?;
 
                 // 5. Copy and tarball the flat snapshot
@@ -780,9 +801,35 @@ impl IndexScheduler {
 
                 // 5.2 Tarball the content of the snapshot in a tempfile with a .snapshot extension
                 let snapshot_path = self.snapshots_path.join(format!("{}.snapshot", db_name));
-                let temp_snapshot_file = tempfile::NamedTempFile::new_in(&self.snapshots_path)?;
-                compression::to_tar_gz(temp_snapshot_dir.path(), temp_snapshot_file.path())?;
-                let file = temp_snapshot_file.persist(snapshot_path)?;
+                let file = match self.dump_and_snapshot_io_timeout {
+                    None => {
+                        let temp_snapshot_file =
+                            tempfile::NamedTempFile::new_in(&self.snapshots_path)?;
+                        compression::to_tar_gz(
+                            temp_snapshot_dir.path(),
+                            temp_snapshot_file.path(),
+                        )?;
+                        temp_snapshot_file.persist(snapshot_path)?
+                    }
+                    // The snapshots directory can be on a filesystem that hangs, the tarball is
+                    // made in the database directory and only its copy is done under the timeout.
+                    // The copy is written next to the snapshot so that moving it is atomic.
+                    Some(timeout) => {
+                        let temp_snapshot_file = tempfile::NamedTempFile::new_in(&base_path)?;
+                        compression::to_tar_gz(
+                            temp_snapshot_dir.path(),
+                            temp_snapshot_file.path(),
+                        )?;
+                        let src = temp_snapshot_file.path().to_owned();
+                        let snapshots_path = self.snapshots_path.clone();
+                        let copy = self.io_worker.run_with_timeout(
+                            "snapshot creation",
+                            timeout,
+                            move || utils::copy_to_temp_file(&src, &snapshots_path),
+                        )?;
+                        copy.persist(snapshot_path)?
+                    }
+                };
 
                 // 5.3 Change the permission to make the snapshot readonly
                 let mut permissions = file.metadata()?.permissions();
@@ -903,9 +950,32 @@ impl IndexScheduler {
                     "[year repr:full][month repr:numerical][day padding:zero]-[hour padding:zero][minute padding:zero][second padding:zero][subsecond digits:3]"
                 )).unwrap();
 
+                // The dumps directory is created on startup but may have been removed since.
+                fs::create_dir_all(&self.dumps_path)?;
                 let path = self.dumps_path.join(format!("{}.dump", dump_uid));
-                let file = File::create(path)?;
-                dump.persist_to(BufWriter::new(file))?;
+                match self.dump_and_snapshot_io_timeout {
+                    None => {
+                        let file = File::create(path)?;
+                        dump.persist_to(BufWriter::new(file))?;
+                    }
+                    // The dumps directory can be on a filesystem that hangs, the dump is written
+                    // in the database directory and only its copy is done under the timeout.
+                    // The copy is written next to the dump so that moving it is atomic.
+                    Some(timeout) => {
+                        let mut base_path = self.env.path().to_owned();
+                        base_path.pop();
+                        let temp_dump_file = tempfile::NamedTempFile::new_in(&base_path)?;
+                        dump.persist_to(BufWriter::new(temp_dump_file.as_file()))?;
+                        let src = temp_dump_file.path().to_owned();
+                        let dumps_path = self.dumps_path.clone();
+                        let copy = self.io_worker.run_with_timeout(
+                            "dump creation",
+                            timeout,
+                            move || utils::copy_to_temp_file(&src, &dumps_path),
+                        )?;
+                        copy.persist(path)?;
+                    }
+                }
 
                 // if we reached this step we can tell the scheduler we succeeded to dump ourselves.
                 task.status = Status::Succeeded;
@@ -924,9 +994,41 @@ impl IndexScheduler {
                 };
 
                 let mut index_wtxn = index.write_txn()?;
+                // the indexes created by their first operation get the default primary key
+                // unless this operation specifies its own.
+                if let Some(default_primary_key) =
+                    self.default_primary_key.clone().filter(|_| must_create_index)
+                {
+                    let requested_primary_key = match &op {
+                        IndexOperation::DocumentOperation { primary_key, .. }
+                        | IndexOperation::SettingsAndDocumentOperation { primary_key, .. } => {
+                            primary_key.as_ref()
+                        }
+                        _ => None,
+                    };
+                    if requested_primary_key.is_none() {
+                        let mut builder = MilliSettings::new(
+                            &mut index_wtxn,
+                            &index,
+                            self.index_mapper.indexer_config(),
+                        );
+                        builder.set_primary_key(default_primary_key);
+                        let must_stop_processing = self.must_stop_processing.clone();
+                        builder.execute(
+                            |indexing_step| debug!("update: {:?}", indexing_step),
+                            || must_stop_processing.get(),
+                        )?;
+                    }
+                }
                 let tasks = self.apply_index_operation(&mut index_wtxn, &index, op)?;
                 index_wtxn.commit()?;
 
+                // the pages of the index are only pinned in memory or released once a change of
+                // the setting is committed. The tasks are already processed, a failure is logged.
+                if let Err(e) = index.refresh_pinned_pages() {
+                    error!("Could not pin the index {} in memory: {}", index_uid, e);
+                }
+
                 // if the update processed successfully, we're going to store the new
                 // stats of the index. Since the tasks have already been processed and
                 // this is a non-critical operation. If it fails, we should not fail
@@ -954,6 +1056,7 @@ impl IndexScheduler {
                 }
                 self.index_mapper.create_index(wtxn, &index_uid, None)?;
 
+                let primary_key = primary_key.or_else(|| self.default_primary_key.clone());
                 self.process_batch(Batch::IndexUpdate { index_uid, primary_key, task })
             }
             Batch::IndexUpdate { index_uid, primary_key, mut task } => {
@@ -1003,7 +1106,7 @@ impl IndexScheduler {
                 Ok(vec![task])
             }
             Batch::IndexDeletion { index_uid, index_has_been_created, mut tasks } => {
-                let wtxn = self.env.write_txn()?;
+                let mut wtxn = self.env.write_txn()?;
 
                 // it's possible that the index doesn't exist
                 let number_of_documents = || -> Result<u64> {
@@ -1013,13 +1116,38 @@ impl IndexScheduler {
                 }()
                 .unwrap_or_default();
 
+                self.search_counts.remove(&mut wtxn, &index_uid)?;
+
+                // The enqueued tasks that can't recreate the index would fail, we cancel them
+                // in the same transaction so that they are only canceled if the index is deleted.
+                let deletion_task_id = tasks.iter().map(|task| task.uid).max().unwrap_or_default();
+                let mut staged_events = self.task_events.staging();
+                let canceled_tasks_content_uuids = self.cancel_tasks_of_deleted_index(
+                    &mut wtxn,
+                    &mut staged_events,
+                    &index_uid,
+                    deletion_task_id,
+                )?;
+
                 // The write transaction is directly owned and committed inside.
                 match self.index_mapper.delete_index(wtxn, &index_uid) {
-                    Ok(()) => (),
+                    Ok(()) => {
+                        staged_events.publish();
+                        self.search_counts.forget(&index_uid);
+                    }
                     Err(Error::IndexNotFound(_)) if index_has_been_created => (),
                     Err(e) => return Err(e),
                 }
 
+                for content_uuid in canceled_tasks_content_uuids {
+                    if let Err(error) = self.delete_update_file(content_uuid) {
+                        error!(
+                            "We failed deleting the content file indentified as {}: {}",
+                            content_uuid, error
+                        )
+                    }
+                }
+
                 // We set all the tasks details to the default value.
                 for task in &mut tasks {
                     task.status = Status::Succeeded;
@@ -1064,6 +1192,9 @@ impl IndexScheduler {
                     self.apply_index_swap(&mut wtxn, task.uid, &swap.indexes.0, &swap.indexes.1)?;
                 }
                 wtxn.commit()?;
+                for IndexSwap { indexes: (lhs, rhs) } in swaps {
+                    self.search_counts.swap_in_memory(lhs, rhs);
+                }
                 task.status = Status::Succeeded;
                 Ok(vec![task])
             }
@@ -1109,6 +1240,9 @@ impl IndexScheduler {
         // 6. Swap in the index mapper
         self.index_mapper.swap(wtxn, lhs, rhs)?;
 
+        // 7. Swap the persisted search counters
+        self.search_counts.swap(wtxn, lhs, rhs)?;
+
         Ok(())
     }
 
@@ -1155,7 +1289,29 @@ impl IndexScheduler {
             } => {
                 let mut primary_key_has_been_set = false;
                 let must_stop_processing = self.must_stop_processing.clone();
-                let indexer_config = self.index_mapper.indexer_config();
+
+                // The autobatcher only batches together the document additions sharing the
+                // same indexing limits.
+                let indexing_limits = tasks
+                    .iter()
+                    .find_map(|task| match task.kind {
+                        KindWithContent::DocumentAdditionOrUpdate { indexing_limits, .. } => {
+                            Some(indexing_limits)
+                        }
+                        _ => None,
+                    })
+                    .flatten();
+                let limited_indexer_config = match indexing_limits {
+                    Some(limits) => Some(limited_indexer_config(
+                        self.index_mapper.indexer_config(),
+                        limits,
+                        &self.limited_thread_pools,
+                    )?),
+                    None => None,
+                };
+                let indexer_config = limited_indexer_config
+                    .as_ref()
+                    .unwrap_or_else(|| self.index_mapper.indexer_config());
 
                 if let Some(primary_key) = primary_key {
                     match index.primary_key(index_wtxn)? {
@@ -1184,7 +1340,43 @@ impl IndexScheduler {
                     }
                 }
 
-                let config = IndexDocumentsConfig { update_method: method, ..Default::default() };
+                // The values of the encrypted fields are encrypted before being given to milli,
+                // which means that they cannot be used to identify the documents.
+                let encrypted_fields = index.encrypted_fields(index_wtxn)?;
+                let mut primary_key_is_inferred = true;
+                if let Some(primary_key) = index.primary_key(index_wtxn)? {
+                    primary_key_is_inferred = false;
+                    let encrypted_primary_key = encrypted_fields
+                        .iter()
+                        .find(|field| milli::is_faceted_by(primary_key, field));
+                    if let Some(field) = encrypted_primary_key {
+                        let error = milli::UserError::InvalidEncryptedAttribute {
+                            field: field.clone(),
+                            setting: "primaryKey",
+                        };
+                        return Err(milli::Error::from(error).into());
+                    }
+                }
+
+                // The autobatcher only batches together the document additions handling their
+                // duplicate documents and their versions the same way.
+                let (duplicate_documents, version_field) = tasks
+                    .iter()
+                    .find_map(|task| match &task.kind {
+                        KindWithContent::DocumentAdditionOrUpdate {
+                            duplicate_documents,
+                            version_field,
+                            ..
+                        } => Some((*duplicate_documents, version_field.clone())),
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+                let config = IndexDocumentsConfig {
+                    update_method: method,
+                    duplicate_documents,
+                    version_field,
+                    ..Default::default()
+                };
 
                 let mut builder = milli::update::IndexDocuments::new(
                     index_wtxn,
@@ -1199,18 +1391,53 @@ impl IndexScheduler {
                     match operation {
                         DocumentOperation::Add(content_uuid) => {
                             let content_file = self.file_store.get_update(content_uuid)?;
-                            let reader = DocumentsBatchReader::from_reader(content_file)
+                            let mut reader = DocumentsBatchReader::from_reader(content_file)
                                 .map_err(milli::Error::from)?;
+                            if !encrypted_fields.is_empty() {
+                                // The primary key of the first payload is inferred from its fields
+                                // ending with `id`, and milli rejects the payloads with several of
+                                // them, so none of these fields may be encrypted.
+                                if primary_key_is_inferred {
+                                    let encrypted_candidate = reader
+                                        .documents_batch_index()
+                                        .iter()
+                                        .map(|(_, name)| name)
+                                        .find(|name| {
+                                            name.to_lowercase().ends_with("id")
+                                                && encrypted_fields.contains(*name)
+                                        });
+                                    if let Some(field) = encrypted_candidate {
+                                        let error = milli::UserError::InvalidEncryptedAttribute {
+                                            field: field.clone(),
+                                            setting: "primaryKey",
+                                        };
+                                        return Err(milli::Error::from(error).into());
+                                    }
+                                    primary_key_is_inferred = false;
+                                }
+                                let cipher = self
+                                    .field_cipher
+                                    .as_ref()
+                                    .ok_or(EncryptionError::MissingKey)?;
+                                reader = encrypt_documents(cipher, &encrypted_fields, reader)?;
+                            }
+                            let previous_duplicates = builder.duplicated_documents();
+                            let previous_rejections = builder.rejected_documents();
                             let (new_builder, user_result) = builder.add_documents(reader)?;
                             builder = new_builder;
+                            let duplicated_documents =
+                                builder.duplicated_documents() - previous_duplicates;
+                            let rejected_documents =
+                                builder.rejected_documents() - previous_rejections;
 
-                            let received_documents =
+                            let (received_documents, generated_ids) =
                                 if let Some(Details::DocumentAdditionOrUpdate {
                                     received_documents,
+                                    generated_ids,
                                     ..
                                 }) = task.details
                                 {
-                                    received_documents
+                                    (received_documents, generated_ids)
                                 } else {
                                     // In the case of a `documentAdditionOrUpdate` the details MUST be set
                                     unreachable!();
@@ -1222,6 +1449,11 @@ impl IndexScheduler {
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(count),
+                                        duplicated_documents: (duplicated_documents != 0)
+                                            .then_some(duplicated_documents),
+                                        rejected_documents: (rejected_documents != 0)
+                                            .then_some(rejected_documents),
+                                        generated_ids,
                                     })
                                 }
                                 Err(e) => {
@@ -1229,6 +1461,9 @@ impl IndexScheduler {
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(0),
+                                        duplicated_documents: None,
+                                        rejected_documents: None,
+                                        generated_ids,
                                     });
                                     task.error = Some(milli::Error::from(e).into());
                                 }
@@ -1347,13 +1582,25 @@ impl IndexScheduler {
                 Ok(vec![task])
             }
             IndexOperation::Settings { index_uid: _, settings, mut tasks } => {
+                let previous_settings = settings_to_json_object(
+                    &meilisearch_types::settings::settings(index, index_wtxn)?,
+                )?;
                 let indexer_config = self.index_mapper.indexer_config();
                 let mut builder = milli::update::Settings::new(index_wtxn, index, indexer_config);
 
                 for (task, (_, settings)) in tasks.iter_mut().zip(settings) {
                     let checked_settings = settings.clone().check();
-                    task.details = Some(Details::SettingsUpdate { settings: Box::new(settings) });
+                    if let Setting::Set(fields) = &checked_settings.encrypted_attributes {
+                        if !fields.is_empty() && self.field_cipher.is_none() {
+                            return Err(EncryptionError::MissingKey.into());
+                        }
+                    }
                     apply_settings_to_builder(&checked_settings, &mut builder);
+                    task.details = Some(Details::SettingsUpdate {
+                        settings: Box::new(settings),
+                        reindexed_documents: None,
+                        changed_settings: None,
+                    });
 
                     // We can apply the status right now and if an update fail later
                     // the whole batch will be marked as failed.
@@ -1361,11 +1608,33 @@ impl IndexScheduler {
                 }
 
                 let must_stop_processing = self.must_stop_processing.clone();
-                builder.execute(
+                let reindexed_documents = builder.execute(
                     |indexing_step| debug!("update: {:?}", indexing_step),
                     || must_stop_processing.get(),
                 )?;
 
+                let new_settings = settings_to_json_object(
+                    &meilisearch_types::settings::settings(index, index_wtxn)?,
+                )?;
+
+                // All the settings of the batch are applied by a single reindex.
+                for task in tasks.iter_mut() {
+                    if let Some(Details::SettingsUpdate {
+                        settings,
+                        reindexed_documents: count,
+                        changed_settings,
+                    }) = &mut task.details
+                    {
+                        *count = Some(reindexed_documents);
+                        let changed = settings_to_json_object(settings.as_ref())?
+                            .into_iter()
+                            .map(|(name, _)| name)
+                            .filter(|name| previous_settings.get(name) != new_settings.get(name))
+                            .collect();
+                        *changed_settings = Some(changed);
+                    }
+                }
+
                 Ok(tasks)
             }
             IndexOperation::SettingsAndDocumentOperation {
@@ -1511,6 +1780,7 @@ impl IndexScheduler {
     fn cancel_matched_tasks(
         &self,
         wtxn: &mut RwTxn,
+        staged_events: &mut StagedEvents,
         cancel_task_id: TaskId,
         matched_tasks: &RoaringBitmap,
         previous_started_at: OffsetDateTime,
@@ -1537,12 +1807,131 @@ impl IndexScheduler {
             task.canceled_by = Some(cancel_task_id);
             task.finished_at = Some(now);
             task.details = task.details.map(|d| d.to_failed());
-            self.update_task(wtxn, &task)?;
+            self.update_task(wtxn, staged_events, &task)?;
         }
         self.canceled_by.put(wtxn, &BEU32::new(cancel_task_id), &tasks_to_cancel)?;
 
         Ok(content_files_to_delete)
     }
+
+    /// Cancel the enqueued tasks of a deleted index that would fail because they can't recreate it.
+    ///
+    /// The tasks registered after a task recreating the index are left untouched.
+    ///
+    /// Returns the content files that the transaction owner must delete if the commit is successful.
+    fn cancel_tasks_of_deleted_index(
+        &self,
+        wtxn: &mut RwTxn,
+        staged_events: &mut StagedEvents,
+        index_uid: &str,
+        deletion_task_id: TaskId,
+    ) -> Result<Vec<Uuid>> {
+        let now = OffsetDateTime::now_utc();
+
+        let mut enqueued_tasks =
+            self.get_status(wtxn, Status::Enqueued)? & self.index_tasks(wtxn, index_uid)?;
+        enqueued_tasks.remove_range(..=deletion_task_id);
+
+        let mut canceled_tasks = RoaringBitmap::new();
+        let mut content_files_to_delete = Vec::new();
+        for mut task in self.get_existing_tasks(wtxn, enqueued_tasks.iter())? {
+            if task.kind.can_create_index() {
+                break;
+            }
+            // An index swap also involves other indexes, it fails by itself.
+            if matches!(task.kind, KindWithContent::IndexSwap { .. }) {
+                continue;
+            }
+            if let Some(uuid) = task.content_uuid() {
+                content_files_to_delete.push(uuid);
+            }
+            let error = Error::IndexDeletedBeforeProcessing {
+                index_uid: index_uid.to_string(),
+                deletion_task: deletion_task_id,
+            };
+            task.status = Status::Canceled;
+            task.canceled_by = Some(deletion_task_id);
+            task.finished_at = Some(now);
+            task.error = Some(error.into());
+            task.details = task.details.map(|d| d.to_failed());
+            self.update_task(wtxn, staged_events, &task)?;
+            canceled_tasks.insert(task.uid);
+        }
+        if !canceled_tasks.is_empty() {
+            self.canceled_by.put(wtxn, &BEU32::new(deletion_task_id), &canceled_tasks)?;
+        }
+
+        Ok(content_files_to_delete)
+    }
+}
+
+/// Returns a copy of the server indexer config restricted by the indexing limits of a task.
+///
+/// The limits of a task can only lower the memory and the number of threads used by the server.
+/// The thread pools are built once per number of threads and kept in `thread_pools`.
+fn limited_indexer_config(
+    config: &IndexerConfig,
+    limits: IndexingLimits,
+    thread_pools: &Mutex<HashMap<usize, Arc<ThreadPool>>>,
+) -> Result<IndexerConfig> {
+    let max_memory = match (config.max_memory, limits.max_memory) {
+        (Some(server), Some(task)) => Some(server.min(task)),
+        (server, task) => server.or(task),
+    };
+
+    let server_threads = config
+        .thread_pool
+        .as_ref()
+        .map_or_else(rayon::current_num_threads, |pool| pool.current_num_threads());
+    let num_threads = limits.max_threads.map_or(server_threads, |task| task.min(server_threads));
+    let thread_pool = match &config.thread_pool {
+        Some(pool) if num_threads == server_threads => pool.clone(),
+        _ => {
+            let mut thread_pools = thread_pools.lock().unwrap();
+            match thread_pools.entry(num_threads) {
+                Entry::Occupied(entry) => entry.get().clone(),
+                Entry::Vacant(entry) => {
+                    let thread_pool = rayon::ThreadPoolBuilder::new()
+                        .thread_name(|index| format!("indexing-thread:{index}"))
+                        .num_threads(num_threads)
+                        .build()
+                        .map_err(anyhow::Error::from)?;
+                    entry.insert(Arc::new(thread_pool)).clone()
+                }
+            }
+        }
+    };
+
+    Ok(IndexerConfig {
+        log_every_n: config.log_every_n,
+        max_nb_chunks: config.max_nb_chunks,
+        documents_chunk_size: config.documents_chunk_size,
+        max_memory,
+        chunk_compression_type: config.chunk_compression_type,
+        chunk_compression_level: config.chunk_compression_level,
+        thread_pool: Some(thread_pool),
+        max_positions_per_attributes: config.max_positions_per_attributes,
+        skip_index_budget: config.skip_index_budget,
+    })
+}
+
+/// Returns a new batch of documents in which the values of the `encrypted_fields` are encrypted.
+fn encrypt_documents(
+    cipher: &FieldCipher,
+    encrypted_fields: &BTreeSet<String>,
+    reader: DocumentsBatchReader<File>,
+) -> Result<DocumentsBatchReader<File>> {
+    let (mut cursor, documents_batch_index) = reader.into_cursor_and_fields_index();
+    let mut builder = DocumentsBatchBuilder::new(BufWriter::new(tempfile::tempfile()?));
+    while let Some(doc) = cursor.next_document().map_err(milli::Error::from)? {
+        let mut document = obkv_to_object(&doc, &documents_batch_index)?;
+        cipher.encrypt_document(encrypted_fields, &mut document)?;
+        builder.append_json_object(&document)?;
+    }
+
+    let mut file = builder.into_inner()?.into_inner().map_err(|e| e.into_error())?;
+    file.rewind()?;
+    Ok(DocumentsBatchReader::from_reader(file).map_err(milli::Error::from)?)
 }
 
 fn delete_document_by_filter<'a>(
@@ -1,5 +1,7 @@
 use std::fmt::Display;
+use std::time::Duration;
 
+use meilisearch_types::encryption::EncryptionError;
 use meilisearch_types::error::{Code, ErrorCode};
 use meilisearch_types::tasks::{Kind, Status};
 use meilisearch_types::{heed, milli};
@@ -52,6 +54,10 @@ pub enum Error {
     IndexNotFound(String),
     #[error("Index `{0}` already exists.")]
     IndexAlreadyExists(String),
+    #[error("Index `{0}` not found. The API key used is not allowed to create it, the index must be created first or the API key must be given the `indexes.create` action.")]
+    IndexNotFoundWithoutCreation(String),
+    #[error("Index `{index_uid}` was deleted by the task `{deletion_task}` before this task could be processed.")]
+    IndexDeletedBeforeProcessing { index_uid: String, deletion_task: TaskId },
     #[error(
         "Indexes must be declared only once during a swap. `{0}` was specified several times."
     )]
@@ -123,8 +129,12 @@ pub enum Error {
     IoError(#[from] std::io::Error),
     #[error(transparent)]
     Persist(#[from] tempfile::PersistError),
+    #[error("The {operation} did not complete its file operations within {timeout:?}.")]
+    IoTimeout { operation: &'static str, timeout: Duration },
     #[error(transparent)]
     FeatureNotEnabled(#[from] FeatureNotEnabledError),
+    #[error(transparent)]
+    Encryption(#[from] EncryptionError),
 
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
@@ -160,6 +170,8 @@ impl Error {
             Error::IndexNotFound(_)
             | Error::WithCustomErrorCode(_, _)
             | Error::IndexAlreadyExists(_)
+            | Error::IndexNotFoundWithoutCreation(_)
+            | Error::IndexDeletedBeforeProcessing { .. }
             | Error::SwapDuplicateIndexFound(_)
             | Error::SwapDuplicateIndexesFound(_)
             | Error::SwapIndexNotFound(_)
@@ -182,7 +194,9 @@ impl Error {
             | Error::FileStore(_)
             | Error::IoError(_)
             | Error::Persist(_)
+            | Error::IoTimeout { .. }
             | Error::FeatureNotEnabled(_)
+            | Error::Encryption(_)
             | Error::Anyhow(_) => true,
             Error::CreateBatch(_)
             | Error::CorruptedTaskQueue
@@ -204,6 +218,8 @@ impl ErrorCode for Error {
             Error::WithCustomErrorCode(code, _) => *code,
             Error::IndexNotFound(_) => Code::IndexNotFound,
             Error::IndexAlreadyExists(_) => Code::IndexAlreadyExists,
+            Error::IndexNotFoundWithoutCreation(_) => Code::IndexNotFound,
+            Error::IndexDeletedBeforeProcessing { .. } => Code::IndexNotFound,
             Error::SwapDuplicateIndexesFound(_) => Code::InvalidSwapDuplicateIndexFound,
             Error::SwapDuplicateIndexFound(_) => Code::InvalidSwapDuplicateIndexFound,
             Error::SwapIndexNotFound(_) => Code::IndexNotFound,
@@ -227,7 +243,9 @@ impl ErrorCode for Error {
             Error::FileStore(e) => e.error_code(),
             Error::IoError(e) => e.error_code(),
             Error::Persist(e) => e.error_code(),
+            Error::IoTimeout { .. } => Code::IoTimeout,
             Error::FeatureNotEnabled(_) => Code::FeatureNotEnabled,
+            Error::Encryption(e) => e.error_code(),
 
             // Irrecoverable errors
             Error::Anyhow(_) => Code::Internal,
@@ -1,4 +1,6 @@
-use std::path::PathBuf;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use std::{fs, thread};
@@ -21,6 +23,7 @@ mod index_map;
 
 const INDEX_MAPPING: &str = "index-mapping";
 const INDEX_STATS: &str = "index-stats";
+const INDEX_DISPLAY_NAMES: &str = "index-display-names";
 
 /// Structure managing meilisearch's indexes.
 ///
@@ -54,6 +57,8 @@ pub struct IndexMapper {
 
     /// Map an index name with an index uuid currently available on disk.
     pub(crate) index_mapping: Database<Str, UuidCodec>,
+    /// Map the name an index is stored under with the name it was given, when they differ.
+    index_display_names: Database<Str, Str>,
     /// Map an index UUID with the cached stats associated to the index.
     ///
     /// Using an UUID forces to use the index_mapping table to recover the index behind a name, ensuring
@@ -68,6 +73,8 @@ pub struct IndexMapper {
     index_growth_amount: usize,
     /// Whether we open a meilisearch index with the MDB_WRITEMAP option or not.
     enable_mdb_writemap: bool,
+    /// Whether the index names only differing by their case designate the same index.
+    case_insensitive_index_uids: bool,
     pub indexer_config: Arc<IndexerConfig>,
 }
 
@@ -135,25 +142,75 @@ impl IndexMapper {
         index_growth_amount: usize,
         index_count: usize,
         enable_mdb_writemap: bool,
+        case_insensitive_index_uids: bool,
         indexer_config: IndexerConfig,
     ) -> Result<Self> {
         let mut wtxn = env.write_txn()?;
         let index_mapping = env.create_database(&mut wtxn, Some(INDEX_MAPPING))?;
         let index_stats = env.create_database(&mut wtxn, Some(INDEX_STATS))?;
+        let index_display_names = env.create_database(&mut wtxn, Some(INDEX_DISPLAY_NAMES))?;
         wtxn.commit()?;
 
         Ok(Self {
             index_map: Arc::new(RwLock::new(IndexMap::new(index_count))),
             index_mapping,
             index_stats,
+            index_display_names,
             base_path,
             index_base_map_size,
             index_growth_amount,
             enable_mdb_writemap,
+            case_insensitive_index_uids,
             indexer_config: Arc::new(indexer_config),
         })
     }
 
+    /// Returns the name under which the index is stored in the mapping table.
+    ///
+    /// When the index uids are case-insensitive, the indexes are stored under their lowercased uid.
+    pub fn canonical_name<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        if self.case_insensitive_index_uids {
+            Cow::Owned(name.to_ascii_lowercase())
+        } else {
+            Cow::Borrowed(name)
+        }
+    }
+
+    /// Remembers the name an index was given so that it is displayed with its original casing.
+    ///
+    /// Only the last name given to an index that does not exist yet is kept.
+    pub fn set_display_name(&self, wtxn: &mut RwTxn, name: &str) -> Result<()> {
+        let canonical_name = self.canonical_name(name);
+        if self.exists(wtxn, &canonical_name)? {
+            return Ok(());
+        }
+        if canonical_name == name {
+            self.index_display_names.delete(wtxn, &canonical_name)?;
+        } else {
+            self.index_display_names.put(wtxn, &canonical_name, name)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the name to display for the index stored under the given name.
+    pub fn display_name(&self, rtxn: &RoTxn, name: &str) -> Result<String> {
+        if !self.case_insensitive_index_uids {
+            return Ok(name.to_string());
+        }
+        let canonical_name = self.canonical_name(name);
+        Ok(match self.index_display_names.get(rtxn, &canonical_name)? {
+            Some(display_name) => display_name.to_string(),
+            None => canonical_name.into_owned(),
+        })
+    }
+
+    /// Returns the uuid of the index, or an `IndexNotFound` error if there is none.
+    fn index_uuid(&self, rtxn: &RoTxn, name: &str) -> Result<Uuid> {
+        self.index_mapping
+            .get(rtxn, &self.canonical_name(name))?
+            .ok_or_else(|| Error::IndexNotFound(name.to_string()))
+    }
+
     /// Get or create the index.
     pub fn create_index(
         &self,
@@ -168,7 +225,13 @@ impl IndexMapper {
             }
             Err(Error::IndexNotFound(_)) => {
                 let uuid = Uuid::new_v4();
-                self.index_mapping.put(&mut wtxn, name, &uuid)?;
+                let canonical_name = self.canonical_name(name);
+                // The tasks refer to the index by its canonical name, the name it was given is
+                // then the one remembered when they were registered.
+                if canonical_name != name {
+                    self.index_display_names.put(&mut wtxn, &canonical_name, name)?;
+                }
+                self.index_mapping.put(&mut wtxn, &canonical_name, &uuid)?;
 
                 let index_path = self.base_path.join(uuid.to_string());
                 fs::create_dir_all(&index_path)?;
@@ -195,16 +258,14 @@ impl IndexMapper {
     /// Removes the index from the mapping table and the in-memory index map
     /// but keeps the associated tasks.
     pub fn delete_index(&self, mut wtxn: RwTxn, name: &str) -> Result<()> {
-        let uuid = self
-            .index_mapping
-            .get(&wtxn, name)?
-            .ok_or_else(|| Error::IndexNotFound(name.to_string()))?;
+        let uuid = self.index_uuid(&wtxn, name)?;
 
         // Not an error if the index had no stats in cache.
         self.index_stats.delete(&mut wtxn, &uuid)?;
 
         // Once we retrieved the UUID of the index we remove it from the mapping table.
-        assert!(self.index_mapping.delete(&mut wtxn, name)?);
+        assert!(self.index_mapping.delete(&mut wtxn, &self.canonical_name(name))?);
+        self.index_display_names.delete(&mut wtxn, &self.canonical_name(name))?;
 
         wtxn.commit()?;
 
@@ -274,7 +335,7 @@ impl IndexMapper {
     }
 
     pub fn exists(&self, rtxn: &RoTxn, name: &str) -> Result<bool> {
-        Ok(self.index_mapping.get(rtxn, name)?.is_some())
+        Ok(self.index_mapping.get(rtxn, &self.canonical_name(name))?.is_some())
     }
 
     /// Resizes the maximum size of the specified index to the double of its current maximum size.
@@ -286,10 +347,7 @@ impl IndexMapper {
     /// - If the Index corresponding to the passed name is concurrently being deleted/resized or cannot be found in the
     ///   in memory hash map.
     pub fn resize_index(&self, rtxn: &RoTxn, name: &str) -> Result<()> {
-        let uuid = self
-            .index_mapping
-            .get(rtxn, name)?
-            .ok_or_else(|| Error::IndexNotFound(name.to_string()))?;
+        let uuid = self.index_uuid(rtxn, name)?;
 
         // We remove the index from the in-memory index map.
         self.index_map.write().unwrap().close_for_resize(
@@ -303,10 +361,7 @@ impl IndexMapper {
 
     /// Return an index, may open it if it wasn't already opened.
     pub fn index(&self, rtxn: &RoTxn, name: &str) -> Result<Index> {
-        let uuid = self
-            .index_mapping
-            .get(rtxn, name)?
-            .ok_or_else(|| Error::IndexNotFound(name.to_string()))?;
+        let uuid = self.index_uuid(rtxn, name)?;
 
         let mut tries = 0;
         // attempts to open the index in a loop.
@@ -397,8 +452,10 @@ impl IndexMapper {
         self.index_mapping
             .iter(rtxn)?
             .map(|res| {
-                res.map_err(Error::from)
-                    .and_then(|(name, _)| self.index(rtxn, name).and_then(|index| f(name, &index)))
+                res.map_err(Error::from).and_then(|(name, _)| {
+                    let index = self.index(rtxn, name)?;
+                    f(&self.display_name(rtxn, name)?, &index)
+                })
             })
             .collect()
     }
@@ -407,23 +464,17 @@ impl IndexMapper {
     pub fn index_names(&self, rtxn: &RoTxn) -> Result<Vec<String>> {
         self.index_mapping
             .iter(rtxn)?
-            .map(|res| res.map_err(Error::from).map(|(name, _)| name.to_string()))
+            .map(|res| res.map_err(Error::from).and_then(|(name, _)| self.display_name(rtxn, name)))
             .collect()
     }
 
     /// Swap two index names.
     pub fn swap(&self, wtxn: &mut RwTxn, lhs: &str, rhs: &str) -> Result<()> {
-        let lhs_uuid = self
-            .index_mapping
-            .get(wtxn, lhs)?
-            .ok_or_else(|| Error::IndexNotFound(lhs.to_string()))?;
-        let rhs_uuid = self
-            .index_mapping
-            .get(wtxn, rhs)?
-            .ok_or_else(|| Error::IndexNotFound(rhs.to_string()))?;
-
-        self.index_mapping.put(wtxn, lhs, &rhs_uuid)?;
-        self.index_mapping.put(wtxn, rhs, &lhs_uuid)?;
+        let lhs_uuid = self.index_uuid(wtxn, lhs)?;
+        let rhs_uuid = self.index_uuid(wtxn, rhs)?;
+
+        self.index_mapping.put(wtxn, &self.canonical_name(lhs), &rhs_uuid)?;
+        self.index_mapping.put(wtxn, &self.canonical_name(rhs), &lhs_uuid)?;
 
         Ok(())
     }
@@ -434,10 +485,7 @@ impl IndexMapper {
     /// Otherwise, the `Index` is opened to compute the stats on the fly (the result is not cached).
     /// The stats for an index are cached after each `Index` update.
     pub fn stats_of(&self, rtxn: &RoTxn, index_uid: &str) -> Result<IndexStats> {
-        let uuid = self
-            .index_mapping
-            .get(rtxn, index_uid)?
-            .ok_or_else(|| Error::IndexNotFound(index_uid.to_string()))?;
+        let uuid = self.index_uuid(rtxn, index_uid)?;
 
         match self.index_stats.get(rtxn, &uuid)? {
             Some(stats) => Ok(stats),
@@ -449,6 +497,21 @@ impl IndexMapper {
         }
     }
 
+    /// Returns an estimation of the memory used by an index: the size of the pages
+    /// of its memory-mapped database that are currently resident in memory.
+    ///
+    /// It is always `0` for the indexes that are not opened and on the platforms other than Linux.
+    pub fn memory_size_of(
+        &self,
+        rtxn: &RoTxn,
+        index_uid: &str,
+        memory_sizes: &MemorySizes,
+    ) -> Result<u64> {
+        let uuid = self.index_uuid(rtxn, index_uid)?;
+
+        Ok(memory_sizes.of(&self.base_path.join(uuid.to_string()).join("data.mdb")))
+    }
+
     /// Stores the new stats for an index.
     ///
     /// Expected usage is to compute the stats the index using `IndexStats::new`, the pass it to this function.
@@ -458,20 +521,65 @@ impl IndexMapper {
         index_uid: &str,
         stats: &IndexStats,
     ) -> Result<()> {
-        let uuid = self
-            .index_mapping
-            .get(wtxn, index_uid)?
-            .ok_or_else(|| Error::IndexNotFound(index_uid.to_string()))?;
+        let uuid = self.index_uuid(wtxn, index_uid)?;
 
         self.index_stats.put(wtxn, &uuid, stats)?;
         Ok(())
     }
 
     pub fn index_exists(&self, rtxn: &RoTxn, name: &str) -> Result<bool> {
-        Ok(self.index_mapping.get(rtxn, name)?.is_some())
+        Ok(self.index_mapping.get(rtxn, &self.canonical_name(name))?.is_some())
     }
 
     pub fn indexer_config(&self) -> &IndexerConfig {
         &self.indexer_config
     }
 }
+
+/// The size of the pages of the memory-mapped files of the process that are resident in memory.
+///
+/// Reading it is costly, it must be read once and shared when computing the stats of many indexes.
+#[derive(Debug, Default)]
+pub struct MemorySizes(HashMap<String, u64>);
+
+impl MemorySizes {
+    /// Sums the resident size of the mappings of every file, as reported by `/proc/self/smaps`.
+    #[cfg(target_os = "linux")]
+    pub fn read() -> Result<Self> {
+        let smaps = fs::read_to_string("/proc/self/smaps")?;
+
+        let mut sizes = HashMap::new();
+        let mut mapped_file = None;
+        for line in smaps.lines() {
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                // the attributes of a mapping, e.g. `Rss:   1024 kB`
+                Some("Rss:") => {
+                    if let Some(path) = mapped_file {
+                        let kb: u64 = tokens.next().and_then(|kb| kb.parse().ok()).unwrap_or(0);
+                        *sizes.entry(String::from(path)).or_default() += kb * 1024;
+                    }
+                }
+                Some(attribute) if attribute.ends_with(':') => (),
+                // the header of a mapping, ending with the absolute path of the mapped file
+                Some(_) => mapped_file = line.find('/').map(|start| &line[start..]),
+                None => (),
+            }
+        }
+
+        Ok(MemorySizes(sizes))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn read() -> Result<Self> {
+        Ok(MemorySizes::default())
+    }
+
+    /// Returns the resident size of the mappings of the given file.
+    fn of(&self, path: &Path) -> u64 {
+        match path.canonicalize() {
+            Ok(path) => self.0.get(&*path.to_string_lossy()).copied().unwrap_or(0),
+            Err(_) => 0,
+        }
+    }
+}
@@ -29,12 +29,23 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
         finished_at,
         index_mapper,
         features: _,
+        search_counts: _,
+        task_events: _,
         max_number_of_tasks: _,
+        default_primary_key: _,
+        field_cipher: _,
+        max_facets_per_search: _,
+        default_documents_limit: _,
+        max_documents_limit: _,
+        dump_and_snapshot_io_timeout: _,
+        io_worker: _,
+        limited_thread_pools: _,
         puffin_frame: _,
         wake_up: _,
         dumps_path: _,
         snapshots_path: _,
         auth_path: _,
+        auth_store_size: _,
         version_file_path: _,
         test_breakpoint_sdr: _,
         planned_failures: _,
@@ -173,12 +184,33 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentAdditionOrUpdate {
             received_documents,
             indexed_documents,
+            duplicated_documents,
+            rejected_documents,
+            generated_ids,
         } => {
-            format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?} }}")
+            let mut snap = format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?}");
+            if let Some(duplicated_documents) = duplicated_documents {
+                snap.push_str(&format!(", duplicated_documents: {duplicated_documents}"));
+            }
+            if let Some(rejected_documents) = rejected_documents {
+                snap.push_str(&format!(", rejected_documents: {rejected_documents}"));
+            }
+            if let Some(generated_ids) = generated_ids {
+                snap.push_str(&format!(", generated_ids: {generated_ids}"));
+            }
+            snap.push_str(" }");
+            snap
         }
-        Details::SettingsUpdate { settings } => {
+        Details::SettingsUpdate { settings, reindexed_documents: None, changed_settings: _ } => {
             format!("{{ settings: {settings:?} }}")
         }
+        Details::SettingsUpdate {
+            settings,
+            reindexed_documents: Some(reindexed_documents),
+            changed_settings: _,
+        } => {
+            format!("{{ settings: {settings:?}, reindexed_documents: {reindexed_documents} }}")
+        }
         Details::IndexInfo { primary_key } => {
             format!("{{ primary_key: {primary_key:?} }}")
         }
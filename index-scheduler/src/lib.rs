@@ -26,6 +26,8 @@ mod index_mapper;
 #[cfg(test)]
 mod insta_snapshot;
 mod lru;
+mod search_counts;
+mod task_events;
 mod utils;
 mod uuid_codec;
 
@@ -38,13 +40,15 @@ use std::ops::{Bound, RangeBounds};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::Relaxed;
-use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use dump::{KindDump, TaskDump, UpdateFile};
 pub use error::Error;
 pub use features::RoFeatures;
 use file_store::FileStore;
+pub use index_mapper::MemorySizes;
+use meilisearch_types::encryption::FieldCipher;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::features::{InstanceTogglableFeatures, RuntimeTogglableFeatures};
 use meilisearch_types::heed::types::{OwnedType, SerdeBincode, SerdeJson, Str};
@@ -52,10 +56,12 @@ use meilisearch_types::heed::{self, Database, Env, RoTxn, RwTxn};
 use meilisearch_types::milli::documents::DocumentsBatchBuilder;
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::{self, CboRoaringBitmapCodec, Index, RoaringBitmapCodec, BEU32};
-use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Details, Kind, KindWithContent, Status, Task};
 use puffin::FrameView;
+use rayon::ThreadPool;
 use roaring::RoaringBitmap;
 use synchronoise::SignalEvent;
+pub use task_events::TaskEvent;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 use utils::{filter_out_references_to_newer_tasks, keep_tasks_within_datetimes, map_bound};
@@ -230,6 +236,8 @@ pub struct IndexSchedulerOptions {
     pub version_file_path: PathBuf,
     /// The path to the folder containing the auth LMDB env.
     pub auth_path: PathBuf,
+    /// The maximum size, in bytes, of the auth LMDB env.
+    pub auth_store_size: usize,
     /// The path to the folder containing the task databases.
     pub tasks_path: PathBuf,
     /// The path to the file store containing the files associated to the tasks.
@@ -258,6 +266,20 @@ pub struct IndexSchedulerOptions {
     /// The maximum number of tasks stored in the task queue before starting
     /// to auto schedule task deletions.
     pub max_number_of_tasks: usize,
+    /// The primary key given to the indexes created without one.
+    pub default_primary_key: Option<String>,
+    /// Set to `true` iff the index uids only differing by their case designate the same index.
+    pub case_insensitive_index_uids: bool,
+    /// The maximum number of facets a single search can request, unlimited if `None`.
+    pub max_facets_per_search: Option<usize>,
+    /// The number of documents returned by a documents browse not specifying a limit.
+    pub default_documents_limit: usize,
+    /// The maximum number of documents returned by a documents browse, unlimited if `None`.
+    pub max_documents_limit: Option<usize>,
+    /// The time after which a file operation of a dump or snapshot creation is considered hanging.
+    pub dump_and_snapshot_io_timeout: Option<Duration>,
+    /// The secret from which the key encrypting the `encryptedAttributes` is derived.
+    pub encryption_key: Option<String>,
     /// The experimental features enabled for this instance.
     pub instance_features: InstanceTogglableFeatures,
 }
@@ -306,6 +328,12 @@ pub struct IndexScheduler {
     /// In charge of fetching and setting the status of experimental features.
     features: features::FeatureData,
 
+    /// Count the number of searches performed on each index.
+    search_counts: search_counts::SearchCounts,
+
+    /// The recent status transitions of the tasks.
+    task_events: task_events::TaskEvents,
+
     /// Get a signal when a batch needs to be processed.
     pub(crate) wake_up: Arc<SignalEvent>,
 
@@ -316,6 +344,31 @@ pub struct IndexScheduler {
     /// the finished tasks automatically.
     pub(crate) max_number_of_tasks: usize,
 
+    /// The primary key given to the indexes created without one.
+    pub(crate) default_primary_key: Option<String>,
+
+    /// Encrypts the values of the `encryptedAttributes` of the indexes.
+    pub(crate) field_cipher: Option<FieldCipher>,
+
+    /// The maximum number of facets a single search can request.
+    pub(crate) max_facets_per_search: Option<usize>,
+
+    /// The number of documents returned by a documents browse not specifying a limit.
+    pub(crate) default_documents_limit: usize,
+
+    /// The maximum number of documents returned by a documents browse.
+    pub(crate) max_documents_limit: Option<usize>,
+
+    /// The time after which a file operation of a dump or snapshot creation is considered hanging.
+    pub(crate) dump_and_snapshot_io_timeout: Option<Duration>,
+
+    /// The thread running the file operations of the dump and snapshot creations under a timeout.
+    pub(crate) io_worker: Arc<utils::IoWorker>,
+
+    /// The thread pools of the document additions limiting their number of indexing threads,
+    /// by number of threads.
+    pub(crate) limited_thread_pools: Arc<Mutex<HashMap<usize, Arc<ThreadPool>>>>,
+
     /// A frame to output the indexation profiling files to disk.
     pub(crate) puffin_frame: Arc<puffin::GlobalFrameView>,
 
@@ -328,6 +381,9 @@ pub struct IndexScheduler {
     /// The path to the folder containing the auth LMDB env.
     pub(crate) auth_path: PathBuf,
 
+    /// The maximum size, in bytes, of the auth LMDB env.
+    pub(crate) auth_store_size: usize,
+
     /// The path to the version file of Meilisearch.
     pub(crate) version_file_path: PathBuf,
 
@@ -369,10 +425,19 @@ impl IndexScheduler {
             wake_up: self.wake_up.clone(),
             autobatching_enabled: self.autobatching_enabled,
             max_number_of_tasks: self.max_number_of_tasks,
+            default_primary_key: self.default_primary_key.clone(),
+            field_cipher: self.field_cipher.clone(),
+            max_facets_per_search: self.max_facets_per_search,
+            default_documents_limit: self.default_documents_limit,
+            max_documents_limit: self.max_documents_limit,
+            dump_and_snapshot_io_timeout: self.dump_and_snapshot_io_timeout,
+            io_worker: self.io_worker.clone(),
+            limited_thread_pools: self.limited_thread_pools.clone(),
             puffin_frame: self.puffin_frame.clone(),
             snapshots_path: self.snapshots_path.clone(),
             dumps_path: self.dumps_path.clone(),
             auth_path: self.auth_path.clone(),
+            auth_store_size: self.auth_store_size,
             version_file_path: self.version_file_path.clone(),
             #[cfg(test)]
             test_breakpoint_sdr: self.test_breakpoint_sdr.clone(),
@@ -381,6 +446,8 @@ impl IndexScheduler {
             #[cfg(test)]
             run_loop_iteration: self.run_loop_iteration.clone(),
             features: self.features.clone(),
+            search_counts: self.search_counts.clone(),
+            task_events: self.task_events.clone(),
         }
     }
 }
@@ -419,11 +486,12 @@ impl IndexScheduler {
         };
 
         let env = heed::EnvOpenOptions::new()
-            .max_dbs(11)
+            .max_dbs(13)
             .map_size(budget.task_db_size)
             .open(options.tasks_path)?;
 
         let features = features::FeatureData::new(&env, options.instance_features)?;
+        let search_counts = search_counts::SearchCounts::new(&env)?;
 
         let file_store = FileStore::new(&options.update_file_path)?;
 
@@ -458,6 +526,7 @@ impl IndexScheduler {
                 options.index_growth_amount,
                 budget.index_count,
                 options.enable_mdb_writemap,
+                options.case_insensitive_index_uids,
                 options.indexer_config,
             )?,
             env,
@@ -466,9 +535,18 @@ impl IndexScheduler {
             puffin_frame: Arc::new(puffin::GlobalFrameView::default()),
             autobatching_enabled: options.autobatching_enabled,
             max_number_of_tasks: options.max_number_of_tasks,
+            default_primary_key: options.default_primary_key,
+            field_cipher: options.encryption_key.as_deref().map(FieldCipher::new),
+            max_facets_per_search: options.max_facets_per_search,
+            default_documents_limit: options.default_documents_limit,
+            max_documents_limit: options.max_documents_limit,
+            dump_and_snapshot_io_timeout: options.dump_and_snapshot_io_timeout,
+            io_worker: Arc::default(),
+            limited_thread_pools: Arc::default(),
             dumps_path: options.dumps_path,
             snapshots_path: options.snapshots_path,
             auth_path: options.auth_path,
+            auth_store_size: options.auth_store_size,
             version_file_path: options.version_file_path,
 
             #[cfg(test)]
@@ -478,6 +556,8 @@ impl IndexScheduler {
             #[cfg(test)]
             run_loop_iteration: Arc::new(RwLock::new(0)),
             features,
+            search_counts,
+            task_events: task_events::TaskEvents::default(),
         };
 
         this.run();
@@ -578,6 +658,7 @@ impl IndexScheduler {
 
                 run.wake_up.wait();
 
+                let mut search_counts_persisted_at = Instant::now();
                 loop {
                     let puffin_enabled = match run.features() {
                         Ok(features) => features.check_puffin().is_ok(),
@@ -591,7 +672,10 @@ impl IndexScheduler {
 
                     match run.tick() {
                         Ok(TickOutcome::TickAgain(_)) => (),
-                        Ok(TickOutcome::WaitForSignal) => run.wake_up.wait(),
+                        Ok(TickOutcome::WaitForSignal) => {
+                            // An idle scheduler still wakes up to persist the search counters.
+                            run.wake_up.wait_timeout(search_counts::PERSIST_INTERVAL);
+                        }
                         Err(e) => {
                             log::error!("{e}");
                             // Wait one second when an irrecoverable error occurs.
@@ -601,6 +685,15 @@ impl IndexScheduler {
                         }
                     }
 
+                    // The search counters are persisted between two batches,
+                    // so never while a batch deletes or swaps them.
+                    if search_counts_persisted_at.elapsed() >= search_counts::PERSIST_INTERVAL {
+                        if let Err(e) = run.search_counts.persist(&run.env) {
+                            log::error!("Could not persist the search counts: {e}");
+                        }
+                        search_counts_persisted_at = Instant::now();
+                    }
+
                     // Let's write the previous frame to disk but only if
                     // the user wanted to profile with puffin.
                     if puffin_enabled {
@@ -662,12 +755,65 @@ impl IndexScheduler {
         self.index_mapper.index(&rtxn, name)
     }
 
+    /// Return the name of the index as it was given when the index was created,
+    /// even when it is reached through an uid that only differs by its case.
+    pub fn index_name(&self, name: &str) -> Result<String> {
+        let rtxn = self.env.read_txn()?;
+        self.index_mapper.display_name(&rtxn, name)
+    }
+
     /// Return the name of all indexes without opening them.
     pub fn index_names(&self) -> Result<Vec<String>> {
         let rtxn = self.env.read_txn()?;
         self.index_mapper.index_names(&rtxn)
     }
 
+    /// Return the cipher of the `encryptedAttributes`, if an encryption key is configured.
+    pub fn field_cipher(&self) -> Option<&FieldCipher> {
+        self.field_cipher.as_ref()
+    }
+
+    /// Return the primary key given to the indexes created without specifying one, if any.
+    pub fn default_primary_key(&self) -> Option<&str> {
+        self.default_primary_key.as_deref()
+    }
+
+    /// Return the maximum number of facets a single search can request, if any.
+    pub fn max_facets_per_search(&self) -> Option<usize> {
+        self.max_facets_per_search
+    }
+
+    /// Return the number of documents returned by a documents browse not specifying a limit.
+    pub fn default_documents_limit(&self) -> usize {
+        self.default_documents_limit
+    }
+
+    /// Return the maximum number of documents a documents browse can return, if any.
+    pub fn max_documents_limit(&self) -> Option<usize> {
+        self.max_documents_limit
+    }
+
+    /// Return an error if the index doesn't exist and no enqueued task is going to create it.
+    ///
+    /// It is used to refuse the registration of updates that are not allowed to create their index.
+    pub fn ensure_index_will_exist(&self, name: &str) -> Result<()> {
+        let rtxn = self.env.read_txn()?;
+        if self.index_mapper.exists(&rtxn, name)? {
+            return Ok(());
+        }
+
+        let enqueued_tasks =
+            self.get_status(&rtxn, Status::Enqueued)? & self.index_tasks(&rtxn, name)?;
+        for task_id in enqueued_tasks {
+            let task = self.get_task(&rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+            if task.kind.can_create_index() {
+                return Ok(());
+            }
+        }
+
+        Err(Error::IndexNotFoundWithoutCreation(name.to_string()))
+    }
+
     /// Attempts `f` for each index that exists known to the index scheduler.
     ///
     /// It is preferable to use this function rather than a loop that opens all indexes, as a way to avoid having all indexes opened,
@@ -879,6 +1025,14 @@ impl IndexScheduler {
         Ok(nbr_index_processing_tasks > 0)
     }
 
+    /// Return true iff a snapshot creation task is enqueued or processing.
+    pub fn is_snapshot_pending(&self) -> Result<bool> {
+        let rtxn = self.env.read_txn()?;
+        let snapshots = self.get_kind(&rtxn, Kind::SnapshotCreation)?;
+        let enqueued = self.get_status(&rtxn, Status::Enqueued)?;
+        Ok(!snapshots.is_disjoint(&enqueued))
+    }
+
     /// Return the task ids matching the query along with the total number of tasks
     /// by ignoring the from and limit parameters from the user's point of view.
     ///
@@ -968,12 +1122,116 @@ impl IndexScheduler {
         }
     }
 
+    /// Returns the tasks with the given uids, in the order of the uids.
+    ///
+    /// The tasks that don't exist or that the user is not allowed to see are returned as `None`.
+    pub fn get_tasks_by_uids(
+        &self,
+        uids: &[TaskId],
+        filters: &meilisearch_auth::AuthFilter,
+    ) -> Result<Vec<Option<Task>>> {
+        let query = Query { uids: Some(uids.to_vec()), ..Query::default() };
+        let (tasks, _) = self.get_tasks_from_authorized_indexes(query, filters)?;
+        let tasks: HashMap<TaskId, Task> = tasks.into_iter().map(|task| (task.uid, task)).collect();
+
+        Ok(uids.iter().map(|uid| tasks.get(uid).cloned()).collect())
+    }
+
     /// Register a new task in the scheduler.
     ///
     /// If it fails and data was associated with the task, it tries to delete the associated data.
     pub fn register(&self, kind: KindWithContent) -> Result<Task> {
+        self.register_task(kind, None, true)
+    }
+
+    /// Register a new task in the scheduler with the details already known at enqueue time,
+    /// e.g. computed while receiving the payload of the task.
+    pub fn register_with_details(&self, kind: KindWithContent, details: Details) -> Result<Task> {
+        self.register_task(kind, Some(details), true)
+    }
+
+    /// Register a new task in the scheduler, its details default to the ones of its kind and
+    /// a task cancelation only stops the processing tasks it matches when `may_interrupt` is set.
+    fn register_task(
+        &self,
+        kind: KindWithContent,
+        details: Option<Details>,
+        may_interrupt: bool,
+    ) -> Result<Task> {
+        let mut wtxn = self.env.write_txn()?;
+        let task = self.put_new_task(&mut wtxn, kind.clone(), details)?;
+
+        if let Err(e) = wtxn.commit() {
+            self.delete_persisted_task_data(&task)?;
+            return Err(e.into());
+        }
+        self.task_events.publish(task.uid, task.status);
+
+        // If the registered task is a task cancelation
+        // we inform the processing tasks to stop (if necessary).
+        if let KindWithContent::TaskCancelation { tasks, .. } = kind {
+            let tasks_to_cancel = RoaringBitmap::from_iter(tasks);
+            if may_interrupt
+                && self
+                    .processing_tasks
+                    .read()
+                    .unwrap()
+                    .must_cancel_processing_tasks(&tasks_to_cancel)
+            {
+                self.must_stop_processing.must_stop();
+            }
+        }
+
+        // notify the scheduler loop to execute a new tick
+        self.wake_up.signal();
+
+        Ok(task)
+    }
+
+    /// Register the tasks creating an index and filling it in a single transaction.
+    ///
+    /// Fails if the index already exists or if an enqueued task targets it, so that the
+    /// tasks never apply to an index they did not create.
+    pub fn register_index_creation(
+        &self,
+        index_uid: &str,
+        kinds: Vec<KindWithContent>,
+    ) -> Result<Vec<Task>> {
         let mut wtxn = self.env.write_txn()?;
+        let enqueued_tasks =
+            self.get_status(&wtxn, Status::Enqueued)? & self.index_tasks(&wtxn, index_uid)?;
+        if self.index_mapper.exists(&wtxn, index_uid)? || !enqueued_tasks.is_empty() {
+            return Err(Error::IndexAlreadyExists(index_uid.to_string()));
+        }
+
+        let mut tasks = Vec::with_capacity(kinds.len());
+        for kind in kinds {
+            tasks.push(self.put_new_task(&mut wtxn, kind, None)?);
+        }
+
+        if let Err(e) = wtxn.commit() {
+            for task in &tasks {
+                self.delete_persisted_task_data(task)?;
+            }
+            return Err(e.into());
+        }
+        for task in &tasks {
+            self.task_events.publish(task.uid, task.status);
+        }
+
+        // notify the scheduler loop to execute a new tick
+        self.wake_up.signal();
+
+        Ok(tasks)
+    }
 
+    /// Write a new enqueued task in the task queue, without committing the transaction.
+    fn put_new_task(
+        &self,
+        wtxn: &mut RwTxn,
+        kind: KindWithContent,
+        details: Option<Details>,
+    ) -> Result<Task> {
         // if the task doesn't delete anything and 50% of the task queue is full, we must refuse to enqueue the incomming task
         if !matches!(&kind, KindWithContent::TaskDeletion { tasks, .. } if !tasks.is_empty())
             && (self.env.non_free_pages_size()? * 100) / self.env.map_size()? as u64 > 50
@@ -982,16 +1240,23 @@ impl IndexScheduler {
         }
 
         let mut task = Task {
-            uid: self.next_task_id(&wtxn)?,
+            uid: self.next_task_id(wtxn)?,
             enqueued_at: OffsetDateTime::now_utc(),
             started_at: None,
             finished_at: None,
             error: None,
             canceled_by: None,
-            details: kind.default_details(),
+            details: details.or_else(|| kind.default_details()),
             status: Status::Enqueued,
-            kind: kind.clone(),
+            kind,
         };
+        // The tasks refer to their indexes by the name these indexes are stored under, so that
+        // the uids only differing by their case designate the same index. The names given to
+        // the indexes they may create are kept to display these indexes as they were named.
+        for index in task.indexes() {
+            self.index_mapper.set_display_name(wtxn, index)?;
+        }
+        utils::canonicalize_index_uids_in_task(&mut task, &self.index_mapper);
         // For deletion and cancelation tasks, we want to make extra sure that they
         // don't attempt to delete/cancel tasks that are newer than themselves.
         filter_out_references_to_newer_tasks(&mut task);
@@ -1002,43 +1267,85 @@ impl IndexScheduler {
         // Get rid of the mutability.
         let task = task;
 
-        self.all_tasks.append(&mut wtxn, &BEU32::new(task.uid), &task)?;
+        self.all_tasks.append(wtxn, &BEU32::new(task.uid), &task)?;
 
         for index in task.indexes() {
-            self.update_index(&mut wtxn, index, |bitmap| {
+            self.update_index(wtxn, index, |bitmap| {
                 bitmap.insert(task.uid);
             })?;
         }
 
-        self.update_status(&mut wtxn, Status::Enqueued, |bitmap| {
+        self.update_status(wtxn, Status::Enqueued, |bitmap| {
             bitmap.insert(task.uid);
         })?;
 
-        self.update_kind(&mut wtxn, task.kind.as_kind(), |bitmap| {
+        self.update_kind(wtxn, task.kind.as_kind(), |bitmap| {
             bitmap.insert(task.uid);
         })?;
 
-        utils::insert_task_datetime(&mut wtxn, self.enqueued_at, task.enqueued_at, task.uid)?;
+        utils::insert_task_datetime(wtxn, self.enqueued_at, task.enqueued_at, task.uid)?;
 
-        if let Err(e) = wtxn.commit() {
-            self.delete_persisted_task_data(&task)?;
-            return Err(e.into());
-        }
+        Ok(task)
+    }
 
-        // If the registered task is a task cancelation
-        // we inform the processing tasks to stop (if necessary).
-        if let KindWithContent::TaskCancelation { tasks, .. } = kind {
-            let tasks_to_cancel = RoaringBitmap::from_iter(tasks);
-            if self.processing_tasks.read().unwrap().must_cancel_processing_tasks(&tasks_to_cancel)
-            {
-                self.must_stop_processing.must_stop();
-            }
+    /// Cancel all the enqueued tasks visible with the given filters by registering a
+    /// task cancelation. The tasks that are processing are never interrupted and, as
+    /// a task cancelation only cancels the tasks still enqueued when it is processed,
+    /// the tasks that start processing in the meantime are left untouched too.
+    ///
+    /// Returns the registered task along with the number of enqueued tasks it targets,
+    /// the number of tasks actually canceled is reported in the task details. No task
+    /// is registered when there is no enqueued task to cancel.
+    pub fn abort_enqueued_tasks(
+        &self,
+        filters: &meilisearch_auth::AuthFilter,
+    ) -> Result<Option<(Task, u64)>> {
+        let query = Query { statuses: Some(vec![Status::Enqueued]), ..Query::default() };
+        let rtxn = self.env.read_txn()?;
+        let (mut tasks, _) = self.get_task_ids_from_authorized_indexes(&rtxn, &query, filters)?;
+        drop(rtxn);
+
+        // the processing tasks are still marked as enqueued in the database.
+        tasks -= &self.processing_tasks.read().unwrap().processing;
+        if tasks.is_empty() {
+            return Ok(None);
         }
+        let aborted = tasks.len();
 
-        // notify the scheduler loop to execute a new tick
-        self.wake_up.signal();
+        let query = String::from("?statuses=enqueued");
+        let task_cancelation = KindWithContent::TaskCancelation { query, tasks };
+        let task = self.register_task(task_cancelation, None, false)?;
 
-        Ok(task)
+        Ok(Some((task, aborted)))
+    }
+
+    /// Receive the status transitions of the tasks happening from now on.
+    pub fn subscribe_to_task_events(&self) -> tokio::sync::broadcast::Receiver<TaskEvent> {
+        self.task_events.subscribe()
+    }
+
+    /// Return the current status of the task of the given event and of the tasks registered
+    /// after it, so that a client resuming its stream catches up, even across restarts.
+    ///
+    /// The given event itself is not returned if the status of its task did not change since.
+    pub fn task_events_after(&self, last_event: TaskEvent) -> Result<Vec<TaskEvent>> {
+        let rtxn = self.env.read_txn()?;
+        let processing = self.processing_tasks.read().unwrap().processing.clone();
+
+        let mut events = Vec::new();
+        for status in enum_iterator::all::<Status>() {
+            let mut tasks = match status {
+                Status::Processing => processing.clone(),
+                Status::Enqueued => self.get_status(&rtxn, status)? - &processing,
+                status => self.get_status(&rtxn, status)?,
+            };
+            tasks.remove_range(..last_event.task_uid);
+            events.extend(tasks.into_iter().map(|task_uid| TaskEvent { task_uid, status }));
+        }
+        events.sort_unstable_by_key(|event| event.task_uid);
+        events.retain(|event| *event != last_event);
+
+        Ok(events)
     }
 
     /// Register a new task coming from a dump in the scheduler.
@@ -1125,6 +1432,9 @@ impl IndexScheduler {
         // We reset the must_stop flag to be sure that we don't stop processing tasks
         self.must_stop_processing.reset();
         self.processing_tasks.write().unwrap().start_processing_at(started_at, processing_tasks);
+        for id in &ids {
+            self.task_events.publish(*id, Status::Processing);
+        }
 
         #[cfg(test)]
         self.breakpoint(Breakpoint::BatchCreated);
@@ -1143,6 +1453,7 @@ impl IndexScheduler {
         self.maybe_fail(tests::FailureLocation::AcquiringWtxn)?;
 
         let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        let mut staged_events = self.task_events.staging();
 
         let finished_at = OffsetDateTime::now_utc();
         match res {
@@ -1162,7 +1473,7 @@ impl IndexScheduler {
                         },
                     )?;
 
-                    self.update_task(&mut wtxn, &task)
+                    self.update_task(&mut wtxn, &mut staged_events, &task)
                         .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e)))?;
                     if let Err(e) = self.delete_persisted_task_data(&task) {
                         log::error!("Failure to delete the content files associated with task {}. Error: {e}", task.uid);
@@ -1223,7 +1534,7 @@ impl IndexScheduler {
                     if let Err(e) = self.delete_persisted_task_data(&task) {
                         log::error!("Failure to delete the content files associated with task {}. Error: {e}", task.uid);
                     }
-                    self.update_task(&mut wtxn, &task)
+                    self.update_task(&mut wtxn, &mut staged_events, &task)
                         .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e)))?;
                 }
             }
@@ -1235,6 +1546,7 @@ impl IndexScheduler {
         self.maybe_fail(tests::FailureLocation::CommittingWtxn)?;
 
         wtxn.commit().map_err(Error::HeedTransaction)?;
+        staged_events.publish();
 
         #[cfg(test)]
         self.breakpoint(Breakpoint::AfterProcessing);
@@ -1247,8 +1559,9 @@ impl IndexScheduler {
         let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
 
         let nb_tasks = self.all_task_ids(&rtxn)?.len();
-        // if we have less than 1M tasks everything is fine
-        if nb_tasks < self.max_number_of_tasks as u64 {
+        let max_number_of_tasks = self.max_number_of_tasks as u64;
+        // if we have less tasks than the configured history everything is fine
+        if nb_tasks < max_number_of_tasks {
             return Ok(());
         }
 
@@ -1256,7 +1569,12 @@ impl IndexScheduler {
             | self.status.get(&rtxn, &Status::Failed)?.unwrap_or_default()
             | self.status.get(&rtxn, &Status::Canceled)?.unwrap_or_default();
 
-        let to_delete = RoaringBitmap::from_iter(finished.into_iter().rev().take(100_000));
+        // We delete the tasks exceeding the history plus a tenth of it (but no more than 100k tasks)
+        // so that we don't have to enqueue a new deletion task on every registration.
+        let excess = nb_tasks - max_number_of_tasks;
+        let margin = (max_number_of_tasks / 10).clamp(1, 100_000);
+        let to_delete =
+            RoaringBitmap::from_iter(finished.into_iter().take((excess + margin) as usize));
 
         // /!\ the len must be at least 2 or else we might enter an infinite loop where we only delete
         //     the deletion tasks we enqueued ourselves.
@@ -1292,11 +1610,30 @@ impl IndexScheduler {
     }
 
     pub fn index_stats(&self, index_uid: &str) -> Result<IndexStats> {
+        self.index_stats_with(index_uid, &MemorySizes::read()?)
+    }
+
+    /// Same as [`Self::index_stats`] but reuses memory sizes read once for all the indexes.
+    pub fn index_stats_with(
+        &self,
+        index_uid: &str,
+        memory_sizes: &MemorySizes,
+    ) -> Result<IndexStats> {
         let is_indexing = self.is_index_processing(index_uid)?;
         let rtxn = self.read_txn()?;
         let index_stats = self.index_mapper.stats_of(&rtxn, index_uid)?;
+        let search_count = self.search_counts.get(&self.index_mapper.canonical_name(index_uid));
+        let memory_size = self.index_mapper.memory_size_of(&rtxn, index_uid, memory_sizes)?;
 
-        Ok(IndexStats { is_indexing, inner_stats: index_stats })
+        Ok(IndexStats { is_indexing, search_count, memory_size, inner_stats: index_stats })
+    }
+
+    /// Record that a search has been performed on the given index.
+    ///
+    /// The counter is kept in memory and regularly persisted to disk.
+    pub fn increment_search_count(&self, index_uid: &str) {
+        // the counters are kept under the name the index is stored under, like its tasks.
+        self.search_counts.increment(&self.index_mapper.canonical_name(index_uid));
     }
 
     pub fn features(&self) -> Result<RoFeatures> {
@@ -1390,7 +1727,7 @@ impl<'a> Dump<'a> {
             _ => None,
         };
 
-        let task = Task {
+        let mut task = Task {
             uid: task.uid,
             enqueued_at: task.enqueued_at,
             started_at: task.started_at,
@@ -1404,6 +1741,9 @@ impl<'a> Dump<'a> {
                     primary_key,
                     method,
                     documents_count,
+                    indexing_limits,
+                    duplicate_documents,
+                    version_field,
                     allow_index_creation,
                 } => KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
@@ -1411,6 +1751,9 @@ impl<'a> Dump<'a> {
                     method,
                     content_file: content_uuid.ok_or(Error::CorruptedDump)?,
                     documents_count,
+                    indexing_limits,
+                    duplicate_documents,
+                    version_field,
                     allow_index_creation,
                 },
                 KindDump::DocumentDeletion { documents_ids } => KindWithContent::DocumentDeletion {
@@ -1459,6 +1802,7 @@ impl<'a> Dump<'a> {
             },
         };
 
+        utils::canonicalize_index_uids_in_task(&mut task, &self.index_scheduler.index_mapper);
         self.index_scheduler.all_tasks.put(&mut self.wtxn, &BEU32::new(task.uid), &task)?;
 
         for index in task.indexes() {
@@ -1553,6 +1897,10 @@ struct IndexBudget {
 pub struct IndexStats {
     /// Whether this index is currently performing indexation, according to the scheduler.
     pub is_indexing: bool,
+    /// Number of searches performed on this index.
+    pub search_count: u64,
+    /// Size of the pages of the index currently resident in memory, in bytes.
+    pub memory_size: u64,
     /// Internal stats computed from the index.
     pub inner_stats: index_mapper::IndexStats,
 }
@@ -1617,6 +1965,7 @@ mod tests {
             let mut options = IndexSchedulerOptions {
                 version_file_path: tempdir.path().join(VERSION_FILE_NAME),
                 auth_path: tempdir.path().join("auth"),
+                auth_store_size: 1000 * 1000, // 1 MB, we don't use MiB on purpose.
                 tasks_path: tempdir.path().join("db_path"),
                 update_file_path: tempdir.path().join("file_store"),
                 indexes_path: tempdir.path().join("indexes"),
@@ -1630,6 +1979,13 @@ mod tests {
                 indexer_config,
                 autobatching_enabled: true,
                 max_number_of_tasks: 1_000_000,
+                default_primary_key: None,
+                case_insensitive_index_uids: false,
+                max_facets_per_search: None,
+                default_documents_limit: 20,
+                max_documents_limit: None,
+                dump_and_snapshot_io_timeout: None,
+                encryption_key: None,
                 instance_features: Default::default(),
             };
             configuration(&mut options);
@@ -1696,6 +2052,9 @@ mod tests {
             method: ReplaceDocuments,
             content_file: Uuid::from_u128(content_file_uuid),
             documents_count,
+            indexing_limits: None,
+            duplicate_documents: Default::default(),
+            version_field: None,
             allow_index_creation: true,
         }
     }
@@ -1709,7 +2068,13 @@ mod tests {
         let mut buffer = BufWriter::new(temp_file.reopen().unwrap());
         buffer.write_all(bytes).unwrap();
         buffer.flush().unwrap();
-        meilisearch_types::document_formats::read_json(temp_file.as_file(), write)
+        meilisearch_types::document_formats::read_json(
+            temp_file.as_file(),
+            write,
+            &Default::default(),
+            None,
+        )
+        .map(|count| count.documents)
     }
 
     /// Create an update file with the given file uuid.
@@ -1916,6 +2281,111 @@ mod tests {
         assert!(index_scheduler.is_task_processing().unwrap());
     }
 
+    #[test]
+    fn test_index_stats_is_indexing() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        index_scheduler.register(index_creation_task("index_a", "id")).unwrap();
+        index_scheduler.register(index_creation_task("index_b", "id")).unwrap();
+        handle.advance_n_successful_batches(2);
+
+        index_scheduler
+            .register(KindWithContent::DocumentClear { index_uid: S("index_a") })
+            .unwrap();
+        // an enqueued task doesn't make its index indexing.
+        assert!(!index_scheduler.index_stats("index_a").unwrap().is_indexing);
+
+        handle.advance_till([Start, BatchCreated]);
+        // only the index targeted by the processing task is indexing.
+        assert!(index_scheduler.index_stats("index_a").unwrap().is_indexing);
+        assert!(!index_scheduler.index_stats("index_b").unwrap().is_indexing);
+    }
+
+    #[test]
+    fn test_dump_creation() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        index_scheduler.register(index_creation_task("catto", "id")).unwrap();
+        handle.advance_one_successful_batch();
+
+        // the dumps directory is recreated if it was removed after the startup.
+        std::fs::remove_dir_all(&index_scheduler.dumps_path).unwrap();
+        index_scheduler
+            .register(KindWithContent::DumpCreation { keys: vec![], instance_uid: None })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        let dumps: Vec<_> = std::fs::read_dir(&index_scheduler.dumps_path)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(dumps.len(), 1);
+        assert_eq!(dumps[0].extension().unwrap(), "dump");
+    }
+
+    #[test]
+    fn test_dump_creation_with_io_timeout() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test_with_custom_config(vec![], |config| {
+                config.dump_and_snapshot_io_timeout = Some(std::time::Duration::from_secs(10));
+            });
+
+        index_scheduler
+            .register(KindWithContent::DumpCreation { keys: vec![], instance_uid: None })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        let dumps: Vec<_> = std::fs::read_dir(&index_scheduler.dumps_path)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(dumps.len(), 1);
+        assert_eq!(dumps[0].extension().unwrap(), "dump");
+    }
+
+    #[test]
+    fn test_dump_creation_timing_out() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test_with_custom_config(vec![], |config| {
+                config.dump_and_snapshot_io_timeout = Some(std::time::Duration::from_nanos(1));
+            });
+
+        index_scheduler
+            .register(KindWithContent::DumpCreation { keys: vec![], instance_uid: None })
+            .unwrap();
+        handle.advance_one_failed_batch();
+
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, 0).unwrap().unwrap();
+        snapshot!(task.error.unwrap().message, @"The dump creation did not complete its file operations within 1ns.");
+
+        // the copies that timed out keep running but are deleted once done, never moved into place.
+        let deadline = Instant::now() + std::time::Duration::from_secs(10);
+        while std::fs::read_dir(&index_scheduler.dumps_path).unwrap().count() != 0 {
+            assert!(Instant::now() < deadline, "the timed out copies were not deleted");
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_is_snapshot_pending() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+        // the snapshot copies the version file and the auth env, they're created by meilisearch.
+        std::fs::write(&index_scheduler.version_file_path, "1.4.1").unwrap();
+        std::fs::create_dir_all(&index_scheduler.auth_path).unwrap();
+        assert!(!index_scheduler.is_snapshot_pending().unwrap());
+
+        index_scheduler.register(KindWithContent::SnapshotCreation).unwrap();
+        assert!(index_scheduler.is_snapshot_pending().unwrap());
+
+        handle.advance_till([Start, BatchCreated]);
+        assert!(index_scheduler.is_snapshot_pending().unwrap());
+
+        handle.advance_till([InsideProcessBatch, ProcessBatchSucceeded, AfterProcessing]);
+        assert!(!index_scheduler.is_snapshot_pending().unwrap());
+        assert_eq!(std::fs::read_dir(&index_scheduler.snapshots_path).unwrap().count(), 1);
+    }
+
     /// We send a lot of tasks but notify the tasks scheduler only once as
     /// we send them very fast, we must make sure that they are all processed.
     #[test]
@@ -2130,6 +2600,9 @@ mod tests {
                 method: ReplaceDocuments,
                 content_file: uuid,
                 documents_count,
+                indexing_limits: None,
+                duplicate_documents: Default::default(),
+                version_field: None,
                 allow_index_creation: true,
             })
             .unwrap();
@@ -2167,6 +2640,9 @@ mod tests {
                 method: ReplaceDocuments,
                 content_file: uuid,
                 documents_count,
+                indexing_limits: None,
+                duplicate_documents: Default::default(),
+                version_field: None,
                 allow_index_creation: true,
             })
             .unwrap();
@@ -2203,6 +2679,9 @@ mod tests {
                 method: ReplaceDocuments,
                 content_file: uuid,
                 documents_count,
+                indexing_limits: None,
+                duplicate_documents: Default::default(),
+                version_field: None,
                 allow_index_creation: true,
             })
             .unwrap();
@@ -2257,6 +2736,9 @@ mod tests {
                 method: ReplaceDocuments,
                 content_file: uuid,
                 documents_count,
+                indexing_limits: None,
+                duplicate_documents: Default::default(),
+                version_field: None,
                 allow_index_creation: true,
             })
             .unwrap();
@@ -2433,6 +2915,9 @@ mod tests {
                 method: ReplaceDocuments,
                 content_file: uuid,
                 documents_count,
+                indexing_limits: None,
+                duplicate_documents: Default::default(),
+                version_field: None,
                 allow_index_creation: true,
             })
             .unwrap();
@@ -2447,6 +2932,57 @@ mod tests {
         snapshot!(snapshot_index_scheduler(&index_scheduler));
     }
 
+    #[test]
+    fn index_deletion_cancels_the_enqueued_tasks_of_the_index() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        index_scheduler.register(index_creation_task("doggos", "id")).unwrap();
+        handle.advance_one_successful_batch();
+
+        let (file0, documents_count0) = sample_documents(&index_scheduler, 0, 0);
+        file0.persist().unwrap();
+
+        let to_enqueue = [
+            KindWithContent::IndexDeletion { index_uid: S("doggos") },
+            KindWithContent::DocumentClear { index_uid: S("doggos") },
+            KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                method: ReplaceDocuments,
+                content_file: Uuid::from_u128(0),
+                documents_count: documents_count0,
+                indexing_limits: None,
+                duplicate_documents: Default::default(),
+                version_field: None,
+                allow_index_creation: false,
+            },
+            // This task recreates the index, the following ones must not be canceled.
+            index_creation_task("doggos", "id"),
+            KindWithContent::DocumentClear { index_uid: S("doggos") },
+        ];
+        for task in to_enqueue {
+            let _ = index_scheduler.register(task).unwrap();
+            index_scheduler.assert_internally_consistent();
+        }
+
+        handle.advance_one_successful_batch();
+        index_scheduler.assert_internally_consistent();
+
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        for task_id in [2, 3] {
+            let task = index_scheduler.get_task(&rtxn, task_id).unwrap().unwrap();
+            assert_eq!(task.status, Status::Canceled);
+            assert_eq!(task.canceled_by, Some(1));
+            snapshot!(task.error.unwrap().message, @"Index `doggos` was deleted by the task `1` before this task could be processed.");
+        }
+        for task_id in [4, 5] {
+            let task = index_scheduler.get_task(&rtxn, task_id).unwrap().unwrap();
+            assert_eq!(task.status, Status::Enqueued);
+        }
+        // The content file of the canceled document addition has been deleted.
+        snapshot!(index_scheduler.file_store.all_uuids().unwrap().count(), @"0");
+    }
+
     #[test]
     fn cancel_enqueued_task() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
@@ -2568,6 +3104,54 @@ mod tests {
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "cancel_processed");
     }
 
+    #[test]
+    fn abort_enqueued_tasks_leaves_the_processing_task() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let mut to_enqueue = Vec::new();
+        for (id, index) in ["catto", "beavero", "wolfo"].into_iter().enumerate() {
+            let (file, documents_count) = sample_documents(&index_scheduler, id as u128, id);
+            file.persist().unwrap();
+            to_enqueue.push(replace_document_import_task(index, None, id as u128, documents_count));
+        }
+        for task in to_enqueue {
+            let _ = index_scheduler.register(task).unwrap();
+            index_scheduler.assert_internally_consistent();
+        }
+
+        handle.advance_till([Start, BatchCreated, InsideProcessBatch]);
+        let (task, aborted) =
+            index_scheduler.abort_enqueued_tasks(&AuthFilter::default()).unwrap().unwrap();
+        assert_eq!(aborted, 2);
+
+        // the processing batch is not interrupted.
+        loop {
+            match handle.advance() {
+                InsideProcessBatch => (),
+                ProcessBatchSucceeded => break,
+                breakpoint => panic!("The processing batch was interrupted at `{breakpoint:?}`."),
+            }
+        }
+        handle.advance_till([AfterProcessing]);
+        handle.advance_one_successful_batch();
+        index_scheduler.assert_internally_consistent();
+
+        let rtxn = index_scheduler.read_txn().unwrap();
+        let status = |uid| index_scheduler.get_task(&rtxn, uid).unwrap().unwrap().status;
+        assert_eq!(status(0), Status::Succeeded);
+        assert_eq!(status(1), Status::Canceled);
+        assert_eq!(status(2), Status::Canceled);
+        let cancelation = index_scheduler.get_task(&rtxn, task.uid).unwrap().unwrap();
+        assert_eq!(
+            cancelation.details,
+            Some(Details::TaskCancelation {
+                matched_tasks: 2,
+                canceled_tasks: Some(2),
+                original_filter: "?statuses=enqueued".to_owned(),
+            })
+        );
+    }
+
     #[test]
     fn test_document_replace() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
@@ -2591,6 +3175,9 @@ mod tests {
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: true,
                 })
                 .unwrap();
@@ -2638,6 +3225,9 @@ mod tests {
                     method: UpdateDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: true,
                 })
                 .unwrap();
@@ -2687,6 +3277,9 @@ mod tests {
                     method,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: true,
                 })
                 .unwrap();
@@ -2714,6 +3307,111 @@ mod tests {
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
 
+    #[test]
+    fn test_document_addition_honors_the_method() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let contents = [
+            (ReplaceDocuments, r#"{ "id": 1, "doggo": "bob", "age": 3 }"#),
+            (UpdateDocuments, r#"{ "id": 1, "doggo": "bobby" }"#),
+            (ReplaceDocuments, r#"{ "id": 2, "doggo": "jean", "age": 5 }"#),
+            (ReplaceDocuments, r#"{ "id": 2, "doggo": "jeannot" }"#),
+        ];
+        for (i, (method, content)) in contents.into_iter().enumerate() {
+            let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(i as u128).unwrap();
+            let documents_count = read_json(content.as_bytes(), file.as_file_mut()).unwrap();
+            file.persist().unwrap();
+            index_scheduler
+                .register(KindWithContent::DocumentAdditionOrUpdate {
+                    index_uid: S("doggos"),
+                    primary_key: Some(S("id")),
+                    method,
+                    content_file: uuid,
+                    documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
+                    allow_index_creation: true,
+                })
+                .unwrap();
+            index_scheduler.assert_internally_consistent();
+        }
+
+        // every task keeps the method it was registered with.
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        for (task_id, (expected, _)) in contents.into_iter().enumerate() {
+            let task = index_scheduler.get_task(&rtxn, task_id as u32).unwrap().unwrap();
+            match task.kind {
+                KindWithContent::DocumentAdditionOrUpdate { method, .. } => {
+                    assert_eq!(method, expected)
+                }
+                kind => panic!("unexpected task kind: {kind:?}"),
+            }
+        }
+        drop(rtxn);
+
+        handle.advance_n_successful_batches(3);
+
+        // the update merged the fields while the replacement dropped the missing ones.
+        let index = index_scheduler.index("doggos").unwrap();
+        let rtxn = index.read_txn().unwrap();
+        let field_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let field_ids = field_ids_map.ids().collect::<Vec<_>>();
+        let documents = index
+            .all_documents(&rtxn)
+            .unwrap()
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            serde_json::json!(documents),
+            serde_json::json!([
+                { "id": 1, "doggo": "bobby", "age": 3 },
+                { "id": 2, "doggo": "jeannot" },
+            ])
+        );
+    }
+
+    #[test]
+    fn io_timeout_on_slow_writer() {
+        use std::io::Write;
+        use std::sync::atomic::AtomicUsize;
+        use std::time::Duration;
+
+        struct SlowWriter(Duration);
+
+        impl Write for SlowWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                std::thread::sleep(self.0);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let worker = crate::utils::IoWorker::default();
+        let error = worker
+            .run_with_timeout("dump creation", Duration::from_millis(50), move || {
+                counter.fetch_add(1, Relaxed);
+                SlowWriter(Duration::from_millis(60)).write_all(b"dump")
+            })
+            .unwrap_err();
+        snapshot!(error, @"The dump creation did not complete its file operations within 50ms.");
+        snapshot!(format!("{:?}", error.error_code()), @"IoTimeout");
+
+        // a writer completing in time succeeds, once the timed out attempts are done.
+        worker
+            .run_with_timeout("dump creation", Duration::from_secs(10), || {
+                SlowWriter(Duration::from_millis(1)).write_all(b"dump")
+            })
+            .unwrap();
+        // the operation was retried before failing, one attempt at a time.
+        assert_eq!(attempts.load(Relaxed), crate::utils::IO_RETRIES + 1);
+    }
+
     #[test]
     fn test_document_replace_without_autobatching() {
         let (index_scheduler, mut handle) = IndexScheduler::test(false, vec![]);
@@ -2737,6 +3435,9 @@ mod tests {
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: true,
                 })
                 .unwrap();
@@ -2788,6 +3489,9 @@ mod tests {
                     method: UpdateDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: true,
                 })
                 .unwrap();
@@ -3115,6 +3819,31 @@ mod tests {
         snapshot!(snapshot_bitmap(&tasks), @"[2,]");
     }
 
+    #[test]
+    fn concurrent_read_transactions_on_an_index() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let kind = index_creation_task("catto", "mouse");
+        let _task = index_scheduler.register(kind).unwrap();
+        handle.advance_one_successful_batch();
+
+        // LMDB read transactions never wait for each other, every search opens
+        // its own read transaction on the shared environment of the index.
+        let index = index_scheduler.index("catto").unwrap();
+        let readers = 8;
+        let barrier = std::sync::Barrier::new(readers);
+        std::thread::scope(|s| {
+            for _ in 0..readers {
+                s.spawn(|| {
+                    let rtxn = index.read_txn().unwrap();
+                    // the barrier is only passed if all the read transactions are alive at once.
+                    barrier.wait();
+                    assert_eq!(index.primary_key(&rtxn).unwrap(), Some("mouse"));
+                });
+            }
+        });
+    }
+
     #[test]
     fn query_tasks_special_rules() {
         let (index_scheduler, mut handle) =
@@ -3286,6 +4015,9 @@ mod tests {
                 method: ReplaceDocuments,
                 content_file: uuid,
                 documents_count,
+                indexing_limits: None,
+                duplicate_documents: Default::default(),
+                version_field: None,
                 allow_index_creation: true,
             })
             .unwrap();
@@ -3301,6 +4033,40 @@ mod tests {
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "document_addition_failed");
     }
 
+    #[test]
+    fn task_events_are_only_published_once_committed() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test(true, vec![(1, FailureLocation::CommittingWtxn)]);
+
+        let mut receiver = index_scheduler.subscribe_to_task_events();
+        index_scheduler.register(index_creation_task("catto", "mouse")).unwrap();
+
+        // the batch succeeds but the scheduler fails to commit, the status change is discarded.
+        handle.advance_till([Start, BatchCreated]);
+        loop {
+            match handle.advance() {
+                InsideProcessBatch => (),
+                ProcessBatchSucceeded => break,
+                breakpoint => panic!("Encountered an unexpected breakpoint `{breakpoint:?}`."),
+            }
+        }
+        handle.advance_one_successful_batch();
+
+        let events: Vec<_> = std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|event| (event.task_uid, event.status))
+            .collect();
+        // the batch is processed twice, the processing status is published each time it starts.
+        assert_eq!(
+            events,
+            vec![
+                (0, Status::Enqueued),
+                (0, Status::Processing),
+                (0, Status::Processing),
+                (0, Status::Succeeded)
+            ]
+        );
+    }
+
     #[test]
     fn fail_in_update_task_after_process_batch_success_for_document_addition() {
         let (index_scheduler, mut handle) = IndexScheduler::test(
@@ -3324,6 +4090,9 @@ mod tests {
                 method: ReplaceDocuments,
                 content_file: uuid,
                 documents_count,
+                indexing_limits: None,
+                duplicate_documents: Default::default(),
+                version_field: None,
                 allow_index_creation: true,
             })
             .unwrap();
@@ -3380,6 +4149,9 @@ mod tests {
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: false,
                 })
                 .unwrap();
@@ -3428,6 +4200,9 @@ mod tests {
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: false,
                 })
                 .unwrap();
@@ -3482,6 +4257,9 @@ mod tests {
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: false,
                 })
                 .unwrap();
@@ -3541,6 +4319,9 @@ mod tests {
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: false,
                 })
                 .unwrap();
@@ -3605,6 +4386,9 @@ mod tests {
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation,
                 })
                 .unwrap();
@@ -3658,6 +4442,9 @@ mod tests {
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation,
                 })
                 .unwrap();
@@ -3710,6 +4497,9 @@ mod tests {
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: true,
                 })
                 .unwrap();
@@ -3771,6 +4561,9 @@ mod tests {
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: true,
                 })
                 .unwrap();
@@ -3828,6 +4621,9 @@ mod tests {
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: true,
                 })
                 .unwrap();
@@ -3909,6 +4705,9 @@ mod tests {
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: true,
                 })
                 .unwrap();
@@ -3992,6 +4791,9 @@ mod tests {
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
+                    indexing_limits: None,
+                    duplicate_documents: Default::default(),
+                    version_field: None,
                     allow_index_creation: true,
                 })
                 .unwrap();
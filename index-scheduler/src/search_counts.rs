@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use meilisearch_types::heed::types::{SerdeJson, Str};
+use meilisearch_types::heed::{Database, Env, RwTxn};
+
+use crate::Result;
+
+const SEARCH_COUNTS: &str = "search-counts";
+
+/// The interval at which the in-memory counters are written to the database.
+pub(crate) const PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Keeps track of the number of searches performed on each index.
+///
+/// The counters live in memory so that incrementing them on every search is cheap,
+/// and they are regularly written to the task database so they survive restarts.
+#[derive(Clone)]
+pub(crate) struct SearchCounts {
+    persisted: Database<Str, SerdeJson<u64>>,
+    counts: Arc<RwLock<HashMap<String, Arc<AtomicU64>>>>,
+    /// Whether a counter changed since the last time they were persisted.
+    dirty: Arc<AtomicBool>,
+}
+
+impl SearchCounts {
+    pub fn new(env: &Env) -> Result<Self> {
+        let mut wtxn = env.write_txn()?;
+        let persisted: Database<Str, SerdeJson<u64>> =
+            env.create_database(&mut wtxn, Some(SEARCH_COUNTS))?;
+
+        let mut counts = HashMap::new();
+        for entry in persisted.iter(&wtxn)? {
+            let (index_uid, count) = entry?;
+            counts.insert(index_uid.to_string(), Arc::new(AtomicU64::new(count)));
+        }
+        wtxn.commit()?;
+
+        Ok(Self {
+            persisted,
+            counts: Arc::new(RwLock::new(counts)),
+            dirty: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Increment the search counter of the given index.
+    pub fn increment(&self, index_uid: &str) {
+        self.dirty.store(true, Ordering::Relaxed);
+        if let Some(count) = self.counts.read().unwrap().get(index_uid) {
+            count.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        let mut counts = self.counts.write().unwrap();
+        counts.entry(index_uid.to_string()).or_default().fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Return the number of searches performed on the given index.
+    pub fn get(&self, index_uid: &str) -> u64 {
+        self.counts.read().unwrap().get(index_uid).map_or(0, |count| count.load(Ordering::Relaxed))
+    }
+
+    /// Remove the counter of a deleted index from the database.
+    ///
+    /// The in-memory counter must be removed with [`Self::forget`] once the transaction is committed.
+    pub fn remove(&self, wtxn: &mut RwTxn, index_uid: &str) -> Result<()> {
+        self.persisted.delete(wtxn, index_uid)?;
+        Ok(())
+    }
+
+    /// Remove the in-memory counter of a deleted index.
+    pub fn forget(&self, index_uid: &str) {
+        self.counts.write().unwrap().remove(index_uid);
+    }
+
+    /// Exchange the counters of two swapped indexes in the database.
+    ///
+    /// The in-memory counters must be exchanged with [`Self::swap_in_memory`]
+    /// once the transaction is committed.
+    pub fn swap(&self, wtxn: &mut RwTxn, lhs: &str, rhs: &str) -> Result<()> {
+        let lhs_count = self.persisted.get(wtxn, lhs)?;
+        let rhs_count = self.persisted.get(wtxn, rhs)?;
+        for (index_uid, count) in [(lhs, rhs_count), (rhs, lhs_count)] {
+            match count {
+                Some(count) => self.persisted.put(wtxn, index_uid, &count)?,
+                None => {
+                    self.persisted.delete(wtxn, index_uid)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Exchange the in-memory counters of two swapped indexes.
+    pub fn swap_in_memory(&self, lhs: &str, rhs: &str) {
+        let mut counts = self.counts.write().unwrap();
+        let lhs_count = counts.remove(lhs);
+        let rhs_count = counts.remove(rhs);
+        if let Some(count) = lhs_count {
+            counts.insert(rhs.to_string(), count);
+        }
+        if let Some(count) = rhs_count {
+            counts.insert(lhs.to_string(), count);
+        }
+    }
+
+    /// Write the current value of every counter to the database if any of them changed
+    /// since the last time they were persisted.
+    pub fn persist(&self, env: &Env) -> Result<()> {
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        self.write_counts(env).map_err(|e| {
+            // the counters have not been written, try again on the next tick
+            self.dirty.store(true, Ordering::Relaxed);
+            e
+        })
+    }
+
+    fn write_counts(&self, env: &Env) -> Result<()> {
+        let mut wtxn = env.write_txn()?;
+        let counts: Vec<_> = self
+            .counts
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(index_uid, count)| (index_uid.clone(), count.load(Ordering::Relaxed)))
+            .collect();
+
+        for (index_uid, count) in counts {
+            self.persisted.put(&mut wtxn, &index_uid, &count)?;
+        }
+        wtxn.commit()?;
+
+        Ok(())
+    }
+}
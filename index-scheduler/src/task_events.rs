@@ -0,0 +1,89 @@
+use std::str::FromStr;
+
+use meilisearch_types::tasks::Status;
+use tokio::sync::broadcast;
+
+use crate::TaskId;
+
+/// The number of events a subscriber can lag behind before being disconnected.
+const CHANNEL_CAPACITY: usize = 10_000;
+
+/// A change of the status of a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskEvent {
+    pub task_uid: TaskId,
+    pub status: Status,
+}
+
+impl TaskEvent {
+    /// The identifier of the event, `{task_uid}-{status}`.
+    ///
+    /// It only depends on the task and its status so that it stays meaningful across restarts.
+    pub fn id(&self) -> String {
+        format!("{}-{}", self.task_uid, self.status)
+    }
+
+    /// Parse an identifier returned by [`TaskEvent::id`].
+    pub fn from_id(id: &str) -> Option<Self> {
+        let (task_uid, status) = id.trim().split_once('-')?;
+        Some(TaskEvent { task_uid: task_uid.parse().ok()?, status: Status::from_str(status).ok()? })
+    }
+}
+
+/// Broadcasts the status transitions of the tasks to the subscribers.
+///
+/// Only the committed transitions are published, along with the `processing` status
+/// that is never persisted but published when a batch starts.
+#[derive(Clone)]
+pub(crate) struct TaskEvents {
+    sender: broadcast::Sender<TaskEvent>,
+}
+
+impl Default for TaskEvents {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        TaskEvents { sender }
+    }
+}
+
+impl TaskEvents {
+    /// Start staging the status changes written in a write transaction.
+    ///
+    /// The staged status changes are only published by calling [`StagedEvents::publish`]
+    /// once the transaction is committed, they are discarded when dropped.
+    pub fn staging(&self) -> StagedEvents<'_> {
+        StagedEvents { task_events: self, staged: Vec::new() }
+    }
+
+    /// Publish a status change that is already committed.
+    pub fn publish(&self, task_uid: TaskId, status: Status) {
+        // there is nothing to do when no one is subscribed.
+        let _ = self.sender.send(TaskEvent { task_uid, status });
+    }
+
+    /// Receive the events published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<TaskEvent> {
+        self.sender.subscribe()
+    }
+}
+
+/// The status changes written in a transaction that is not yet committed.
+#[must_use]
+pub(crate) struct StagedEvents<'a> {
+    task_events: &'a TaskEvents,
+    staged: Vec<(TaskId, Status)>,
+}
+
+impl StagedEvents<'_> {
+    /// Record a status change that must only be published once the transaction is committed.
+    pub fn stage(&mut self, task_uid: TaskId, status: Status) {
+        self.staged.push((task_uid, status));
+    }
+
+    /// Publish the staged status changes, must be called after the transaction is committed.
+    pub fn publish(self) {
+        for (task_uid, status) in self.staged {
+            self.task_events.publish(task_uid, status);
+        }
+    }
+}
@@ -1,15 +1,24 @@
 //! Utility functions on the DBs. Mainly getter and setters.
 
 use std::collections::{BTreeSet, HashSet};
+use std::fs::File;
+use std::io;
 use std::ops::Bound;
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use meilisearch_types::heed::types::{DecodeIgnore, OwnedType};
 use meilisearch_types::heed::{Database, RoTxn, RwTxn};
 use meilisearch_types::milli::{CboRoaringBitmapCodec, BEU32};
 use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status};
 use roaring::{MultiOps, RoaringBitmap};
+use tempfile::NamedTempFile;
 use time::OffsetDateTime;
 
+use crate::index_mapper::IndexMapper;
+use crate::task_events::StagedEvents;
 use crate::{Error, IndexScheduler, Result, Task, TaskId, BEI128};
 
 impl IndexScheduler {
@@ -44,7 +53,12 @@ impl IndexScheduler {
             .collect::<Result<_>>()
     }
 
-    pub(crate) fn update_task(&self, wtxn: &mut RwTxn, task: &Task) -> Result<()> {
+    pub(crate) fn update_task(
+        &self,
+        wtxn: &mut RwTxn,
+        staged_events: &mut StagedEvents,
+        task: &Task,
+    ) -> Result<()> {
         let old_task = self.get_task(wtxn, task.uid)?.ok_or(Error::CorruptedTaskQueue)?;
 
         debug_assert_eq!(old_task.uid, task.uid);
@@ -60,6 +74,7 @@ impl IndexScheduler {
             self.update_status(wtxn, task.status, |bitmap| {
                 bitmap.insert(task.uid);
             })?;
+            staged_events.stage(task.uid, task.status);
         }
 
         if old_task.kind.as_kind() != task.kind.as_kind() {
@@ -94,7 +109,8 @@ impl IndexScheduler {
 
     /// Returns the whole set of tasks that belongs to this index.
     pub(crate) fn index_tasks(&self, rtxn: &RoTxn, index: &str) -> Result<RoaringBitmap> {
-        Ok(self.index_tasks.get(rtxn, index)?.unwrap_or_default())
+        let index = self.index_mapper.canonical_name(index);
+        Ok(self.index_tasks.get(rtxn, &index)?.unwrap_or_default())
     }
 
     pub(crate) fn update_index(
@@ -103,12 +119,13 @@ impl IndexScheduler {
         index: &str,
         f: impl Fn(&mut RoaringBitmap),
     ) -> Result<()> {
-        let mut tasks = self.index_tasks(wtxn, index)?;
+        let index = self.index_mapper.canonical_name(index);
+        let mut tasks = self.index_tasks(wtxn, &index)?;
         f(&mut tasks);
         if tasks.is_empty() {
-            self.index_tasks.delete(wtxn, index)?;
+            self.index_tasks.delete(wtxn, &index)?;
         } else {
-            self.index_tasks.put(wtxn, index, &tasks)?;
+            self.index_tasks.put(wtxn, &index, &tasks)?;
         }
 
         Ok(())
@@ -233,6 +250,41 @@ pub(crate) fn map_bound<T, U>(bound: Bound<T>, map: impl FnOnce(T) -> U) -> Boun
     }
 }
 
+/// Replaces the index uids of the task by the names their indexes are stored under.
+pub fn canonicalize_index_uids_in_task(task: &mut Task, index_mapper: &IndexMapper) {
+    use KindWithContent as K;
+    let mut index_uids = vec![];
+    match &mut task.kind {
+        K::DocumentAdditionOrUpdate { index_uid, .. }
+        | K::DocumentDeletion { index_uid, .. }
+        | K::DocumentDeletionByFilter { index_uid, .. }
+        | K::DocumentClear { index_uid }
+        | K::SettingsUpdate { index_uid, .. }
+        | K::IndexDeletion { index_uid }
+        | K::IndexCreation { index_uid, .. }
+        | K::IndexUpdate { index_uid, .. } => index_uids.push(index_uid),
+        K::IndexSwap { swaps } => {
+            for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
+                index_uids.push(lhs);
+                index_uids.push(rhs);
+            }
+        }
+        K::TaskCancelation { .. }
+        | K::TaskDeletion { .. }
+        | K::DumpCreation { .. }
+        | K::SnapshotCreation => (),
+    };
+    if let Some(Details::IndexSwap { swaps }) = &mut task.details {
+        for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
+            index_uids.push(lhs);
+            index_uids.push(rhs);
+        }
+    }
+    for index_uid in index_uids {
+        *index_uid = index_mapper.canonical_name(index_uid).into_owned();
+    }
+}
+
 pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
     use KindWithContent as K;
     let mut index_uids = vec![];
@@ -392,6 +444,9 @@ impl IndexScheduler {
                     KindWithContent::TaskCancelation { query: _, tasks } => {
                         assert!(tasks.contains(uid));
                     }
+                    KindWithContent::IndexDeletion { index_uid } => {
+                        assert!(kind.indexes().contains(&index_uid.as_str()));
+                    }
                     _ => panic!(),
                 }
             }
@@ -402,7 +457,11 @@ impl IndexScheduler {
                             assert_eq!(&sw1, sw2);
                         }
                     }
-                    Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
+                    Details::DocumentAdditionOrUpdate {
+                        received_documents,
+                        indexed_documents,
+                        ..
+                    } => {
                         assert_eq!(kind.as_kind(), Kind::DocumentAdditionOrUpdate);
                         match indexed_documents {
                             Some(indexed_documents) => {
@@ -421,7 +480,11 @@ impl IndexScheduler {
                             }
                         }
                     }
-                    Details::SettingsUpdate { settings: _ } => {
+                    Details::SettingsUpdate {
+                        settings: _,
+                        reindexed_documents: _,
+                        changed_settings: _,
+                    } => {
                         assert_eq!(kind.as_kind(), Kind::SettingsUpdate);
                     }
                     Details::IndexInfo { primary_key: pk1 } => match &kind {
@@ -596,3 +659,92 @@ pub fn dichotomic_search(start_point: usize, mut is_good: impl FnMut(usize) -> b
         }
     }
 }
+
+/// The number of times a file operation of a dump or snapshot creation is retried
+/// after failing or timing out.
+pub(crate) const IO_RETRIES: usize = 2;
+
+type IoJob = Box<dyn FnOnce() + Send>;
+
+/// A single thread running the file operations of the dump and snapshot creations.
+///
+/// An operation that doesn't complete in time can't be interrupted, it keeps the thread busy
+/// and the following operations wait behind it, so the threads never pile up when a
+/// filesystem hangs. The thread is started on first use and restarted if an operation panics.
+#[derive(Default)]
+pub(crate) struct IoWorker {
+    sender: Mutex<Option<mpsc::Sender<IoJob>>>,
+}
+
+impl IoWorker {
+    /// Runs a file operation on the worker, retrying it up to [`IO_RETRIES`] times when it fails.
+    ///
+    /// An attempt that doesn't complete within `timeout`, including the time spent waiting for
+    /// the previous operations, fails with an [`Error::IoTimeout`] instead of hanging the
+    /// scheduler, which can happen on network filesystems. The value it eventually returns
+    /// is dropped without being used.
+    pub fn run_with_timeout<T, F>(
+        &self,
+        operation: &'static str,
+        timeout: Duration,
+        f: F,
+    ) -> Result<T>
+    where
+        T: Send + 'static,
+        F: Fn() -> io::Result<T> + Send + Sync + 'static,
+    {
+        let f = Arc::new(f);
+        let mut attempt = 0;
+        loop {
+            let (sender, receiver) = mpsc::sync_channel(1);
+            let f = f.clone();
+            self.send(Box::new(move || {
+                let _ = sender.send(f());
+            }))?;
+            let error = match receiver.recv_timeout(timeout) {
+                Ok(Ok(value)) => return Ok(value),
+                Ok(Err(e)) => Error::IoError(e),
+                Err(RecvTimeoutError::Timeout) => Error::IoTimeout { operation, timeout },
+                // the operation panicked, there is no point in retrying.
+                Err(RecvTimeoutError::Disconnected) => return Err(Error::ProcessBatchPanicked),
+            };
+            if attempt == IO_RETRIES {
+                return Err(error);
+            }
+            attempt += 1;
+            log::warn!("Retrying the {operation} after a failure: {error}");
+        }
+    }
+
+    fn send(&self, mut job: IoJob) -> Result<()> {
+        let mut sender = self.sender.lock().unwrap();
+        if let Some(worker) = sender.as_ref() {
+            match worker.send(job) {
+                Ok(()) => return Ok(()),
+                // the thread stopped after an operation panicked.
+                Err(mpsc::SendError(returned)) => job = returned,
+            }
+        }
+
+        let (worker, receiver) = mpsc::channel::<IoJob>();
+        std::thread::Builder::new().name(String::from("io-worker")).spawn(move || {
+            for job in receiver {
+                job();
+            }
+        })?;
+        // the receiver lives as long as the thread that was just started.
+        let _ = worker.send(job);
+        *sender = Some(worker);
+        Ok(())
+    }
+}
+
+/// Copies the file at `src` to a temporary file created in `dir`.
+///
+/// The copy is deleted when dropped, it's up to the caller to persist it once it's complete
+/// so that a copy that timed out is never moved into place.
+pub(crate) fn copy_to_temp_file(src: &Path, dir: &Path) -> io::Result<NamedTempFile> {
+    let mut temp_file = NamedTempFile::new_in(dir)?;
+    io::copy(&mut File::open(src)?, temp_file.as_file_mut())?;
+    Ok(temp_file)
+}
@@ -9,8 +9,12 @@ use crate::{AuthController, HeedAuthStore, Result};
 const KEYS_PATH: &str = "keys";
 
 impl AuthController {
-    pub fn dump(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result<()> {
-        let mut store = HeedAuthStore::new(&src)?;
+    pub fn dump(
+        src: impl AsRef<Path>,
+        dst: impl AsRef<Path>,
+        auth_store_size: usize,
+    ) -> Result<()> {
+        let mut store = HeedAuthStore::new_with_size(&src, auth_store_size)?;
 
         // do not attempt to close the database on drop!
         store.set_drop_on_close(false);
@@ -27,8 +31,12 @@ impl AuthController {
         Ok(())
     }
 
-    pub fn load_dump(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result<()> {
-        let store = HeedAuthStore::new(&dst)?;
+    pub fn load_dump(
+        src: impl AsRef<Path>,
+        dst: impl AsRef<Path>,
+        auth_store_size: usize,
+    ) -> Result<()> {
+        let store = HeedAuthStore::new_with_size(&dst, auth_store_size)?;
 
         let keys_file_path = src.as_ref().join(KEYS_PATH);
 
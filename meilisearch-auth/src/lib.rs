@@ -4,7 +4,8 @@ mod store;
 
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use error::{AuthControllerError, Result};
 use maplit::hashset;
@@ -12,26 +13,39 @@ use meilisearch_types::index_uid_pattern::IndexUidPattern;
 use meilisearch_types::keys::{Action, CreateApiKey, Key, PatchApiKey};
 use meilisearch_types::milli::update::Setting;
 use serde::{Deserialize, Serialize};
-pub use store::open_auth_store_env;
 use store::{generate_key_as_hexa, HeedAuthStore};
+pub use store::{open_auth_store_env, DEFAULT_AUTH_STORE_SIZE};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+/// The minimum time between two updates of the last use date of a key.
+const KEY_LAST_USE_INTERVAL: Duration = Duration::from_secs(60);
+
 #[derive(Clone)]
 pub struct AuthController {
     store: Arc<HeedAuthStore>,
     master_key: Option<String>,
+    /// When the use of each key was last recorded by this instance.
+    key_uses: Arc<Mutex<HashMap<Uuid, Instant>>>,
 }
 
 impl AuthController {
-    pub fn new(db_path: impl AsRef<Path>, master_key: &Option<String>) -> Result<Self> {
-        let store = HeedAuthStore::new(db_path)?;
+    pub fn new(
+        db_path: impl AsRef<Path>,
+        master_key: &Option<String>,
+        auth_store_size: usize,
+    ) -> Result<Self> {
+        let store = HeedAuthStore::new_with_size(db_path, auth_store_size)?;
 
         if store.is_empty()? {
             generate_default_keys(&store)?;
         }
 
-        Ok(Self { store: Arc::new(store), master_key: master_key.clone() })
+        Ok(Self {
+            store: Arc::new(store),
+            master_key: master_key.clone(),
+            key_uses: Arc::default(),
+        })
     }
 
     /// Return `Ok(())` if the auth controller is able to access one of its database.
@@ -58,17 +72,23 @@ impl AuthController {
     }
 
     pub fn update_key(&self, uid: Uuid, patch: PatchApiKey) -> Result<Key> {
-        let mut key = self.get_key(uid)?;
-        match patch.description {
-            Setting::NotSet => (),
-            description => key.description = description.set(),
-        };
-        match patch.name {
-            Setting::NotSet => (),
-            name => key.name = name.set(),
-        };
-        key.updated_at = OffsetDateTime::now_utc();
-        self.store.put_api_key(key)
+        self.store
+            .update_api_key(uid, |key| {
+                match patch.description {
+                    Setting::NotSet => (),
+                    description => key.description = description.set(),
+                };
+                match patch.name {
+                    Setting::NotSet => (),
+                    name => key.name = name.set(),
+                };
+                match patch.expires_at {
+                    Setting::NotSet => (),
+                    expires_at => key.expires_at = expires_at.set(),
+                };
+                key.updated_at = OffsetDateTime::now_utc();
+            })?
+            .ok_or_else(|| AuthControllerError::ApiKeyNotFound(uid.to_string()))
     }
 
     pub fn get_key(&self, uid: Uuid) -> Result<Key> {
@@ -109,14 +129,69 @@ impl AuthController {
         self.store.list_api_keys()
     }
 
+    pub fn search_keys(&self, query: &str) -> Result<Vec<Key>> {
+        self.store.search_keys(query)
+    }
+
+    /// Returns `true` if the use of the key must be recorded with [`Self::record_key_use`],
+    /// the use of a key being recorded at most once per minute to avoid a write on every request.
+    pub fn should_record_key_use(&self, uid: Uuid) -> bool {
+        let now = Instant::now();
+        let mut key_uses = self.key_uses.lock().unwrap();
+        match key_uses.get(&uid) {
+            Some(recorded_at) if now.duration_since(*recorded_at) < KEY_LAST_USE_INTERVAL => false,
+            _ => {
+                key_uses.insert(uid, now);
+                true
+            }
+        }
+    }
+
+    pub fn record_key_use(&self, uid: Uuid) -> Result<()> {
+        self.store.record_key_use(uid, OffsetDateTime::now_utc())
+    }
+
     pub fn delete_key(&self, uid: Uuid) -> Result<()> {
         if self.store.delete_api_key(uid)? {
+            self.key_uses.lock().unwrap().remove(&uid);
             Ok(())
         } else {
             Err(AuthControllerError::ApiKeyNotFound(uid.to_string()))
         }
     }
 
+    /// Deletes all the expired keys and returns how many were deleted.
+    pub fn delete_expired_keys(&self) -> Result<usize> {
+        let expired = self.store.delete_expired_keys(OffsetDateTime::now_utc())?;
+        let mut key_uses = self.key_uses.lock().unwrap();
+        for uid in &expired {
+            key_uses.remove(uid);
+        }
+        Ok(expired.len())
+    }
+
+    /// Deletes the given keys, identified by their uid or by the key itself.
+    ///
+    /// Returns for each key whether it has been deleted, unknown keys are reported as not deleted.
+    pub fn delete_keys(&self, keys: Vec<String>) -> Result<Vec<(String, bool)>> {
+        keys.into_iter()
+            .map(|key| {
+                let uid = match Uuid::parse_str(&key) {
+                    Ok(uid) => Some(uid),
+                    Err(_) => self.get_optional_uid_from_encoded_key(key.as_bytes())?,
+                };
+                let deleted = match uid {
+                    Some(uid) if self.store.delete_api_key(uid)? => {
+                        self.key_uses.lock().unwrap().remove(&uid);
+                        true
+                    }
+                    _ => false,
+                };
+                Ok((key, deleted))
+            })
+            .collect()
+    }
+
     pub fn get_master_key(&self) -> Option<&String> {
         self.master_key.as_ref()
     }
@@ -214,6 +289,18 @@ impl AuthFilter {
                 .unwrap_or(true)
     }
 
+    /// Returns `true` if every index the key gives access to is also accessible with this filter.
+    ///
+    /// A key without any index is only visible when all the indexes are accessible.
+    pub fn is_key_visible(&self, key: &Key) -> bool {
+        self.all_indexes_authorized()
+            || (!key.indexes.is_empty()
+                && key
+                    .indexes
+                    .iter()
+                    .all(|index| !index.matches_all() && self.is_index_authorized(index)))
+    }
+
     pub fn get_index_search_rules(&self, index: &str) -> Option<IndexSearchRules> {
         if !self.is_index_authorized(index) {
             return None;
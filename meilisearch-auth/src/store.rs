@@ -22,7 +22,8 @@ use uuid::Uuid;
 use super::error::{AuthControllerError, Result};
 use super::{Action, Key};
 
-const AUTH_STORE_SIZE: usize = 1_073_741_824; //1GiB
+/// The default maximum size of the auth store.
+pub const DEFAULT_AUTH_STORE_SIZE: usize = 1_073_741_824; //1GiB
 const AUTH_DB_PATH: &str = "auth";
 const KEY_DB_NAME: &str = "api-keys";
 const KEY_ID_ACTION_INDEX_EXPIRATION_DB_NAME: &str = "keyid-action-index-expiration";
@@ -43,18 +44,27 @@ impl Drop for HeedAuthStore {
     }
 }
 
-pub fn open_auth_store_env(path: &Path) -> milli::heed::Result<milli::heed::Env> {
+pub fn open_auth_store_env(path: &Path, map_size: usize) -> milli::heed::Result<milli::heed::Env> {
     let mut options = EnvOpenOptions::new();
-    options.map_size(AUTH_STORE_SIZE); // 1GB
+    options.map_size(map_size);
     options.max_dbs(2);
     options.open(path)
 }
 
 impl HeedAuthStore {
+    /// Opens the auth store with the default map size.
     pub fn new(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new_with_size(path, DEFAULT_AUTH_STORE_SIZE)
+    }
+
+    /// Opens the auth store with the given map size, the maximum size it can grow to.
+    ///
+    /// The map size must be a multiple of the OS page size. A store that is already bigger
+    /// than the map size on disk is opened with its current size and can't grow anymore.
+    pub fn new_with_size(path: impl AsRef<Path>, map_size: usize) -> Result<Self> {
         let path = path.as_ref().join(AUTH_DB_PATH);
         create_dir_all(&path)?;
-        let env = Arc::new(open_auth_store_env(path.as_ref())?);
+        let env = Arc::new(open_auth_store_env(path.as_ref(), map_size)?);
         let mut wtxn = env.write_txn()?;
         let keys = env.create_database(&mut wtxn, Some(KEY_DB_NAME))?;
         let action_keyid_index_expiration =
@@ -91,13 +101,34 @@ impl HeedAuthStore {
     }
 
     pub fn put_api_key(&self, key: Key) -> Result<Key> {
-        let uid = key.uid;
         let mut wtxn = self.env.write_txn()?;
+        self.put_api_key_in_txn(&mut wtxn, &key)?;
+        wtxn.commit()?;
+
+        Ok(key)
+    }
 
-        self.keys.put(&mut wtxn, uid.as_bytes(), &key)?;
+    /// Applies the update to the key within a single write transaction,
+    /// so that a concurrent write of the key, like the record of its use, is not overwritten.
+    pub fn update_api_key(&self, uid: Uuid, update: impl FnOnce(&mut Key)) -> Result<Option<Key>> {
+        let mut wtxn = self.env.write_txn()?;
+        let mut key = match self.keys.get(&wtxn, uid.as_bytes())? {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        update(&mut key);
+        self.put_api_key_in_txn(&mut wtxn, &key)?;
+        wtxn.commit()?;
+
+        Ok(Some(key))
+    }
+
+    fn put_api_key_in_txn(&self, wtxn: &mut RwTxn, key: &Key) -> Result<()> {
+        let uid = key.uid;
+        self.keys.put(wtxn, uid.as_bytes(), key)?;
 
         // delete key from inverted database before refilling it.
-        self.delete_key_from_inverted_db(&mut wtxn, &uid)?;
+        self.delete_key_from_inverted_db(wtxn, &uid)?;
         // create inverted database.
         let db = self.action_keyid_index_expiration;
 
@@ -151,12 +182,12 @@ impl HeedAuthStore {
         for action in actions {
             if no_index_restriction {
                 // If there is no index restriction we put None.
-                db.put(&mut wtxn, &(&uid, &action, None), &key.expires_at)?;
+                db.put(wtxn, &(&uid, &action, None), &key.expires_at)?;
             } else {
                 // else we create a key for each index.
                 for index in key.indexes.iter() {
                     db.put(
-                        &mut wtxn,
+                        wtxn,
                         &(&uid, &action, Some(index.to_string().as_bytes())),
                         &key.expires_at,
                     )?;
@@ -164,9 +195,7 @@ impl HeedAuthStore {
             }
         }
 
-        wtxn.commit()?;
-
-        Ok(key)
+        Ok(())
     }
 
     pub fn get_api_key(&self, uid: Uuid) -> Result<Option<Key>> {
@@ -174,6 +203,20 @@ impl HeedAuthStore {
         self.keys.get(&rtxn, uid.as_bytes()).map_err(|e| e.into())
     }
 
+    /// Marks the key as used at the given date. Only the stored key is written,
+    /// the inverted database doesn't depend on its last use date.
+    pub fn record_key_use(&self, uid: Uuid, used_at: OffsetDateTime) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        // the key may have been deleted since it was used.
+        if let Some(mut key) = self.keys.get(&wtxn, uid.as_bytes())? {
+            key.last_used_at = Some(used_at);
+            self.keys.put(&mut wtxn, uid.as_bytes(), &key)?;
+        }
+        wtxn.commit()?;
+
+        Ok(())
+    }
+
     pub fn get_uid_from_encoded_key(
         &self,
         encoded_key: &[u8],
@@ -210,6 +253,27 @@ impl HeedAuthStore {
         Ok(existing)
     }
 
+    /// Deletes the keys that are already expired at `now`, the keys without expiration date are kept.
+    ///
+    /// Returns the uids of the deleted keys.
+    pub fn delete_expired_keys(&self, now: OffsetDateTime) -> Result<Vec<Uuid>> {
+        let mut wtxn = self.env.write_txn()?;
+        let mut expired = Vec::new();
+        for result in self.keys.remap_key_type::<DecodeIgnore>().iter(&wtxn)? {
+            let (_, key) = result?;
+            if key.expires_at.map_or(false, |expires_at| expires_at <= now) {
+                expired.push(key.uid);
+            }
+        }
+        for uid in &expired {
+            self.keys.delete(&mut wtxn, uid.as_bytes())?;
+            self.delete_key_from_inverted_db(&mut wtxn, uid)?;
+        }
+        wtxn.commit()?;
+
+        Ok(expired)
+    }
+
     pub fn delete_all_keys(&self) -> Result<()> {
         let mut wtxn = self.env.write_txn()?;
         self.keys.clear(&mut wtxn)?;
@@ -228,6 +292,16 @@ impl HeedAuthStore {
         Ok(list)
     }
 
+    /// Returns the keys whose description contains the query, ignoring the case.
+    pub fn search_keys(&self, query: &str) -> Result<Vec<Key>> {
+        let query = query.to_lowercase();
+        let mut list = self.list_api_keys()?;
+        list.retain(|key| {
+            key.description.as_ref().map_or(false, |desc| desc.to_lowercase().contains(&query))
+        });
+        Ok(list)
+    }
+
     pub fn get_expiration_date(
         &self,
         uid: Uuid,
@@ -133,7 +133,10 @@ pub fn immutable_field_error(field: &str, accepted: &[&str], code: Code) -> Dese
 // Implement a convenience function to build a `missing_field` error
 macro_rules! make_missing_field_convenience_builder {
     ($err_code:ident, $fn_name:ident) => {
-        impl DeserrJsonError<$err_code> {
+        impl<Format> DeserrError<Format, $err_code>
+        where
+            Self: DeserializeError,
+        {
             pub fn $fn_name(field: &str, location: ValuePointerRef) -> Self {
                 let x = deserr::take_cf_content(Self::error::<Infallible>(
                     None,
@@ -151,6 +154,8 @@ make_missing_field_convenience_builder!(MissingApiKeyExpiresAt, missing_api_key_
 make_missing_field_convenience_builder!(MissingApiKeyIndexes, missing_api_key_indexes);
 make_missing_field_convenience_builder!(MissingSwapIndexes, missing_swap_indexes);
 make_missing_field_convenience_builder!(MissingDocumentFilter, missing_document_filter);
+make_missing_field_convenience_builder!(MissingTaskUids, missing_task_uids);
+make_missing_field_convenience_builder!(MissingSuggestPrefix, missing_suggest_prefix);
 make_missing_field_convenience_builder!(
     MissingFacetSearchFacetName,
     missing_facet_search_facet_name
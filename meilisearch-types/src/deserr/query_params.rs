@@ -12,6 +12,7 @@ We also use other helper types such as `CS` (i.e. comma-separated) from `serde_c
 */
 
 use std::convert::Infallible;
+use std::num::NonZeroUsize;
 use std::ops::Deref;
 use std::str::FromStr;
 
@@ -103,6 +104,7 @@ macro_rules! impl_from_query_param_wrap_original_value_in_error {
     };
 }
 impl_from_query_param_wrap_original_value_in_error!(usize, DeserrParseIntError);
+impl_from_query_param_wrap_original_value_in_error!(NonZeroUsize, DeserrParseIntError);
 impl_from_query_param_wrap_original_value_in_error!(u32, DeserrParseIntError);
 impl_from_query_param_wrap_original_value_in_error!(bool, DeserrParseBoolError);
 
@@ -1,6 +1,7 @@
+use std::collections::BTreeMap;
 use std::fmt::{self, Debug, Display};
 use std::fs::File;
-use std::io::{self, Seek, Write};
+use std::io::{self, Read, Seek, Write};
 use std::marker::PhantomData;
 
 use memmap2::MmapOptions;
@@ -19,6 +20,26 @@ pub enum PayloadType {
     Ndjson,
     Json,
     Csv { delimiter: u8 },
+    Tsv,
+}
+
+impl PayloadType {
+    /// Returns the format of the payloads sent with the given `Content-Type`, ignoring
+    /// its parameters. The csv payloads use the default `,` delimiter.
+    pub fn from_content_type(content_type: &str) -> Option<PayloadType> {
+        let essence = content_type.split(';').next().unwrap_or_default().trim();
+        if essence.eq_ignore_ascii_case("application/json") {
+            Some(PayloadType::Json)
+        } else if essence.eq_ignore_ascii_case("application/x-ndjson") {
+            Some(PayloadType::Ndjson)
+        } else if essence.eq_ignore_ascii_case("text/csv") {
+            Some(PayloadType::Csv { delimiter: b',' })
+        } else if essence.eq_ignore_ascii_case("text/tab-separated-values") {
+            Some(PayloadType::Tsv)
+        } else {
+            None
+        }
+    }
 }
 
 impl fmt::Display for PayloadType {
@@ -27,6 +48,7 @@ impl fmt::Display for PayloadType {
             PayloadType::Ndjson => f.write_str("ndjson"),
             PayloadType::Json => f.write_str("json"),
             PayloadType::Csv { .. } => f.write_str("csv"),
+            PayloadType::Tsv => f.write_str("tsv"),
         }
     }
 }
@@ -103,22 +125,88 @@ impl ErrorCode for DocumentFormatError {
     }
 }
 
-/// Reads CSV from input and write an obkv batch to writer.
-pub fn read_csv(file: &File, writer: impl Write + Seek, delimiter: u8) -> Result<u64> {
+/// The number of documents read from a payload.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DocumentsCount {
+    pub documents: u64,
+    /// The number of documents for which a primary key value was generated.
+    pub generated_ids: u64,
+}
+
+fn documents_builder<W: Write>(
+    writer: W,
+    field_renames: &BTreeMap<String, String>,
+    generate_ids_for: Option<&str>,
+) -> DocumentsBatchBuilder<W> {
     let mut builder = DocumentsBatchBuilder::new(writer);
+    if let Some(primary_key) = generate_ids_for {
+        // The ids are generated before the fields are renamed.
+        let source = field_renames.iter().find(|(_, target)| *target == primary_key);
+        builder.generate_missing_ids(source.map_or(primary_key, |(source, _)| source));
+    }
+    builder
+}
+
+fn documents_count<W: Write>(builder: &DocumentsBatchBuilder<W>) -> DocumentsCount {
+    DocumentsCount {
+        documents: builder.documents_count() as u64,
+        generated_ids: builder.generated_ids_count() as u64,
+    }
+}
+
+/// Reads CSV from input and write an obkv batch to writer.
+///
+/// When a `header` is given the input has no header line of its own and the given
+/// one, made of the field names separated by the `delimiter`, is used instead.
+pub fn read_csv(
+    file: &File,
+    writer: impl Write + Seek,
+    delimiter: u8,
+    header: Option<&str>,
+    field_renames: &BTreeMap<String, String>,
+    generate_ids_for: Option<&str>,
+) -> Result<DocumentsCount> {
+    let mut builder = documents_builder(writer, field_renames, generate_ids_for);
     let mmap = unsafe { MmapOptions::new().map(file)? };
-    let csv = csv::ReaderBuilder::new().delimiter(delimiter).from_reader(mmap.as_ref());
+    let header = header.map(|header| format!("{header}\n")).unwrap_or_default();
+    let input = header.as_bytes().chain(mmap.as_ref());
+    let csv = csv::ReaderBuilder::new().delimiter(delimiter).from_reader(input);
     builder.append_csv(csv).map_err(|e| (PayloadType::Csv { delimiter }, e))?;
+    builder.rename_fields(field_renames).map_err(|e| (PayloadType::Csv { delimiter }, e))?;
 
-    let count = builder.documents_count();
+    let count = documents_count(&builder);
     let _ = builder.into_inner().map_err(DocumentFormatError::Io)?;
 
-    Ok(count as u64)
+    Ok(count)
+}
+
+/// Reads TSV from input and write an obkv batch to writer.
+pub fn read_tsv(
+    file: &File,
+    writer: impl Write + Seek,
+    field_renames: &BTreeMap<String, String>,
+    generate_ids_for: Option<&str>,
+) -> Result<DocumentsCount> {
+    let mut builder = documents_builder(writer, field_renames, generate_ids_for);
+    let mmap = unsafe { MmapOptions::new().map(file)? };
+    let tsv = csv::ReaderBuilder::new().delimiter(b'\t').from_reader(mmap.as_ref());
+    builder.append_csv(tsv).map_err(|e| (PayloadType::Tsv, e))?;
+    builder.rename_fields(field_renames).map_err(|e| (PayloadType::Tsv, e))?;
+
+    let count = documents_count(&builder);
+    let _ = builder.into_inner().map_err(DocumentFormatError::Io)?;
+
+    Ok(count)
 }
 
 /// Reads JSON from temporary file  and write an obkv batch to writer.
-pub fn read_json(file: &File, writer: impl Write + Seek) -> Result<u64> {
-    let mut builder = DocumentsBatchBuilder::new(writer);
+pub fn read_json(
+    file: &File,
+    writer: impl Write + Seek,
+    field_renames: &BTreeMap<String, String>,
+    generate_ids_for: Option<&str>,
+) -> Result<DocumentsCount> {
+    let mut builder = documents_builder(writer, field_renames, generate_ids_for);
     let mmap = unsafe { MmapOptions::new().map(file)? };
     let mut deserializer = serde_json::Deserializer::from_slice(&mmap);
 
@@ -143,27 +231,34 @@ pub fn read_json(file: &File, writer: impl Write + Seek) -> Result<u64> {
             builder.append_json_object(&content).map_err(DocumentFormatError::Io)?;
         }
     }
+    builder.rename_fields(field_renames).map_err(|e| (PayloadType::Json, e))?;
 
-    let count = builder.documents_count();
+    let count = documents_count(&builder);
     let _ = builder.into_inner().map_err(DocumentFormatError::Io)?;
 
-    Ok(count as u64)
+    Ok(count)
 }
 
 /// Reads JSON from temporary file  and write an obkv batch to writer.
-pub fn read_ndjson(file: &File, writer: impl Write + Seek) -> Result<u64> {
-    let mut builder = DocumentsBatchBuilder::new(writer);
+pub fn read_ndjson(
+    file: &File,
+    writer: impl Write + Seek,
+    field_renames: &BTreeMap<String, String>,
+    generate_ids_for: Option<&str>,
+) -> Result<DocumentsCount> {
+    let mut builder = documents_builder(writer, field_renames, generate_ids_for);
     let mmap = unsafe { MmapOptions::new().map(file)? };
 
     for result in serde_json::Deserializer::from_slice(&mmap).into_iter() {
         let object = result.map_err(Error::Json).map_err(|e| (PayloadType::Ndjson, e))?;
         builder.append_json_object(&object).map_err(Into::into).map_err(DocumentFormatError::Io)?;
     }
+    builder.rename_fields(field_renames).map_err(|e| (PayloadType::Ndjson, e))?;
 
-    let count = builder.documents_count();
+    let count = documents_count(&builder);
     let _ = builder.into_inner().map_err(Into::into).map_err(DocumentFormatError::Io)?;
 
-    Ok(count as u64)
+    Ok(count)
 }
 
 /// The actual handling of the deserialization process in serde
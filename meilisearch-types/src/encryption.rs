@@ -0,0 +1,183 @@
+//! Encryption at rest of the attributes listed in the `encryptedAttributes` setting.
+//!
+//! The values of these attributes are encrypted with AES-256-GCM before being given to milli
+//! and are stored as base64 strings. The name of the attribute is used as associated data so
+//! that a value can't be moved from an attribute to another.
+//!
+//! The secret configured by the user can be any UTF-8 string. The 256 bits AES key is derived
+//! from it with PBKDF2-HMAC-SHA256, using [`KEY_DERIVATION_ITERATIONS`] iterations and the
+//! [`KEY_DERIVATION_SALT`] salt, so that a secret with a low entropy is costly to brute-force.
+
+use std::collections::BTreeSet;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2::{self, PBKDF2_HMAC_SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde_json::Value;
+
+use crate::error::{Code, ErrorCode};
+use crate::Document;
+
+/// The salt with which the AES key is derived from the secret configured by the user.
+pub const KEY_DERIVATION_SALT: &[u8] = b"meilisearch-encrypted-attributes";
+/// The number of PBKDF2 iterations with which the AES key is derived.
+pub const KEY_DERIVATION_ITERATIONS: u32 = 100_000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EncryptionError {
+    #[error("The index has encrypted attributes but no encryption key is configured. The `--encryption-key` option must be set.")]
+    MissingKey,
+    #[error("The value of the `{0}` attribute could not be encrypted.")]
+    Encryption(String),
+    #[error("The value of the `{0}` attribute could not be decrypted. The encryption key may have been changed.")]
+    Decryption(String),
+}
+
+impl ErrorCode for EncryptionError {
+    fn error_code(&self) -> Code {
+        match self {
+            EncryptionError::MissingKey => Code::MissingEncryptionKey,
+            EncryptionError::Encryption(_) | EncryptionError::Decryption(_) => Code::Internal,
+        }
+    }
+}
+
+/// Encrypts and decrypts the values of the encrypted attributes with a key
+/// derived from the secret configured by the user.
+#[derive(Debug, Clone)]
+pub struct FieldCipher {
+    key: Arc<LessSafeKey>,
+    rng: SystemRandom,
+}
+
+impl FieldCipher {
+    pub fn new(secret: &str) -> Self {
+        let mut key = [0; 32];
+        pbkdf2::derive(
+            PBKDF2_HMAC_SHA256,
+            NonZeroU32::new(KEY_DERIVATION_ITERATIONS).unwrap(),
+            KEY_DERIVATION_SALT,
+            secret.as_bytes(),
+            &mut key,
+        );
+        // The derived key always has the length of an AES-256 key.
+        let key = UnboundKey::new(&AES_256_GCM, &key).unwrap();
+        FieldCipher { key: Arc::new(LessSafeKey::new(key)), rng: SystemRandom::new() }
+    }
+
+    /// Encrypts the JSON value of the given attribute into a base64 string
+    /// containing the nonce followed by the encrypted value.
+    pub fn encrypt(&self, attribute: &str, value: &Value) -> Result<Value, EncryptionError> {
+        let error = || EncryptionError::Encryption(attribute.to_string());
+
+        let mut nonce = [0; NONCE_LEN];
+        self.rng.fill(&mut nonce).map_err(|_| error())?;
+        let mut in_out = serde_json::to_vec(value).map_err(|_| error())?;
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(attribute.as_bytes()),
+                &mut in_out,
+            )
+            .map_err(|_| error())?;
+
+        let mut encrypted = nonce.to_vec();
+        encrypted.extend_from_slice(&in_out);
+        Ok(Value::String(BASE64.encode(encrypted)))
+    }
+
+    /// Decrypts a value previously encrypted with [`FieldCipher::encrypt`].
+    pub fn decrypt(&self, attribute: &str, value: &Value) -> Result<Value, EncryptionError> {
+        let error = || EncryptionError::Decryption(attribute.to_string());
+
+        let encrypted = value.as_str().ok_or_else(error)?;
+        let mut encrypted = BASE64.decode(encrypted).map_err(|_| error())?;
+        if encrypted.len() < NONCE_LEN {
+            return Err(error());
+        }
+        let mut in_out = encrypted.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&encrypted).map_err(|_| error())?;
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::from(attribute.as_bytes()), &mut in_out)
+            .map_err(|_| error())?;
+        serde_json::from_slice(plaintext).map_err(|_| error())
+    }
+
+    /// Encrypts the values of the top-level `attributes` present in the document.
+    pub fn encrypt_document(
+        &self,
+        attributes: &BTreeSet<String>,
+        document: &mut Document,
+    ) -> Result<(), EncryptionError> {
+        for attribute in attributes {
+            if let Some(value) = document.get_mut(attribute) {
+                *value = self.encrypt(attribute, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Decrypts the values of the top-level `attributes` present in the document.
+///
+/// Fails if one of these attributes is present and no cipher is configured.
+pub fn decrypt_document(
+    cipher: Option<&FieldCipher>,
+    attributes: &BTreeSet<String>,
+    document: &mut Document,
+) -> Result<(), EncryptionError> {
+    for attribute in attributes {
+        if let Some(value) = document.get_mut(attribute) {
+            let cipher = cipher.ok_or(EncryptionError::MissingKey)?;
+            *value = cipher.decrypt(attribute, value)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn encrypt_and_decrypt_document() {
+        let cipher = FieldCipher::new("a very secret key");
+        let attributes = BTreeSet::from([String::from("email"), String::from("address")]);
+        let original = json!({
+            "id": 1,
+            "email": "jean.bob@example.com",
+            "address": { "city": "Paris" },
+        });
+
+        let mut document = original.as_object().unwrap().clone();
+        cipher.encrypt_document(&attributes, &mut document).unwrap();
+        assert_eq!(document["id"], json!(1));
+        assert!(!document["email"].as_str().unwrap().contains("jean.bob"));
+        assert!(document["address"].is_string());
+
+        decrypt_document(Some(&cipher), &attributes, &mut document).unwrap();
+        assert_eq!(Value::Object(document), original);
+    }
+
+    #[test]
+    fn decrypt_with_another_key_or_attribute() {
+        let cipher = FieldCipher::new("a very secret key");
+        let encrypted = cipher.encrypt("email", &json!("jean.bob@example.com")).unwrap();
+
+        assert!(cipher.decrypt("address", &encrypted).is_err());
+        assert!(FieldCipher::new("another key").decrypt("email", &encrypted).is_err());
+
+        let mut document = Document::new();
+        document.insert(String::from("email"), encrypted);
+        let attributes = BTreeSet::from([String::from("email")]);
+        let result = decrypt_document(None, &attributes, &mut document);
+        assert!(matches!(result, Err(EncryptionError::MissingKey)));
+    }
+}
@@ -184,10 +184,10 @@ DocumentNotFound                      , InvalidRequest       , NOT_FOUND;
 DumpAlreadyProcessing                 , InvalidRequest       , CONFLICT;
 DumpNotFound                          , InvalidRequest       , NOT_FOUND;
 DumpProcessFailed                     , Internal             , INTERNAL_SERVER_ERROR;
+DuplicateDocumentId                   , InvalidRequest       , BAD_REQUEST;
 DuplicateIndexFound                   , InvalidRequest       , BAD_REQUEST;
 ImmutableApiKeyActions                , InvalidRequest       , BAD_REQUEST;
 ImmutableApiKeyCreatedAt              , InvalidRequest       , BAD_REQUEST;
-ImmutableApiKeyExpiresAt              , InvalidRequest       , BAD_REQUEST;
 ImmutableApiKeyIndexes                , InvalidRequest       , BAD_REQUEST;
 ImmutableApiKeyKey                    , InvalidRequest       , BAD_REQUEST;
 ImmutableApiKeyUid                    , InvalidRequest       , BAD_REQUEST;
@@ -205,23 +205,34 @@ Internal                              , Internal             , INTERNAL_SERVER_E
 InvalidApiKey                         , Auth                 , FORBIDDEN ;
 InvalidApiKeyActions                  , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyDescription              , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyExpiredFilter            , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyExpiresAt                , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyIndexes                  , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyLimit                    , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyName                     , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyOffset                   , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeySearch                   , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyUid                      , InvalidRequest       , BAD_REQUEST ;
 InvalidContentType                    , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
 InvalidDocumentCsvDelimiter           , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentCsvHeader              , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentRenameFields           , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentAutogenerateIds        , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentMaxIndexingMemory      , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentMaxIndexingThreads     , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentDuplicates             , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFields                 , InvalidRequest       , BAD_REQUEST ;
 MissingDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentGeoField               , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentVersionField           , InvalidRequest       , BAD_REQUEST ;
 InvalidVectorDimensions               , InvalidRequest       , BAD_REQUEST ;
 InvalidVectorsType                    , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentId                     , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentLimit                  , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentOffset                 , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentShowChecksum           , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentSort                   , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexLimit                     , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexOffset                    , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexPrimaryKey                , InvalidRequest       , BAD_REQUEST ;
@@ -232,9 +243,15 @@ InvalidSearchAttributesToHighlight    , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToRetrieve     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropLength               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker               , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDisjunctiveFacets        , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSortFacetValuesBy        , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSortFacetValuesDirection , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFacets                   , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFilter                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchGroupBy                  , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchGroupLimit               , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchMaxGroups                , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHighlightPostTag         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHighlightPreTag          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHitsPerPage              , InvalidRequest       , BAD_REQUEST ;
@@ -247,14 +264,21 @@ InvalidFacetSearchQuery               , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchName                , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchVector                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowMatchesPosition      , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchShowMatchCount           , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScore         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScoreDetails  , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchShowRankingStages        , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchShowProcessedQuery       , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSort                     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchWithinIds                , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchLocales                  , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDisabledRankingRules     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDisplayedAttributes    , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDistinctAttribute      , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFaceting               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFilterableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPagination             , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsPinnedInMemory         , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsRankingRules           , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSearchableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSortableAttributes     , InvalidRequest       , BAD_REQUEST ;
@@ -262,10 +286,18 @@ InvalidSettingsStopWords              , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsNonSeparatorTokens     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSeparatorTokens        , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDictionary             , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsCaseSensitive          , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsLanguages              , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsSearchableNumbers      , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsTieBreakingSort        , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsEncryptedAttributes    , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSynonyms               , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsBidirectionalSynonyms  , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsTypoTolerance          , InvalidRequest       , BAD_REQUEST ;
 InvalidState                          , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidStoreFile                      , Internal             , INTERNAL_SERVER_ERROR ;
+InvalidSuggestLimit                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestPrefix                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSwapDuplicateIndexFound        , InvalidRequest       , BAD_REQUEST ;
 InvalidSwapIndexes                    , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterEnqueuedAt            , InvalidRequest       , BAD_REQUEST ;
@@ -281,6 +313,7 @@ InvalidTaskStatuses                   , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskTypes                      , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskUids                       , InvalidRequest       , BAD_REQUEST  ;
 IoError                               , System               , UNPROCESSABLE_ENTITY;
+IoTimeout                             , System               , INTERNAL_SERVER_ERROR ;
 FeatureNotEnabled                     , InvalidRequest       , BAD_REQUEST ;
 MalformedPayload                      , InvalidRequest       , BAD_REQUEST ;
 MaxFieldsLimitExceeded                , InvalidRequest       , BAD_REQUEST ;
@@ -290,12 +323,15 @@ MissingApiKeyIndexes                  , InvalidRequest       , BAD_REQUEST ;
 MissingAuthorizationHeader            , Auth                 , UNAUTHORIZED ;
 MissingContentType                    , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
 MissingDocumentId                     , InvalidRequest       , BAD_REQUEST ;
+MissingEncryptionKey                  , InvalidRequest       , BAD_REQUEST ;
 MissingFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
 MissingIndexUid                       , InvalidRequest       , BAD_REQUEST ;
 MissingMasterKey                      , Auth                 , UNAUTHORIZED ;
 MissingPayload                        , InvalidRequest       , BAD_REQUEST ;
+MissingSuggestPrefix                  , InvalidRequest       , BAD_REQUEST ;
 MissingSwapIndexes                    , InvalidRequest       , BAD_REQUEST ;
 MissingTaskFilters                    , InvalidRequest       , BAD_REQUEST ;
+MissingTaskUids                       , InvalidRequest       , BAD_REQUEST ;
 NoSpaceLeftOnDevice                   , System               , UNPROCESSABLE_ENTITY;
 PayloadTooLarge                       , InvalidRequest       , PAYLOAD_TOO_LARGE ;
 TaskNotFound                          , InvalidRequest       , NOT_FOUND ;
@@ -336,6 +372,7 @@ impl ErrorCode for milli::Error {
                     UserError::InvalidDocumentId { .. } | UserError::TooManyDocumentIds { .. } => {
                         Code::InvalidDocumentId
                     }
+                    UserError::DuplicateDocumentId { .. } => Code::DuplicateDocumentId,
                     UserError::NoPrimaryKeyCandidateFound => Code::IndexPrimaryKeyNoCandidateFound,
                     UserError::MultiplePrimaryKeyCandidatesFound { .. } => {
                         Code::IndexPrimaryKeyMultipleCandidatesFound
@@ -358,6 +395,13 @@ impl ErrorCode for milli::Error {
                     UserError::InvalidMinTypoWordLenSetting(_, _) => {
                         Code::InvalidSettingsTypoTolerance
                     }
+                    UserError::InvalidLanguage(_) => Code::InvalidSettingsLanguages,
+                    UserError::InvalidTieBreakingSort(_) => Code::InvalidSettingsTieBreakingSort,
+                    UserError::InvalidEncryptedAttribute { .. }
+                    | UserError::EncryptedAttributesOnNonEmptyIndex
+                    | UserError::NestedEncryptedAttribute(_) => {
+                        Code::InvalidSettingsEncryptedAttributes
+                    }
                 }
             }
         }
@@ -62,6 +62,7 @@ impl CreateApiKey {
             expires_at,
             created_at: now,
             updated_at: now,
+            last_used_at: None,
         }
     }
 }
@@ -75,7 +76,6 @@ fn deny_immutable_fields_api_key(
         "uid" => immutable_field_error(field, accepted, Code::ImmutableApiKeyUid),
         "actions" => immutable_field_error(field, accepted, Code::ImmutableApiKeyActions),
         "indexes" => immutable_field_error(field, accepted, Code::ImmutableApiKeyIndexes),
-        "expiresAt" => immutable_field_error(field, accepted, Code::ImmutableApiKeyExpiresAt),
         "createdAt" => immutable_field_error(field, accepted, Code::ImmutableApiKeyCreatedAt),
         "updatedAt" => immutable_field_error(field, accepted, Code::ImmutableApiKeyUpdatedAt),
         _ => deserr::take_cf_content(DeserrJsonError::<BadRequest>::error::<Infallible>(
@@ -93,6 +93,9 @@ pub struct PatchApiKey {
     pub description: Setting<String>,
     #[deserr(default, error = DeserrJsonError<InvalidApiKeyName>)]
     pub name: Setting<String>,
+    /// A `null` expiration date makes the key never expire.
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyExpiresAt>, try_from(Setting<String>) = parse_patched_expiration_date -> ParseOffsetDateTimeError)]
+    pub expires_at: Setting<OffsetDateTime>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -110,6 +113,13 @@ pub struct Key {
     pub created_at: OffsetDateTime,
     #[serde(with = "time::serde::rfc3339")]
     pub updated_at: OffsetDateTime,
+    /// The last time the key authorized a request, updated at most once per minute.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "time::serde::rfc3339::option"
+    )]
+    pub last_used_at: Option<OffsetDateTime>,
 }
 
 impl Key {
@@ -125,6 +135,7 @@ impl Key {
             expires_at: None,
             created_at: now,
             updated_at: now,
+            last_used_at: None,
         }
     }
 
@@ -140,6 +151,7 @@ impl Key {
             expires_at: None,
             created_at: now,
             updated_at: now,
+            last_used_at: None,
         }
     }
 }
@@ -147,7 +159,22 @@ impl Key {
 fn parse_expiration_date(
     string: Option<String>,
 ) -> std::result::Result<Option<OffsetDateTime>, ParseOffsetDateTimeError> {
-    let Some(string) = string else { return Ok(None) };
+    string.map(parse_future_date).transpose()
+}
+
+fn parse_patched_expiration_date(
+    setting: Setting<String>,
+) -> std::result::Result<Setting<OffsetDateTime>, ParseOffsetDateTimeError> {
+    match setting {
+        Setting::Set(string) => parse_future_date(string).map(Setting::Set),
+        Setting::Reset => Ok(Setting::Reset),
+        Setting::NotSet => Ok(Setting::NotSet),
+    }
+}
+
+fn parse_future_date(
+    string: String,
+) -> std::result::Result<OffsetDateTime, ParseOffsetDateTimeError> {
     let datetime = if let Ok(datetime) = OffsetDateTime::parse(&string, &Rfc3339) {
         datetime
     } else if let Ok(primitive_datetime) = PrimitiveDateTime::parse(
@@ -173,7 +200,7 @@ fn parse_expiration_date(
         return Err(ParseOffsetDateTimeError(string));
     };
     if datetime > OffsetDateTime::now_utc() {
-        Ok(Some(datetime))
+        Ok(datetime)
     } else {
         Err(ParseOffsetDateTimeError(string))
     }
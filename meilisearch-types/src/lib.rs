@@ -1,6 +1,7 @@
 pub mod compression;
 pub mod deserr;
 pub mod document_formats;
+pub mod encryption;
 pub mod error;
 pub mod facet_values_sort;
 pub mod features;
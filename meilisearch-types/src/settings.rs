@@ -167,6 +167,14 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsRankingRules>)]
     pub ranking_rules: Setting<Vec<RankingRuleView>>,
+    /// Asc/desc rules ordering the documents that are still tied after the ranking rules.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsTieBreakingSort>)]
+    pub tie_breaking_sort: Setting<Vec<RankingRuleView>>,
+    /// Attributes whose values are encrypted at rest with the `--encryption-key`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsEncryptedAttributes>)]
+    pub encrypted_attributes: Setting<BTreeSet<String>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsStopWords>)]
     pub stop_words: Setting<BTreeSet<String>>,
@@ -179,9 +187,25 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsDictionary>)]
     pub dictionary: Setting<BTreeSet<String>>,
+    /// Disables the case folding of the documents and queries.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsCaseSensitive>)]
+    pub case_sensitive: Setting<bool>,
+    /// Languages present in the documents, the language detection is restricted to them.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsLanguages>)]
+    pub languages: Setting<BTreeSet<String>>,
+    /// Indexes the numbers of the searchable attributes as text.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSearchableNumbers>)]
+    pub searchable_numbers: Setting<bool>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSynonyms>)]
     pub synonyms: Setting<BTreeMap<String, Vec<String>>>,
+    /// Groups of words that are all synonyms of each other.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsBidirectionalSynonyms>)]
+    pub bidirectional_synonyms: Setting<Vec<Vec<String>>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsDistinctAttribute>)]
     pub distinct_attribute: Setting<String>,
@@ -194,6 +218,10 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPagination>)]
     pub pagination: Setting<PaginationSettings>,
+    /// Asks the OS to keep the pages of the index in memory.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsPinnedInMemory>)]
+    pub pinned_in_memory: Setting<bool>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -208,15 +236,22 @@ impl Settings<Checked> {
             filterable_attributes: Setting::Reset,
             sortable_attributes: Setting::Reset,
             ranking_rules: Setting::Reset,
+            tie_breaking_sort: Setting::Reset,
+            encrypted_attributes: Setting::Reset,
             stop_words: Setting::Reset,
             synonyms: Setting::Reset,
+            bidirectional_synonyms: Setting::Reset,
             non_separator_tokens: Setting::Reset,
             separator_tokens: Setting::Reset,
             dictionary: Setting::Reset,
+            case_sensitive: Setting::Reset,
+            languages: Setting::Reset,
+            searchable_numbers: Setting::Reset,
             distinct_attribute: Setting::Reset,
             typo_tolerance: Setting::Reset,
             faceting: Setting::Reset,
             pagination: Setting::Reset,
+            pinned_in_memory: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -228,15 +263,22 @@ impl Settings<Checked> {
             filterable_attributes,
             sortable_attributes,
             ranking_rules,
+            tie_breaking_sort,
+            encrypted_attributes,
             stop_words,
             non_separator_tokens,
             separator_tokens,
             dictionary,
+            case_sensitive,
+            languages,
+            searchable_numbers,
             synonyms,
+            bidirectional_synonyms,
             distinct_attribute,
             typo_tolerance,
             faceting,
             pagination,
+            pinned_in_memory,
             ..
         } = self;
 
@@ -246,15 +288,22 @@ impl Settings<Checked> {
             filterable_attributes,
             sortable_attributes,
             ranking_rules,
+            tie_breaking_sort,
+            encrypted_attributes,
             stop_words,
             non_separator_tokens,
             separator_tokens,
             dictionary,
+            case_sensitive,
+            languages,
+            searchable_numbers,
             synonyms,
+            bidirectional_synonyms,
             distinct_attribute,
             typo_tolerance,
             faceting,
             pagination,
+            pinned_in_memory,
             _kind: PhantomData,
         }
     }
@@ -264,7 +313,8 @@ impl Settings<Unchecked> {
     pub fn check(self) -> Settings<Checked> {
         let displayed_attributes = match self.displayed_attributes {
             Setting::Set(fields) => {
-                if fields.iter().any(|f| f == "*") {
+                // the exclusions must be kept along the wildcard
+                if fields.iter().any(|f| f == "*") && !fields.iter().any(|f| f.starts_with('-')) {
                     Setting::Reset
                 } else {
                     Setting::Set(fields)
@@ -290,15 +340,22 @@ impl Settings<Unchecked> {
             filterable_attributes: self.filterable_attributes,
             sortable_attributes: self.sortable_attributes,
             ranking_rules: self.ranking_rules,
+            tie_breaking_sort: self.tie_breaking_sort,
+            encrypted_attributes: self.encrypted_attributes,
             stop_words: self.stop_words,
             synonyms: self.synonyms,
+            bidirectional_synonyms: self.bidirectional_synonyms,
             non_separator_tokens: self.non_separator_tokens,
             separator_tokens: self.separator_tokens,
             dictionary: self.dictionary,
+            case_sensitive: self.case_sensitive,
+            languages: self.languages,
+            searchable_numbers: self.searchable_numbers,
             distinct_attribute: self.distinct_attribute,
             typo_tolerance: self.typo_tolerance,
             faceting: self.faceting,
             pagination: self.pagination,
+            pinned_in_memory: self.pinned_in_memory,
             _kind: PhantomData,
         }
     }
@@ -350,6 +407,20 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match settings.tie_breaking_sort {
+        Setting::Set(ref criteria) => {
+            builder.set_tie_breaking_sort(criteria.iter().map(|c| c.clone().into()).collect())
+        }
+        Setting::Reset => builder.reset_tie_breaking_sort(),
+        Setting::NotSet => (),
+    }
+
+    match settings.encrypted_attributes {
+        Setting::Set(ref fields) => builder.set_encrypted_fields(fields.clone()),
+        Setting::Reset => builder.reset_encrypted_fields(),
+        Setting::NotSet => (),
+    }
+
     match settings.stop_words {
         Setting::Set(ref stop_words) => builder.set_stop_words(stop_words.clone()),
         Setting::Reset => builder.reset_stop_words(),
@@ -378,12 +449,36 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match settings.case_sensitive {
+        Setting::Set(value) => builder.set_case_sensitive(value),
+        Setting::Reset => builder.reset_case_sensitive(),
+        Setting::NotSet => (),
+    }
+
+    match settings.languages {
+        Setting::Set(ref languages) => builder.set_languages(languages.clone()),
+        Setting::Reset => builder.reset_languages(),
+        Setting::NotSet => (),
+    }
+
+    match settings.searchable_numbers {
+        Setting::Set(value) => builder.set_searchable_numbers(value),
+        Setting::Reset => builder.reset_searchable_numbers(),
+        Setting::NotSet => (),
+    }
+
     match settings.synonyms {
         Setting::Set(ref synonyms) => builder.set_synonyms(synonyms.clone().into_iter().collect()),
         Setting::Reset => builder.reset_synonyms(),
         Setting::NotSet => (),
     }
 
+    match settings.bidirectional_synonyms {
+        Setting::Set(ref groups) => builder.set_bidirectional_synonyms(groups.clone()),
+        Setting::Reset => builder.reset_bidirectional_synonyms(),
+        Setting::NotSet => (),
+    }
+
     match settings.distinct_attribute {
         Setting::Set(ref attr) => builder.set_distinct_field(attr.clone()),
         Setting::Reset => builder.reset_distinct_field(),
@@ -476,6 +571,12 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_pagination_max_total_hits(),
         Setting::NotSet => (),
     }
+
+    match settings.pinned_in_memory {
+        Setting::Set(value) => builder.set_pinned_in_memory(value),
+        Setting::Reset => builder.reset_pinned_in_memory(),
+        Setting::NotSet => (),
+    }
 }
 
 pub fn settings(
@@ -489,11 +590,13 @@ pub fn settings(
         .user_defined_searchable_fields(rtxn)?
         .map(|fields| fields.into_iter().map(String::from).collect());
 
-    let filterable_attributes = index.filterable_fields(rtxn)?.into_iter().collect();
+    let filterable_attributes = index.user_defined_filterable_fields(rtxn)?.into_iter().collect();
 
     let sortable_attributes = index.sortable_fields(rtxn)?.into_iter().collect();
 
     let criteria = index.criteria(rtxn)?;
+    let tie_breaking_sort = index.tie_breaking_sort(rtxn)?;
+    let encrypted_attributes = index.encrypted_fields(rtxn)?;
 
     let stop_words = index
         .stop_words(rtxn)?
@@ -506,10 +609,14 @@ pub fn settings(
     let non_separator_tokens = index.non_separator_tokens(rtxn)?.unwrap_or_default();
     let separator_tokens = index.separator_tokens(rtxn)?.unwrap_or_default();
     let dictionary = index.dictionary(rtxn)?.unwrap_or_default();
+    let case_sensitive = index.case_sensitive(rtxn)?;
+    let languages = index.languages_names(rtxn)?.unwrap_or_default();
+    let searchable_numbers = index.searchable_numbers(rtxn)?;
 
     let distinct_field = index.distinct_field(rtxn)?.map(String::from);
 
     let synonyms = index.user_defined_synonyms(rtxn)?;
+    let bidirectional_synonyms = index.bidirectional_synonyms(rtxn)?;
 
     let min_typo_word_len = MinWordSizeTyposSetting {
         one_typo: Setting::Set(index.min_word_len_one_typo(rtxn)?),
@@ -549,6 +656,8 @@ pub fn settings(
         ),
     };
 
+    let pinned_in_memory = index.pinned_in_memory(rtxn)?;
+
     Ok(Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -561,22 +670,112 @@ pub fn settings(
         filterable_attributes: Setting::Set(filterable_attributes),
         sortable_attributes: Setting::Set(sortable_attributes),
         ranking_rules: Setting::Set(criteria.iter().map(|c| c.clone().into()).collect()),
+        tie_breaking_sort: Setting::Set(
+            tie_breaking_sort.iter().map(|c| c.clone().into()).collect(),
+        ),
+        encrypted_attributes: Setting::Set(encrypted_attributes),
         stop_words: Setting::Set(stop_words),
         non_separator_tokens: Setting::Set(non_separator_tokens),
         separator_tokens: Setting::Set(separator_tokens),
         dictionary: Setting::Set(dictionary),
+        case_sensitive: Setting::Set(case_sensitive),
+        languages: Setting::Set(languages),
+        searchable_numbers: Setting::Set(searchable_numbers),
         distinct_attribute: match distinct_field {
             Some(field) => Setting::Set(field),
             None => Setting::Reset,
         },
         synonyms: Setting::Set(synonyms),
+        bidirectional_synonyms: Setting::Set(bidirectional_synonyms),
         typo_tolerance: Setting::Set(typo_tolerance),
         faceting: Setting::Set(faceting),
         pagination: Setting::Set(pagination),
+        pinned_in_memory: Setting::Set(pinned_in_memory),
         _kind: PhantomData,
     })
 }
 
+/// A field referenced by a setting but absent from all the documents of the index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsWarning {
+    pub setting: String,
+    pub field: String,
+    pub message: String,
+}
+
+/// Returns a warning for every field referenced by the given settings that does
+/// not appear in any of the documents of the index.
+pub fn validate_settings(
+    index: &Index,
+    rtxn: &crate::heed::RoTxn,
+    settings: &Settings<Unchecked>,
+) -> Result<Vec<SettingsWarning>, milli::Error> {
+    let fields_ids_map = index.fields_ids_map(rtxn)?;
+    let is_known = |field: &str| {
+        fields_ids_map
+            .names()
+            .any(|name| milli::is_faceted_by(name, field) || milli::is_faceted_by(field, name))
+    };
+
+    let mut referenced: Vec<(&str, &str)> = Vec::new();
+    if let Setting::Set(fields) = &settings.searchable_attributes {
+        referenced.extend(fields.iter().map(|f| ("searchableAttributes", f.as_str())));
+    }
+    if let Setting::Set(fields) = &settings.displayed_attributes {
+        // the exclusions of the wildcard can reference fields not yet present
+        let fields = fields.iter().filter(|f| !f.starts_with('-'));
+        referenced.extend(fields.map(|f| ("displayedAttributes", f.as_str())));
+    }
+    if let Setting::Set(fields) = &settings.filterable_attributes {
+        referenced.extend(fields.iter().map(|f| ("filterableAttributes", f.as_str())));
+    }
+    if let Setting::Set(fields) = &settings.sortable_attributes {
+        referenced.extend(fields.iter().map(|f| ("sortableAttributes", f.as_str())));
+    }
+    if let Setting::Set(rules) = &settings.ranking_rules {
+        referenced.extend(rules.iter().filter_map(|rule| match rule {
+            RankingRuleView::Asc(field) | RankingRuleView::Desc(field) => {
+                Some(("rankingRules", field.as_str()))
+            }
+            _ => None,
+        }));
+    }
+    if let Setting::Set(rules) = &settings.tie_breaking_sort {
+        referenced.extend(rules.iter().filter_map(|rule| match rule {
+            RankingRuleView::Asc(field) | RankingRuleView::Desc(field) => {
+                Some(("tieBreakingSort", field.as_str()))
+            }
+            _ => None,
+        }));
+    }
+    if let Setting::Set(field) = &settings.distinct_attribute {
+        referenced.push(("distinctAttribute", field.as_str()));
+    }
+    if let Setting::Set(TypoSettings { disable_on_attributes: Setting::Set(fields), .. }) =
+        &settings.typo_tolerance
+    {
+        referenced.extend(fields.iter().map(|f| ("typoTolerance", f.as_str())));
+    }
+    if let Setting::Set(FacetingSettings { sort_facet_values_by: Setting::Set(fields), .. }) =
+        &settings.faceting
+    {
+        referenced.extend(fields.keys().map(|f| ("faceting", f.as_str())));
+    }
+
+    Ok(referenced
+        .into_iter()
+        .filter(|(_, field)| *field != "*" && !is_known(field))
+        .map(|(setting, field)| SettingsWarning {
+            setting: setting.to_string(),
+            field: field.to_string(),
+            message: format!(
+                "The field `{field}` referenced in `{setting}` is not present in any document."
+            ),
+        })
+        .collect())
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserr)]
 #[deserr(try_from(&String) = FromStr::from_str -> CriterionError)]
 pub enum RankingRuleView {
@@ -686,15 +885,22 @@ pub(crate) mod test {
             filterable_attributes: Setting::NotSet,
             sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
+            tie_breaking_sort: Setting::NotSet,
+            encrypted_attributes: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
+            case_sensitive: Setting::NotSet,
+            languages: Setting::NotSet,
+            searchable_numbers: Setting::NotSet,
             synonyms: Setting::NotSet,
+            bidirectional_synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            pinned_in_memory: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -710,15 +916,22 @@ pub(crate) mod test {
             filterable_attributes: Setting::NotSet,
             sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
+            tie_breaking_sort: Setting::NotSet,
+            encrypted_attributes: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
+            case_sensitive: Setting::NotSet,
+            languages: Setting::NotSet,
+            searchable_numbers: Setting::NotSet,
             synonyms: Setting::NotSet,
+            bidirectional_synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            pinned_in_memory: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -4,7 +4,7 @@ use std::fmt::{Display, Write};
 use std::str::FromStr;
 
 use enum_iterator::Sequence;
-use milli::update::IndexDocumentsMethod;
+use milli::update::{DuplicateDocuments, IndexDocumentsMethod};
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize, Serializer};
 use time::{Duration, OffsetDateTime};
@@ -92,6 +92,16 @@ pub enum KindWithContent {
         method: IndexDocumentsMethod,
         content_file: Uuid,
         documents_count: u64,
+        /// Lowers the resources used to index the documents of this task.
+        #[serde(default)]
+        indexing_limits: Option<IndexingLimits>,
+        /// What to do with the documents of the batch sharing the same id.
+        #[serde(default)]
+        duplicate_documents: DuplicateDocuments,
+        /// The field holding the version of the documents, a document is only applied
+        /// if its version matches the version of the stored document.
+        #[serde(default)]
+        version_field: Option<String>,
         allow_index_creation: bool,
     },
     DocumentDeletion {
@@ -140,6 +150,16 @@ pub enum KindWithContent {
     SnapshotCreation,
 }
 
+/// The resources a document addition can use, on top of the limits of the indexer configuration.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexingLimits {
+    /// The maximum memory, in bytes, used by the indexer.
+    pub max_memory: Option<usize>,
+    /// The maximum number of threads used by the indexer.
+    pub max_threads: Option<usize>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct IndexSwap {
@@ -192,6 +212,20 @@ impl KindWithContent {
         }
     }
 
+    /// Returns `true` if processing this task creates its index when it doesn't exist.
+    pub fn can_create_index(&self) -> bool {
+        match self {
+            KindWithContent::IndexCreation { .. } => true,
+            KindWithContent::DocumentAdditionOrUpdate { allow_index_creation, .. } => {
+                *allow_index_creation
+            }
+            KindWithContent::SettingsUpdate { allow_index_creation, is_deletion, .. } => {
+                *allow_index_creation && !is_deletion
+            }
+            _ => false,
+        }
+    }
+
     /// Returns the default `Details` that correspond to this `KindWithContent`,
     /// `None` if it cannot be generated.
     pub fn default_details(&self) -> Option<Details> {
@@ -200,6 +234,9 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    duplicated_documents: None,
+                    rejected_documents: None,
+                    generated_ids: None,
                 })
             }
             KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
@@ -217,9 +254,11 @@ impl KindWithContent {
             KindWithContent::DocumentClear { .. } | KindWithContent::IndexDeletion { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
-            KindWithContent::SettingsUpdate { new_settings, .. } => {
-                Some(Details::SettingsUpdate { settings: new_settings.clone() })
-            }
+            KindWithContent::SettingsUpdate { new_settings, .. } => Some(Details::SettingsUpdate {
+                settings: new_settings.clone(),
+                reindexed_documents: None,
+                changed_settings: None,
+            }),
             KindWithContent::IndexCreation { primary_key, .. }
             | KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
@@ -248,6 +287,9 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: Some(0),
+                    duplicated_documents: None,
+                    rejected_documents: None,
+                    generated_ids: None,
                 })
             }
             KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
@@ -265,9 +307,11 @@ impl KindWithContent {
             KindWithContent::DocumentClear { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
-            KindWithContent::SettingsUpdate { new_settings, .. } => {
-                Some(Details::SettingsUpdate { settings: new_settings.clone() })
-            }
+            KindWithContent::SettingsUpdate { new_settings, .. } => Some(Details::SettingsUpdate {
+                settings: new_settings.clone(),
+                reindexed_documents: None,
+                changed_settings: None,
+            }),
             KindWithContent::IndexDeletion { .. } => None,
             KindWithContent::IndexCreation { primary_key, .. }
             | KindWithContent::IndexUpdate { primary_key, .. } => {
@@ -299,14 +343,19 @@ impl From<&KindWithContent> for Option<Details> {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    duplicated_documents: None,
+                    rejected_documents: None,
+                    generated_ids: None,
                 })
             }
             KindWithContent::DocumentDeletion { .. } => None,
             KindWithContent::DocumentDeletionByFilter { .. } => None,
             KindWithContent::DocumentClear { .. } => None,
-            KindWithContent::SettingsUpdate { new_settings, .. } => {
-                Some(Details::SettingsUpdate { settings: new_settings.clone() })
-            }
+            KindWithContent::SettingsUpdate { new_settings, .. } => Some(Details::SettingsUpdate {
+                settings: new_settings.clone(),
+                reindexed_documents: None,
+                changed_settings: None,
+            }),
             KindWithContent::IndexDeletion { .. } => None,
             KindWithContent::IndexCreation { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
@@ -495,16 +544,60 @@ impl std::error::Error for ParseTaskKindError {}
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum Details {
-    DocumentAdditionOrUpdate { received_documents: u64, indexed_documents: Option<u64> },
-    SettingsUpdate { settings: Box<Settings<Unchecked>> },
-    IndexInfo { primary_key: Option<String> },
-    DocumentDeletion { provided_ids: usize, deleted_documents: Option<u64> },
-    DocumentDeletionByFilter { original_filter: String, deleted_documents: Option<u64> },
-    ClearAll { deleted_documents: Option<u64> },
-    TaskCancelation { matched_tasks: u64, canceled_tasks: Option<u64>, original_filter: String },
-    TaskDeletion { matched_tasks: u64, deleted_tasks: Option<u64>, original_filter: String },
-    Dump { dump_uid: Option<String> },
-    IndexSwap { swaps: Vec<IndexSwap> },
+    DocumentAdditionOrUpdate {
+        received_documents: u64,
+        indexed_documents: Option<u64>,
+        /// The number of documents whose id was already used by a previous document of the batch.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duplicated_documents: Option<u64>,
+        /// The number of documents that were not applied because their version
+        /// didn't match the version of the stored document.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rejected_documents: Option<u64>,
+        /// The number of documents for which a primary key value was generated,
+        /// `None` if the ids were not generated for this task.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        generated_ids: Option<u64>,
+    },
+    SettingsUpdate {
+        settings: Box<Settings<Unchecked>>,
+        /// The number of documents that were reindexed to apply the settings.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reindexed_documents: Option<u64>,
+        /// The settings of the update whose value changed once applied.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        changed_settings: Option<Vec<String>>,
+    },
+    IndexInfo {
+        primary_key: Option<String>,
+    },
+    DocumentDeletion {
+        provided_ids: usize,
+        deleted_documents: Option<u64>,
+    },
+    DocumentDeletionByFilter {
+        original_filter: String,
+        deleted_documents: Option<u64>,
+    },
+    ClearAll {
+        deleted_documents: Option<u64>,
+    },
+    TaskCancelation {
+        matched_tasks: u64,
+        canceled_tasks: Option<u64>,
+        original_filter: String,
+    },
+    TaskDeletion {
+        matched_tasks: u64,
+        deleted_tasks: Option<u64>,
+        original_filter: String,
+    },
+    Dump {
+        dump_uid: Option<String>,
+    },
+    IndexSwap {
+        swaps: Vec<IndexSwap>,
+    },
 }
 
 impl Details {
@@ -521,10 +614,11 @@ impl Details {
             Self::ClearAll { deleted_documents } => *deleted_documents = Some(0),
             Self::TaskCancelation { canceled_tasks, .. } => *canceled_tasks = Some(0),
             Self::TaskDeletion { deleted_tasks, .. } => *deleted_tasks = Some(0),
-            Self::SettingsUpdate { .. }
-            | Self::IndexInfo { .. }
-            | Self::Dump { .. }
-            | Self::IndexSwap { .. } => (),
+            Self::SettingsUpdate { reindexed_documents, changed_settings, .. } => {
+                *reindexed_documents = Some(0);
+                *changed_settings = Some(Vec::new());
+            }
+            Self::IndexInfo { .. } | Self::Dump { .. } | Self::IndexSwap { .. } => (),
         }
 
         details
@@ -254,6 +254,7 @@ struct Infos {
     db_path: bool,
     import_dump: bool,
     dump_dir: bool,
+    dump_and_snapshot_io_timeout: Option<u64>,
     ignore_missing_dump: bool,
     ignore_dump_if_db_exists: bool,
     import_snapshot: bool,
@@ -263,6 +264,13 @@ struct Infos {
     ignore_snapshot_if_db_exists: bool,
     http_addr: bool,
     http_payload_size_limit: Byte,
+    max_auth_store_size: Byte,
+    max_task_history: usize,
+    default_primary_key: bool,
+    case_insensitive_index_uids: bool,
+    max_facets_per_search: Option<usize>,
+    default_documents_limit: usize,
+    max_documents_limit: Option<usize>,
     log_level: String,
     max_indexing_memory: MaxMemory,
     max_indexing_threads: MaxThreads,
@@ -287,9 +295,17 @@ impl From<Opt> for Infos {
             experimental_reduce_indexing_memory_usage,
             http_addr,
             master_key: _,
+            encryption_key: _,
             env,
             max_index_size: _,
             max_task_db_size: _,
+            max_auth_store_size,
+            max_task_history,
+            default_primary_key,
+            case_insensitive_index_uids,
+            max_facets_per_search,
+            default_documents_limit,
+            max_documents_limit,
             http_payload_size_limit,
             ssl_cert_path,
             ssl_key_path,
@@ -307,6 +323,7 @@ impl From<Opt> for Infos {
             ignore_missing_dump,
             ignore_dump_if_db_exists,
             dump_dir,
+            dump_and_snapshot_io_timeout,
             log_level,
             indexer_options,
             config_file_path,
@@ -331,6 +348,7 @@ impl From<Opt> for Infos {
             db_path: db_path != PathBuf::from("./data.ms"),
             import_dump: import_dump.is_some(),
             dump_dir: dump_dir != PathBuf::from("dumps/"),
+            dump_and_snapshot_io_timeout,
             ignore_missing_dump,
             ignore_dump_if_db_exists,
             import_snapshot: import_snapshot.is_some(),
@@ -340,6 +358,13 @@ impl From<Opt> for Infos {
             ignore_snapshot_if_db_exists,
             http_addr: http_addr != default_http_addr(),
             http_payload_size_limit,
+            max_auth_store_size,
+            max_task_history,
+            default_primary_key: default_primary_key.is_some(),
+            case_insensitive_index_uids,
+            max_facets_per_search,
+            default_documents_limit,
+            max_documents_limit,
             log_level: log_level.to_string(),
             max_indexing_memory,
             max_indexing_threads,
@@ -600,11 +625,14 @@ pub struct SearchAggregator {
     max_attributes_to_crop: usize,
     crop_marker: bool,
     show_matches_position: bool,
+    show_match_count: bool,
     crop_length: bool,
 
     // facets
     facets_sum_of_terms: usize,
     facets_total_number_of_facets: usize,
+    facets_disjunctive: bool,
+    facets_sort_values_by: bool,
 
     // scoring
     show_ranking_score: bool,
@@ -626,16 +654,29 @@ impl SearchAggregator {
             crop_length,
             attributes_to_highlight: _,
             show_matches_position,
+            show_match_count,
             show_ranking_score,
             show_ranking_score_details,
+            show_ranking_stages: _,
+            show_processed_query: _,
             filter,
             sort,
             facets: _,
+            disjunctive_facets,
+            sort_facet_values_by,
+            sort_facet_values_direction,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
             attributes_to_search_on,
+            within_ids: _,
+            locales: _,
+            disabled_ranking_rules: _,
+            group_by: _,
+            group_limit: _,
+            max_groups: _,
+            rules_filter: _,
         } = query;
 
         let mut ret = Self::default();
@@ -705,6 +746,10 @@ impl SearchAggregator {
         ret.crop_marker = *crop_marker != DEFAULT_CROP_MARKER();
         ret.crop_length = *crop_length != DEFAULT_CROP_LENGTH();
         ret.show_matches_position = *show_matches_position;
+        ret.show_match_count = *show_match_count;
+        ret.facets_disjunctive = *disjunctive_facets;
+        ret.facets_sort_values_by =
+            sort_facet_values_by.is_some() || sort_facet_values_direction.is_some();
 
         ret.show_ranking_score = *show_ranking_score;
         ret.show_ranking_score_details = *show_ranking_score_details;
@@ -719,8 +764,11 @@ impl SearchAggregator {
             vector: _,
             processing_time_ms,
             hits_info: _,
+            groups: _,
             facet_distribution: _,
             facet_stats: _,
+            ranking_stages: _,
+            processed_query: _,
         } = result;
 
         self.total_succeeded = self.total_succeeded.saturating_add(1);
@@ -757,9 +805,12 @@ impl SearchAggregator {
             max_attributes_to_crop,
             crop_marker,
             show_matches_position,
+            show_match_count,
             crop_length,
             facets_sum_of_terms,
             facets_total_number_of_facets,
+            facets_disjunctive,
+            facets_sort_values_by,
             show_ranking_score,
             show_ranking_score_details,
         } = other;
@@ -823,12 +874,15 @@ impl SearchAggregator {
         self.max_attributes_to_crop = self.max_attributes_to_crop.max(max_attributes_to_crop);
         self.crop_marker |= crop_marker;
         self.show_matches_position |= show_matches_position;
+        self.show_match_count |= show_match_count;
         self.crop_length |= crop_length;
 
         // facets
         self.facets_sum_of_terms = self.facets_sum_of_terms.saturating_add(facets_sum_of_terms);
         self.facets_total_number_of_facets =
             self.facets_total_number_of_facets.saturating_add(facets_total_number_of_facets);
+        self.facets_disjunctive |= facets_disjunctive;
+        self.facets_sort_values_by |= facets_sort_values_by;
 
         // matching strategy
         for (key, value) in matching_strategy.into_iter() {
@@ -870,9 +924,12 @@ impl SearchAggregator {
             max_attributes_to_crop,
             crop_marker,
             show_matches_position,
+            show_match_count,
             crop_length,
             facets_sum_of_terms,
             facets_total_number_of_facets,
+            facets_disjunctive,
+            facets_sort_values_by,
             show_ranking_score,
             show_ranking_score_details,
         } = self;
@@ -927,10 +984,13 @@ impl SearchAggregator {
                     "max_attributes_to_crop": max_attributes_to_crop,
                     "crop_marker": crop_marker,
                     "show_matches_position": show_matches_position,
+                    "show_match_count": show_match_count,
                     "crop_length": crop_length,
                 },
                 "facets": {
                     "avg_facets_number": format!("{:.2}", facets_sum_of_terms as f64 / facets_total_number_of_facets as f64),
+                    "disjunctive": facets_disjunctive,
+                    "sort_values_by": facets_sort_values_by,
                 },
                 "matching_strategy": {
                     "most_used_strategy": matching_strategy.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
@@ -1000,15 +1060,27 @@ impl MultiSearchAggregator {
                     attributes_to_highlight: _,
                     show_ranking_score: _,
                     show_ranking_score_details: _,
+                    show_ranking_stages: _,
+                    show_processed_query: _,
                     show_matches_position: _,
+                    show_match_count: _,
                     filter: _,
                     sort: _,
                     facets: _,
+                    disjunctive_facets: _,
+                    sort_facet_values_by: _,
+                    sort_facet_values_direction: _,
                     highlight_pre_tag: _,
                     highlight_post_tag: _,
                     crop_marker: _,
                     matching_strategy: _,
                     attributes_to_search_on: _,
+                    within_ids: _,
+                    locales: _,
+                    disabled_ranking_rules: _,
+                    group_by: _,
+                    group_limit: _,
+                    max_groups: _,
                 } = query;
 
                 index_uid.as_str()
@@ -1281,7 +1353,17 @@ impl DocumentsAggregator {
         index_creation: bool,
         request: &HttpRequest,
     ) -> Self {
-        let UpdateDocumentsQuery { primary_key, csv_delimiter: _ } = documents_query;
+        let UpdateDocumentsQuery {
+            primary_key,
+            csv_delimiter: _,
+            csv_header: _,
+            rename_fields: _,
+            autogenerate_ids: _,
+            max_indexing_memory: _,
+            max_indexing_threads: _,
+            duplicates: _,
+            version_field: _,
+        } = documents_query;
 
         let mut primary_keys = HashSet::new();
         if let Some(primary_key) = primary_key.clone() {
@@ -2,6 +2,7 @@ use actix_web as aweb;
 use aweb::error::{JsonPayloadError, QueryPayloadError};
 use byte_unit::Byte;
 use meilisearch_types::document_formats::{DocumentFormatError, PayloadType};
+use meilisearch_types::encryption::EncryptionError;
 use meilisearch_types::error::{Code, ErrorCode, ResponseError};
 use meilisearch_types::index_uid::{IndexUid, IndexUidFormatError};
 use serde_json::Value;
@@ -14,6 +15,8 @@ pub enum MeilisearchHttpError {
     MissingContentType(Vec<String>),
     #[error("The Content-Type `{0}` does not support the use of a csv delimiter. The csv delimiter can only be used with the Content-Type `text/csv`.")]
     CsvDelimiterWithWrongContentType(String),
+    #[error("The Content-Type `{0}` does not support the use of a csv header. The csv header can only be used with the Content-Type `text/csv`.")]
+    CsvHeaderWithWrongContentType(String),
     #[error(
         "The Content-Type `{0}` is invalid. Accepted values for the Content-Type header are: {}",
         .1.iter().map(|s| format!("`{}`", s)).collect::<Vec<_>>().join(", ")
@@ -25,6 +28,18 @@ pub enum MeilisearchHttpError {
     EmptyFilter,
     #[error("Invalid syntax for the filter parameter: `expected {}, found: {1}`.", .0.join(", "))]
     InvalidExpression(&'static [&'static str], Value),
+    #[error("The field rename `{0}` is malformed. It must be formatted as `source:target`.")]
+    MalformedFieldRename(String),
+    #[error("The fields `{0}` and `{1}` cannot both be renamed into `{2}`.")]
+    ConflictingFieldRenames(String, String, String),
+    #[error("Unknown locale `{0}` in the `locales` search parameter.")]
+    InvalidSearchLocale(String),
+    #[error("Unknown ranking rule `{0}` in the `disabledRankingRules` search parameter. Only the built-in ranking rules `words`, `typo`, `proximity`, `attribute`, `sort` and `exactness` can be disabled.")]
+    InvalidSearchDisabledRankingRule(String),
+    #[error("Attribute `{0}` is not filterable. The hits can only be grouped by a filterable attribute.")]
+    InvalidSearchGroupBy(String),
+    #[error("The search requests the distribution of {0} facets, but at most {1} facets can be requested by a single search.")]
+    TooManySearchFacets(usize, usize),
     #[error("A {0} payload is missing.")]
     MissingPayload(PayloadType),
     #[error("The provided payload reached the size limit. The maximum accepted payload size is {}.",  Byte::from_bytes(*.0 as u64).get_appropriate_unit(true))]
@@ -44,6 +59,8 @@ pub enum MeilisearchHttpError {
     #[error(transparent)]
     Milli(#[from] meilisearch_types::milli::Error),
     #[error(transparent)]
+    Encryption(#[from] EncryptionError),
+    #[error(transparent)]
     Payload(#[from] PayloadError),
     #[error(transparent)]
     FileStore(#[from] file_store::Error),
@@ -58,11 +75,22 @@ impl ErrorCode for MeilisearchHttpError {
         match self {
             MeilisearchHttpError::MissingContentType(_) => Code::MissingContentType,
             MeilisearchHttpError::CsvDelimiterWithWrongContentType(_) => Code::InvalidContentType,
+            MeilisearchHttpError::CsvHeaderWithWrongContentType(_) => Code::InvalidContentType,
             MeilisearchHttpError::MissingPayload(_) => Code::MissingPayload,
             MeilisearchHttpError::InvalidContentType(_, _) => Code::InvalidContentType,
             MeilisearchHttpError::DocumentNotFound(_) => Code::DocumentNotFound,
             MeilisearchHttpError::EmptyFilter => Code::InvalidDocumentFilter,
             MeilisearchHttpError::InvalidExpression(_, _) => Code::InvalidSearchFilter,
+            MeilisearchHttpError::InvalidSearchLocale(_) => Code::InvalidSearchLocales,
+            MeilisearchHttpError::InvalidSearchDisabledRankingRule(_) => {
+                Code::InvalidSearchDisabledRankingRules
+            }
+            MeilisearchHttpError::InvalidSearchGroupBy(_) => Code::InvalidSearchGroupBy,
+            MeilisearchHttpError::TooManySearchFacets(_, _) => Code::InvalidSearchFacets,
+            MeilisearchHttpError::MalformedFieldRename(_)
+            | MeilisearchHttpError::ConflictingFieldRenames(_, _, _) => {
+                Code::InvalidDocumentRenameFields
+            }
             MeilisearchHttpError::PayloadTooLarge(_) => Code::PayloadTooLarge,
             MeilisearchHttpError::SwapIndexPayloadWrongLength(_) => Code::InvalidSwapIndexes,
             MeilisearchHttpError::IndexUid(e) => e.error_code(),
@@ -70,6 +98,7 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::HeedError(_) => Code::Internal,
             MeilisearchHttpError::IndexScheduler(e) => e.error_code(),
             MeilisearchHttpError::Milli(e) => e.error_code(),
+            MeilisearchHttpError::Encryption(e) => e.error_code(),
             MeilisearchHttpError::Payload(e) => e.error_code(),
             MeilisearchHttpError::FileStore(_) => Code::Internal,
             MeilisearchHttpError::DocumentFormat(e) => e.error_code(),
@@ -109,14 +138,25 @@ pub enum PayloadError {
 impl ErrorCode for PayloadError {
     fn error_code(&self) -> Code {
         match self {
+            // The payload stream is aborted by the client (disconnection, timeout, broken
+            // chunked transfer...), it must not be reported as an internal error.
             PayloadError::Payload(e) => match e {
-                aweb::error::PayloadError::Incomplete(_) => Code::Internal,
-                aweb::error::PayloadError::EncodingCorrupted => Code::Internal,
+                aweb::error::PayloadError::Incomplete(_) => Code::BadRequest,
+                aweb::error::PayloadError::EncodingCorrupted => Code::BadRequest,
                 aweb::error::PayloadError::Overflow => Code::PayloadTooLarge,
-                aweb::error::PayloadError::UnknownLength => Code::Internal,
-                aweb::error::PayloadError::Http2Payload(_) => Code::Internal,
+                aweb::error::PayloadError::UnknownLength => Code::BadRequest,
+                aweb::error::PayloadError::Http2Payload(_) => Code::BadRequest,
+                // A corrupted `Content-Encoding` stream is reported by the decoder as invalid data.
+                aweb::error::PayloadError::Io(e)
+                    if matches!(
+                        e.kind(),
+                        std::io::ErrorKind::InvalidData | std::io::ErrorKind::InvalidInput
+                    ) =>
+                {
+                    Code::BadRequest
+                }
                 aweb::error::PayloadError::Io(_) => Code::Internal,
-                _ => todo!(),
+                _ => Code::BadRequest,
             },
             PayloadError::Json(err) => match err {
                 JsonPayloadError::Overflow { .. } => Code::PayloadTooLarge,
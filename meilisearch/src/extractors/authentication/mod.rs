@@ -212,6 +212,15 @@ pub mod policies {
             if auth.is_key_authorized(key_uuid, action, index).unwrap_or(false)
                 && index.map(|index| auth_filter.is_index_authorized(index)).unwrap_or(true)
             {
+                if auth.should_record_key_use(key_uuid) {
+                    // the use is written off the request path.
+                    let auth = auth.clone();
+                    tokio::task::spawn_blocking(move || {
+                        if let Err(e) = auth.record_key_use(key_uuid) {
+                            log::warn!("Could not record the use of the API key {key_uuid}: {e}");
+                        }
+                    });
+                }
                 return Some(auth_filter);
             }
 
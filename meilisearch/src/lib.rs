@@ -203,6 +203,16 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
             .name(String::from("register-snapshot-tasks"))
             .spawn(move || loop {
                 thread::sleep(snapshot_delay);
+                // We skip this tick if the previous snapshot is still waiting for the scheduler,
+                // otherwise a busy instance would pile up snapshots and run them back to back.
+                match index_scheduler.is_snapshot_pending() {
+                    Ok(true) => continue,
+                    Ok(false) => (),
+                    Err(e) => {
+                        error!("Error while checking the pending snapshots: {}", e);
+                        continue;
+                    }
+                }
                 if let Err(e) = index_scheduler.register(KindWithContent::SnapshotCreation) {
                     error!("Error while registering snapshot: {}", e);
                 }
@@ -220,12 +230,17 @@ fn open_or_create_database_unchecked(
 ) -> anyhow::Result<(IndexScheduler, AuthController)> {
     // we don't want to create anything in the data.ms yet, thus we
     // wrap our two builders in a closure that'll be executed later.
-    let auth_controller = AuthController::new(&opt.db_path, &opt.master_key);
+    let auth_controller = AuthController::new(
+        &opt.db_path,
+        &opt.master_key,
+        opt.max_auth_store_size.get_bytes() as usize,
+    );
     let instance_features = opt.to_instance_features();
     let index_scheduler_builder = || -> anyhow::Result<_> {
         Ok(IndexScheduler::new(IndexSchedulerOptions {
             version_file_path: opt.db_path.join(VERSION_FILE_NAME),
             auth_path: opt.db_path.join("auth"),
+            auth_store_size: opt.max_auth_store_size.get_bytes() as usize,
             tasks_path: opt.db_path.join("tasks"),
             update_file_path: opt.db_path.join("update_files"),
             indexes_path: opt.db_path.join("indexes"),
@@ -236,7 +251,14 @@ fn open_or_create_database_unchecked(
             enable_mdb_writemap: opt.experimental_reduce_indexing_memory_usage,
             indexer_config: (&opt.indexer_options).try_into()?,
             autobatching_enabled: true,
-            max_number_of_tasks: 1_000_000,
+            max_number_of_tasks: opt.max_task_history,
+            default_primary_key: opt.default_primary_key.clone(),
+            case_insensitive_index_uids: opt.case_insensitive_index_uids,
+            max_facets_per_search: opt.max_facets_per_search,
+            default_documents_limit: opt.default_documents_limit,
+            max_documents_limit: opt.max_documents_limit,
+            dump_and_snapshot_io_timeout: opt.dump_and_snapshot_io_timeout.map(Duration::from_secs),
+            encryption_key: opt.encryption_key.clone(),
             index_growth_amount: byte_unit::Byte::from_str("10GiB").unwrap().get_bytes() as usize,
             index_count: DEFAULT_INDEX_COUNT,
             instance_features,
@@ -373,6 +395,7 @@ fn import_dump(
         log::info!("{} documents found.", user_result?);
         builder.execute()?;
         wtxn.commit()?;
+        index.refresh_pinned_pages()?;
         log::info!("All documents successfully imported.");
     }
 
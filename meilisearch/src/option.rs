@@ -12,6 +12,7 @@ use std::{env, fmt, fs};
 
 use byte_unit::{Byte, ByteError};
 use clap::Parser;
+use meilisearch_auth::DEFAULT_AUTH_STORE_SIZE;
 use meilisearch_types::features::InstanceTogglableFeatures;
 use meilisearch_types::milli::update::IndexerConfig;
 use rustls::server::{
@@ -27,10 +28,18 @@ const POSSIBLE_ENV: [&str; 2] = ["development", "production"];
 const MEILI_DB_PATH: &str = "MEILI_DB_PATH";
 const MEILI_HTTP_ADDR: &str = "MEILI_HTTP_ADDR";
 const MEILI_MASTER_KEY: &str = "MEILI_MASTER_KEY";
+const MEILI_ENCRYPTION_KEY: &str = "MEILI_ENCRYPTION_KEY";
 const MEILI_ENV: &str = "MEILI_ENV";
 #[cfg(feature = "analytics")]
 const MEILI_NO_ANALYTICS: &str = "MEILI_NO_ANALYTICS";
 const MEILI_HTTP_PAYLOAD_SIZE_LIMIT: &str = "MEILI_HTTP_PAYLOAD_SIZE_LIMIT";
+const MEILI_MAX_TASK_HISTORY: &str = "MEILI_MAX_TASK_HISTORY";
+const MEILI_MAX_AUTH_STORE_SIZE: &str = "MEILI_MAX_AUTH_STORE_SIZE";
+const MEILI_DEFAULT_PRIMARY_KEY: &str = "MEILI_DEFAULT_PRIMARY_KEY";
+const MEILI_CASE_INSENSITIVE_INDEX_UIDS: &str = "MEILI_CASE_INSENSITIVE_INDEX_UIDS";
+const MEILI_MAX_FACETS_PER_SEARCH: &str = "MEILI_MAX_FACETS_PER_SEARCH";
+const MEILI_DEFAULT_DOCUMENTS_LIMIT: &str = "MEILI_DEFAULT_DOCUMENTS_LIMIT";
+const MEILI_MAX_DOCUMENTS_LIMIT: &str = "MEILI_MAX_DOCUMENTS_LIMIT";
 const MEILI_SSL_CERT_PATH: &str = "MEILI_SSL_CERT_PATH";
 const MEILI_SSL_KEY_PATH: &str = "MEILI_SSL_KEY_PATH";
 const MEILI_SSL_AUTH_PATH: &str = "MEILI_SSL_AUTH_PATH";
@@ -47,6 +56,7 @@ const MEILI_IMPORT_DUMP: &str = "MEILI_IMPORT_DUMP";
 const MEILI_IGNORE_MISSING_DUMP: &str = "MEILI_IGNORE_MISSING_DUMP";
 const MEILI_IGNORE_DUMP_IF_DB_EXISTS: &str = "MEILI_IGNORE_DUMP_IF_DB_EXISTS";
 const MEILI_DUMP_DIR: &str = "MEILI_DUMP_DIR";
+const MEILI_DUMP_AND_SNAPSHOT_IO_TIMEOUT: &str = "MEILI_DUMP_AND_SNAPSHOT_IO_TIMEOUT";
 const MEILI_LOG_LEVEL: &str = "MEILI_LOG_LEVEL";
 const MEILI_EXPERIMENTAL_ENABLE_METRICS: &str = "MEILI_EXPERIMENTAL_ENABLE_METRICS";
 const MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE: &str =
@@ -57,6 +67,9 @@ const DEFAULT_DB_PATH: &str = "./data.ms";
 const DEFAULT_HTTP_ADDR: &str = "localhost:7700";
 const DEFAULT_ENV: &str = "development";
 const DEFAULT_HTTP_PAYLOAD_SIZE_LIMIT: &str = "100 MB";
+const DEFAULT_MAX_TASK_HISTORY: usize = 1_000_000;
+const MIN_MAX_TASK_HISTORY: usize = 10;
+const DEFAULT_DOCUMENTS_LIMIT: usize = 20;
 const DEFAULT_SNAPSHOT_DIR: &str = "snapshots/";
 const DEFAULT_SNAPSHOT_INTERVAL_SEC: u64 = 86400;
 const DEFAULT_SNAPSHOT_INTERVAL_SEC_STR: &str = "86400";
@@ -149,6 +162,12 @@ pub struct Opt {
     #[clap(long, env = MEILI_MASTER_KEY)]
     pub master_key: Option<String>,
 
+    /// Sets the secret used to encrypt the values of the `encryptedAttributes` of the indexes.
+    /// It can be any string, the encryption key is derived from it with PBKDF2-HMAC-SHA256.
+    /// Changing it makes the already encrypted values unreadable.
+    #[clap(long, env = MEILI_ENCRYPTION_KEY)]
+    pub encryption_key: Option<String>,
+
     /// Configures the instance's environment. Value must be either `production` or `development`.
     #[clap(long, env = MEILI_ENV, default_value_t = default_env(), value_parser = POSSIBLE_ENV)]
     #[serde(default = "default_env")]
@@ -175,6 +194,45 @@ pub struct Opt {
     #[serde(skip, default = "default_max_task_db_size")]
     pub max_task_db_size: Byte,
 
+    /// Sets the maximum size of the API keys database. Value must be given in bytes or explicitly
+    /// stating a base unit (for instance: 107374182400, '107.7Gb', or '107374 Mb').
+    #[clap(long, env = MEILI_MAX_AUTH_STORE_SIZE, default_value_t = default_max_auth_store_size())]
+    #[serde(default = "default_max_auth_store_size")]
+    pub max_auth_store_size: Byte,
+
+    /// Sets the maximum number of tasks kept in the task queue. Once this number is reached,
+    /// the oldest finished tasks are deleted. Enqueued and processing tasks are never deleted.
+    /// It must be at least 10.
+    #[clap(long, env = MEILI_MAX_TASK_HISTORY, default_value_t = default_max_task_history(), value_parser = parse_max_task_history)]
+    #[serde(default = "default_max_task_history")]
+    pub max_task_history: usize,
+
+    /// Sets the primary key given to the indexes created without specifying one.
+    #[clap(long, env = MEILI_DEFAULT_PRIMARY_KEY)]
+    pub default_primary_key: Option<String>,
+
+    /// Makes the index uids case-insensitive: `Products` and `products` designate the same index,
+    /// which is looked up by its lowercased uid but keeps the casing it was created with.
+    #[clap(long, env = MEILI_CASE_INSENSITIVE_INDEX_UIDS)]
+    #[serde(default)]
+    pub case_insensitive_index_uids: bool,
+
+    /// Sets the maximum number of attributes a single search can request a facet distribution on.
+    /// The searches requesting more facets are rejected. There is no limit by default.
+    #[clap(long, env = MEILI_MAX_FACETS_PER_SEARCH)]
+    pub max_facets_per_search: Option<usize>,
+
+    /// Sets the number of documents returned when browsing the documents of an index
+    /// without specifying a `limit`.
+    #[clap(long, env = MEILI_DEFAULT_DOCUMENTS_LIMIT, default_value_t = default_documents_limit())]
+    #[serde(default = "default_documents_limit")]
+    pub default_documents_limit: usize,
+
+    /// Sets the maximum number of documents returned when browsing the documents of an index.
+    /// The requests asking for more documents are clamped to this limit. There is no limit by default.
+    #[clap(long, env = MEILI_MAX_DOCUMENTS_LIMIT)]
+    pub max_documents_limit: Option<usize>,
+
     /// Sets the maximum size of accepted payloads. Value must be given in bytes or explicitly stating a
     /// base unit (for instance: 107374182400, '107.7Gb', or '107374 Mb').
     #[clap(long, env = MEILI_HTTP_PAYLOAD_SIZE_LIMIT, default_value_t = default_http_payload_size_limit())]
@@ -282,6 +340,12 @@ pub struct Opt {
     #[serde(default = "default_dump_dir")]
     pub dump_dir: PathBuf,
 
+    /// Sets the number of seconds after which writing a dump or snapshot file in its directory is
+    /// considered hanging, which can happen on network filesystems. The write is retried a few
+    /// times before failing the task. There is no timeout by default.
+    #[clap(long, env = MEILI_DUMP_AND_SNAPSHOT_IO_TIMEOUT, value_name = "IO_TIMEOUT_SEC")]
+    pub dump_and_snapshot_io_timeout: Option<u64>,
+
     /// Defines how much detail should be present in Meilisearch's logs.
     ///
     /// Meilisearch currently supports six log levels, listed in order of increasing verbosity: OFF, ERROR, WARN, INFO, DEBUG, TRACE.
@@ -367,9 +431,17 @@ impl Opt {
             db_path,
             http_addr,
             master_key,
+            encryption_key,
             env,
             max_index_size: _,
             max_task_db_size: _,
+            max_auth_store_size,
+            max_task_history,
+            default_primary_key,
+            case_insensitive_index_uids,
+            max_facets_per_search,
+            default_documents_limit,
+            max_documents_limit,
             http_payload_size_limit,
             ssl_cert_path,
             ssl_key_path,
@@ -381,6 +453,7 @@ impl Opt {
             snapshot_dir,
             schedule_snapshot,
             dump_dir,
+            dump_and_snapshot_io_timeout,
             log_level,
             indexer_options,
             import_snapshot: _,
@@ -400,6 +473,9 @@ impl Opt {
         if let Some(master_key) = master_key {
             export_to_env_if_not_present(MEILI_MASTER_KEY, master_key);
         }
+        if let Some(encryption_key) = encryption_key {
+            export_to_env_if_not_present(MEILI_ENCRYPTION_KEY, encryption_key);
+        }
         export_to_env_if_not_present(MEILI_ENV, env);
         #[cfg(feature = "analytics")]
         {
@@ -409,6 +485,31 @@ impl Opt {
             MEILI_HTTP_PAYLOAD_SIZE_LIMIT,
             http_payload_size_limit.to_string(),
         );
+        export_to_env_if_not_present(MEILI_MAX_AUTH_STORE_SIZE, max_auth_store_size.to_string());
+        export_to_env_if_not_present(MEILI_MAX_TASK_HISTORY, max_task_history.to_string());
+        if let Some(default_primary_key) = default_primary_key {
+            export_to_env_if_not_present(MEILI_DEFAULT_PRIMARY_KEY, default_primary_key);
+        }
+        export_to_env_if_not_present(
+            MEILI_CASE_INSENSITIVE_INDEX_UIDS,
+            case_insensitive_index_uids.to_string(),
+        );
+        if let Some(max_facets_per_search) = max_facets_per_search {
+            export_to_env_if_not_present(
+                MEILI_MAX_FACETS_PER_SEARCH,
+                max_facets_per_search.to_string(),
+            );
+        }
+        export_to_env_if_not_present(
+            MEILI_DEFAULT_DOCUMENTS_LIMIT,
+            default_documents_limit.to_string(),
+        );
+        if let Some(max_documents_limit) = max_documents_limit {
+            export_to_env_if_not_present(
+                MEILI_MAX_DOCUMENTS_LIMIT,
+                max_documents_limit.to_string(),
+            );
+        }
         if let Some(ssl_cert_path) = ssl_cert_path {
             export_to_env_if_not_present(MEILI_SSL_CERT_PATH, ssl_cert_path);
         }
@@ -430,6 +531,12 @@ impl Opt {
         }
 
         export_to_env_if_not_present(MEILI_DUMP_DIR, dump_dir);
+        if let Some(io_timeout) = dump_and_snapshot_io_timeout {
+            export_to_env_if_not_present(
+                MEILI_DUMP_AND_SNAPSHOT_IO_TIMEOUT,
+                io_timeout.to_string(),
+            );
+        }
         export_to_env_if_not_present(MEILI_LOG_LEVEL, log_level.to_string());
         export_to_env_if_not_present(
             MEILI_EXPERIMENTAL_ENABLE_METRICS,
@@ -547,7 +654,7 @@ impl TryFrom<&IndexerOpts> for IndexerConfig {
         Ok(Self {
             log_every_n: Some(DEFAULT_LOG_EVERY_N),
             max_memory: other.max_indexing_memory.map(|b| b.get_bytes() as usize),
-            thread_pool: Some(thread_pool),
+            thread_pool: Some(Arc::new(thread_pool)),
             max_positions_per_attributes: None,
             skip_index_budget: other.skip_index_budget,
             ..Default::default()
@@ -723,6 +830,18 @@ fn default_max_task_db_size() -> Byte {
     Byte::from_bytes(TASK_DB_SIZE)
 }
 
+fn default_max_auth_store_size() -> Byte {
+    Byte::from_bytes(DEFAULT_AUTH_STORE_SIZE as u64)
+}
+
+fn default_max_task_history() -> usize {
+    DEFAULT_MAX_TASK_HISTORY
+}
+
+fn default_documents_limit() -> usize {
+    DEFAULT_DOCUMENTS_LIMIT
+}
+
 fn default_http_payload_size_limit() -> Byte {
     Byte::from_str(DEFAULT_HTTP_PAYLOAD_SIZE_LIMIT).unwrap()
 }
@@ -769,6 +888,14 @@ impl FromStr for ScheduleSnapshot {
     }
 }
 
+fn parse_max_task_history(s: &str) -> Result<usize, String> {
+    let max_task_history: usize = s.parse().map_err(|e: ParseIntError| e.to_string())?;
+    if max_task_history < MIN_MAX_TASK_HISTORY {
+        return Err(format!("the task history must keep at least {MIN_MAX_TASK_HISTORY} tasks"));
+    }
+    Ok(max_task_history)
+}
+
 fn parse_schedule_snapshot(s: &str) -> Result<ScheduleSnapshot, ParseIntError> {
     Ok(if s.is_empty() { ScheduleSnapshot::Disabled } else { ScheduleSnapshot::from_str(s)? })
 }
@@ -27,6 +27,10 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::post().to(SeqHandler(create_api_key)))
             .route(web::get().to(SeqHandler(list_api_keys))),
     )
+    .service(web::resource("/delete-batch").route(web::post().to(SeqHandler(delete_api_keys))))
+    .service(
+        web::resource("/purge-expired").route(web::post().to(SeqHandler(purge_expired_api_keys))),
+    )
     .service(
         web::resource("/{key}")
             .route(web::get().to(SeqHandler(get_api_key)))
@@ -51,13 +55,19 @@ pub async fn create_api_key(
     Ok(HttpResponse::Created().json(res))
 }
 
-#[derive(Deserr, Debug, Clone, Copy)]
+#[derive(Deserr, Debug, Clone)]
 #[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
 pub struct ListApiKeys {
     #[deserr(default, error = DeserrQueryParamError<InvalidApiKeyOffset>)]
     pub offset: Param<usize>,
     #[deserr(default = Param(PAGINATION_DEFAULT_LIMIT), error = DeserrQueryParamError<InvalidApiKeyLimit>)]
     pub limit: Param<usize>,
+    /// Only list the keys whose description contains this text, ignoring the case.
+    #[deserr(default, error = DeserrQueryParamError<InvalidApiKeySearch>)]
+    pub search: Option<String>,
+    /// Only list the expired keys when `true`, or the keys that didn't expire yet when `false`.
+    #[deserr(default, error = DeserrQueryParamError<InvalidApiKeyExpiredFilter>)]
+    pub expired: Option<Param<bool>>,
 }
 
 impl ListApiKeys {
@@ -70,11 +80,26 @@ pub async fn list_api_keys(
     auth_controller: GuardedData<ActionPolicy<{ actions::KEYS_GET }>, Data<AuthController>>,
     list_api_keys: AwebQueryParameter<ListApiKeys, DeserrQueryParamError>,
 ) -> Result<HttpResponse, ResponseError> {
-    let paginate = list_api_keys.into_inner().as_pagination();
+    let list_api_keys = list_api_keys.into_inner();
+    let search = list_api_keys.search.clone();
+    let expired = list_api_keys.expired.map(|expired| expired.0);
+    let paginate = list_api_keys.as_pagination();
     let page_view = tokio::task::spawn_blocking(move || -> Result<_, AuthControllerError> {
-        let keys = auth_controller.list_keys()?;
-        let page_view = paginate
-            .auto_paginate_sized(keys.into_iter().map(|k| KeyView::from_key(k, &auth_controller)));
+        // a key restricted to some indexes only sees the keys restricted to these indexes
+        let filters = auth_controller.filters();
+        let keys = match search {
+            Some(query) => auth_controller.search_keys(&query)?,
+            None => auth_controller.list_keys()?,
+        };
+        // a key without expiration date never expires
+        let now = OffsetDateTime::now_utc();
+        let is_expired = |k: &Key| k.expires_at.map_or(false, |expires_at| expires_at <= now);
+        let page_view = paginate.auto_paginate_sized(
+            keys.into_iter()
+                .filter(|k| filters.is_key_visible(k))
+                .filter(|k| expired.map_or(true, |expired| is_expired(k) == expired))
+                .map(|k| KeyView::from_key(k, &auth_controller)),
+        );
 
         Ok(page_view)
     })
@@ -94,6 +119,10 @@ pub async fn get_api_key(
         let uid =
             Uuid::parse_str(&key).or_else(|_| auth_controller.get_uid_from_encoded_key(&key))?;
         let key = auth_controller.get_key(uid)?;
+        // a key restricted to some indexes can't see the keys giving access to other indexes
+        if !auth_controller.filters().is_key_visible(&key) {
+            return Err(AuthControllerError::ApiKeyNotFound(uid.to_string()));
+        }
 
         Ok(KeyView::from_key(key, &auth_controller))
     })
@@ -139,6 +168,43 @@ pub async fn delete_api_key(
     Ok(HttpResponse::NoContent().finish())
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyDeletionView {
+    key: String,
+    deleted: bool,
+}
+
+pub async fn delete_api_keys(
+    auth_controller: GuardedData<ActionPolicy<{ actions::KEYS_DELETE }>, Data<AuthController>>,
+    body: AwebJson<Vec<String>, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    let keys = body.into_inner();
+    let res = tokio::task::spawn_blocking(move || auth_controller.delete_keys(keys))
+        .await
+        .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))??;
+
+    let res: Vec<_> =
+        res.into_iter().map(|(key, deleted)| KeyDeletionView { key, deleted }).collect();
+    Ok(HttpResponse::Ok().json(res))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PurgedKeysView {
+    deleted_keys: usize,
+}
+
+pub async fn purge_expired_api_keys(
+    auth_controller: GuardedData<ActionPolicy<{ actions::KEYS_DELETE }>, Data<AuthController>>,
+) -> Result<HttpResponse, ResponseError> {
+    let deleted_keys = tokio::task::spawn_blocking(move || auth_controller.delete_expired_keys())
+        .await
+        .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))??;
+
+    Ok(HttpResponse::Ok().json(PurgedKeysView { deleted_keys }))
+}
+
 #[derive(Deserialize)]
 pub struct AuthParam {
     key: String,
@@ -159,6 +225,11 @@ struct KeyView {
     created_at: OffsetDateTime,
     #[serde(serialize_with = "time::serde::rfc3339::serialize")]
     updated_at: OffsetDateTime,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "time::serde::rfc3339::option::serialize"
+    )]
+    last_used_at: Option<OffsetDateTime>,
 }
 
 impl KeyView {
@@ -175,6 +246,7 @@ impl KeyView {
             expires_at: key.expires_at,
             created_at: key.created_at,
             updated_at: key.updated_at,
+            last_used_at: key.last_used_at,
         }
     }
 }
@@ -1,4 +1,7 @@
+use std::collections::{BTreeMap, HashMap};
 use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
 
 use actix_web::http::header::CONTENT_TYPE;
 use actix_web::web::Data;
@@ -11,20 +14,25 @@ use index_scheduler::IndexScheduler;
 use log::debug;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
-use meilisearch_types::document_formats::{read_csv, read_json, read_ndjson, PayloadType};
+use meilisearch_types::document_formats::{
+    read_csv, read_json, read_ndjson, read_tsv, PayloadType,
+};
+use meilisearch_types::encryption::{decrypt_document, FieldCipher};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::milli::update::IndexDocumentsMethod;
-use meilisearch_types::milli::DocumentId;
+use meilisearch_types::milli::update::{DuplicateDocuments, IndexDocumentsMethod};
+use meilisearch_types::milli::{AscDesc, DocumentId, Member, SortError};
+use meilisearch_types::serde_cs::vec::CS;
 use meilisearch_types::star_or::OptionStarOrList;
-use meilisearch_types::tasks::KindWithContent;
+use meilisearch_types::tasks::{Details, IndexingLimits, KindWithContent};
 use meilisearch_types::{milli, Document, Index};
 use mime::Mime;
 use once_cell::sync::Lazy;
 use serde::Deserialize;
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use tempfile::tempfile;
 use tokio::fs::File;
 use tokio::io::{AsyncSeekExt, AsyncWriteExt, BufWriter};
@@ -36,11 +44,20 @@ use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::payload::Payload;
 use crate::extractors::sequential_extractor::SeqHandler;
-use crate::routes::{PaginationView, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT};
+use crate::routes::{PaginationView, SummarizedTaskView};
 use crate::search::parse_filter;
 
+/// Header set on the documents browse responses whose `limit` was clamped, holding the maximum
+/// number of documents a browse can return.
+const LIMIT_CLAMPED_HEADER: &str = "X-Meilisearch-Limit-Clamped";
+
 static ACCEPTED_CONTENT_TYPE: Lazy<Vec<String>> = Lazy::new(|| {
-    vec!["application/json".to_string(), "application/x-ndjson".to_string(), "text/csv".to_string()]
+    vec![
+        "application/json".to_string(),
+        "application/x-ndjson".to_string(),
+        "text/csv".to_string(),
+        "text/tab-separated-values".to_string(),
+    ]
 });
 
 /// Extracts the mime type from the content type and return
@@ -91,6 +108,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
 pub struct GetDocument {
     #[deserr(default, error = DeserrQueryParamError<InvalidDocumentFields>)]
     fields: OptionStarOrList<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentShowChecksum>)]
+    show_checksum: Param<bool>,
 }
 
 pub async fn get_document(
@@ -105,11 +124,13 @@ pub async fn get_document(
 
     analytics.get_fetch_documents(&DocumentFetchKind::PerDocumentId, &req);
 
-    let GetDocument { fields } = params.into_inner();
+    let GetDocument { fields, show_checksum } = params.into_inner();
     let attributes_to_retrieve = fields.merge_star_and_none();
 
     let index = index_scheduler.index(&index_uid)?;
-    let document = retrieve_document(&index, &document_id, attributes_to_retrieve)?;
+    let cipher = index_scheduler.field_cipher();
+    let document =
+        retrieve_document(&index, &document_id, attributes_to_retrieve, show_checksum.0, cipher)?;
     debug!("returns: {:?}", document);
     Ok(HttpResponse::Ok().json(document))
 }
@@ -140,12 +161,16 @@ pub async fn delete_document(
 pub struct BrowseQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidDocumentOffset>)]
     offset: Param<usize>,
-    #[deserr(default = Param(PAGINATION_DEFAULT_LIMIT), error = DeserrQueryParamError<InvalidDocumentLimit>)]
-    limit: Param<usize>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentLimit>)]
+    limit: Option<Param<usize>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidDocumentFields>)]
     fields: OptionStarOrList<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidDocumentFilter>)]
     filter: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentSort>)]
+    sort: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentShowChecksum>)]
+    show_checksum: Param<bool>,
 }
 
 #[derive(Debug, Deserr)]
@@ -153,12 +178,16 @@ pub struct BrowseQueryGet {
 pub struct BrowseQuery {
     #[deserr(default, error = DeserrJsonError<InvalidDocumentOffset>)]
     offset: usize,
-    #[deserr(default = PAGINATION_DEFAULT_LIMIT, error = DeserrJsonError<InvalidDocumentLimit>)]
-    limit: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentLimit>)]
+    limit: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidDocumentFields>)]
     fields: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
     filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentSort>)]
+    sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentShowChecksum>)]
+    show_checksum: bool,
 }
 
 pub async fn documents_by_query_post(
@@ -175,7 +204,7 @@ pub async fn documents_by_query_post(
     analytics.post_fetch_documents(
         &DocumentFetchKind::Normal {
             with_filter: body.filter.is_some(),
-            limit: body.limit,
+            limit: body.limit.unwrap_or_else(|| index_scheduler.default_documents_limit()),
             offset: body.offset,
         },
         &req,
@@ -193,7 +222,7 @@ pub async fn get_documents(
 ) -> Result<HttpResponse, ResponseError> {
     debug!("called with params: {:?}", params);
 
-    let BrowseQueryGet { limit, offset, fields, filter } = params.into_inner();
+    let BrowseQueryGet { limit, offset, fields, filter, sort, show_checksum } = params.into_inner();
 
     let filter = match filter {
         Some(f) => match serde_json::from_str(&f) {
@@ -205,15 +234,17 @@ pub async fn get_documents(
 
     let query = BrowseQuery {
         offset: offset.0,
-        limit: limit.0,
+        limit: limit.map(|limit| limit.0),
         fields: fields.merge_star_and_none(),
         filter,
+        sort: sort.map(CS::into_inner),
+        show_checksum: show_checksum.0,
     };
 
     analytics.get_fetch_documents(
         &DocumentFetchKind::Normal {
             with_filter: query.filter.is_some(),
-            limit: query.limit,
+            limit: query.limit.unwrap_or_else(|| index_scheduler.default_documents_limit()),
             offset: query.offset,
         },
         &req,
@@ -228,15 +259,25 @@ fn documents_by_query(
     query: BrowseQuery,
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
-    let BrowseQuery { offset, limit, fields, filter } = query;
+    let BrowseQuery { offset, limit, fields, filter, sort, show_checksum } = query;
+
+    let limit = limit.unwrap_or_else(|| index_scheduler.default_documents_limit());
+    let clamped_to = index_scheduler.max_documents_limit().filter(|max| limit > *max);
+    let limit = clamped_to.unwrap_or(limit);
 
     let index = index_scheduler.index(&index_uid)?;
-    let (total, documents) = retrieve_documents(&index, offset, limit, filter, fields)?;
+    let cipher = index_scheduler.field_cipher();
+    let (total, documents) =
+        retrieve_documents(&index, offset, limit, filter, sort, fields, show_checksum, cipher)?;
 
     let ret = PaginationView::new(offset, limit, total as usize, documents);
 
     debug!("returns: {:?}", ret);
-    Ok(HttpResponse::Ok().json(ret))
+    let mut response = HttpResponse::Ok();
+    if let Some(max) = clamped_to {
+        response.insert_header((LIMIT_CLAMPED_HEADER, max.to_string()));
+    }
+    Ok(response.json(ret))
 }
 
 #[derive(Deserialize, Debug, Deserr)]
@@ -246,6 +287,83 @@ pub struct UpdateDocumentsQuery {
     pub primary_key: Option<String>,
     #[deserr(default, try_from(char) = from_char_csv_delimiter -> DeserrQueryParamError<InvalidDocumentCsvDelimiter>, error = DeserrQueryParamError<InvalidDocumentCsvDelimiter>)]
     pub csv_delimiter: Option<u8>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentCsvHeader>)]
+    pub csv_header: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentRenameFields>)]
+    pub rename_fields: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentAutogenerateIds>)]
+    pub autogenerate_ids: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentMaxIndexingMemory>)]
+    pub max_indexing_memory: Option<Param<NonZeroUsize>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentMaxIndexingThreads>)]
+    pub max_indexing_threads: Option<Param<NonZeroUsize>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentDuplicates>)]
+    pub duplicates: Duplicates,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentVersionField>)]
+    pub version_field: Option<String>,
+}
+
+/// What to do with the documents of a batch sharing the same id.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum Duplicates {
+    /// The last document replaces or is merged into the previous ones.
+    #[default]
+    Collapse,
+    /// Only the first document is kept.
+    KeepFirst,
+    /// The whole batch fails.
+    Reject,
+}
+
+impl From<Duplicates> for DuplicateDocuments {
+    fn from(duplicates: Duplicates) -> Self {
+        match duplicates {
+            Duplicates::Collapse => DuplicateDocuments::Collapse,
+            Duplicates::KeepFirst => DuplicateDocuments::KeepFirst,
+            Duplicates::Reject => DuplicateDocuments::Reject,
+        }
+    }
+}
+
+/// Returns the indexing limits requested by the `maxIndexingMemory` (in bytes)
+/// and `maxIndexingThreads` parameters, if any.
+fn parse_indexing_limits(
+    max_indexing_memory: Option<Param<NonZeroUsize>>,
+    max_indexing_threads: Option<Param<NonZeroUsize>>,
+) -> Option<IndexingLimits> {
+    if max_indexing_memory.is_none() && max_indexing_threads.is_none() {
+        return None;
+    }
+    Some(IndexingLimits {
+        max_memory: max_indexing_memory.map(|Param(memory)| memory.get()),
+        max_threads: max_indexing_threads.map(|Param(threads)| threads.get()),
+    })
+}
+
+/// Parses the `source:target` pairs of the `renameFields` parameter.
+fn parse_field_renames(
+    rename_fields: Option<CS<String>>,
+) -> Result<BTreeMap<String, String>, MeilisearchHttpError> {
+    let mut renames = BTreeMap::new();
+    let mut targets = HashMap::new();
+    for rename in rename_fields.into_iter().flatten() {
+        let (source, target) = match rename.rsplit_once(':') {
+            Some((source, target)) if !source.is_empty() && !target.is_empty() => {
+                (source.to_string(), target.to_string())
+            }
+            _ => return Err(MeilisearchHttpError::MalformedFieldRename(rename)),
+        };
+        match targets.insert(target.clone(), source.clone()) {
+            Some(other) if other != source => {
+                return Err(MeilisearchHttpError::ConflictingFieldRenames(other, source, target))
+            }
+            _ => (),
+        }
+        renames.insert(source, target);
+    }
+    Ok(renames)
 }
 
 fn from_char_csv_delimiter(
@@ -277,15 +395,28 @@ pub async fn replace_documents(
     analytics.add_documents(&params, index_scheduler.index(&index_uid).is_err(), &req);
 
     let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+    let generate_ids_for = if params.autogenerate_ids.0 {
+        Some(primary_key_to_generate(&index_scheduler, &index_uid, &params.primary_key)?)
+    } else {
+        None
+    };
+    let indexing_limits =
+        parse_indexing_limits(params.max_indexing_memory, params.max_indexing_threads);
     let task = document_addition(
         extract_mime_type(&req)?,
         index_scheduler,
         index_uid,
         params.primary_key,
         params.csv_delimiter,
+        params.csv_header,
+        parse_field_renames(params.rename_fields)?,
+        generate_ids_for,
         body,
         IndexDocumentsMethod::ReplaceDocuments,
         allow_index_creation,
+        indexing_limits,
+        params.duplicates.into(),
+        params.version_field,
     )
     .await?;
 
@@ -308,21 +439,58 @@ pub async fn update_documents(
     analytics.update_documents(&params, index_scheduler.index(&index_uid).is_err(), &req);
 
     let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+    let generate_ids_for = if params.autogenerate_ids.0 {
+        Some(primary_key_to_generate(&index_scheduler, &index_uid, &params.primary_key)?)
+    } else {
+        None
+    };
+    let indexing_limits =
+        parse_indexing_limits(params.max_indexing_memory, params.max_indexing_threads);
     let task = document_addition(
         extract_mime_type(&req)?,
         index_scheduler,
         index_uid,
         params.primary_key,
         params.csv_delimiter,
+        params.csv_header,
+        parse_field_renames(params.rename_fields)?,
+        generate_ids_for,
         body,
         IndexDocumentsMethod::UpdateDocuments,
         allow_index_creation,
+        indexing_limits,
+        params.duplicates.into(),
+        params.version_field,
     )
     .await?;
 
     Ok(HttpResponse::Accepted().json(task))
 }
 
+/// Returns the primary key for which the missing ids must be generated: the one given in the
+/// parameters, or the one of the index, or the configured default primary key if the index
+/// doesn't have one yet, and `id` when none is configured.
+fn primary_key_to_generate(
+    index_scheduler: &IndexScheduler,
+    index_uid: &IndexUid,
+    primary_key: &Option<String>,
+) -> Result<String, ResponseError> {
+    if let Some(primary_key) = primary_key {
+        return Ok(primary_key.clone());
+    }
+    let index_primary_key = match index_scheduler.index(index_uid) {
+        Ok(index) => {
+            let rtxn = index.read_txn()?;
+            index.primary_key(&rtxn)?.map(String::from)
+        }
+        Err(index_scheduler::Error::IndexNotFound(_)) => None,
+        Err(e) => return Err(e.into()),
+    };
+    Ok(index_primary_key
+        .or_else(|| index_scheduler.default_primary_key().map(String::from))
+        .unwrap_or_else(|| String::from("id")))
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn document_addition(
     mime_type: Option<Mime>,
@@ -330,40 +498,46 @@ async fn document_addition(
     index_uid: IndexUid,
     primary_key: Option<String>,
     csv_delimiter: Option<u8>,
+    csv_header: Option<String>,
+    field_renames: BTreeMap<String, String>,
+    generate_ids_for: Option<String>,
     mut body: Payload,
     method: IndexDocumentsMethod,
     allow_index_creation: bool,
+    indexing_limits: Option<IndexingLimits>,
+    duplicate_documents: DuplicateDocuments,
+    version_field: Option<String>,
 ) -> Result<SummarizedTaskView, MeilisearchHttpError> {
-    let format = match (
-        mime_type.as_ref().map(|m| (m.type_().as_str(), m.subtype().as_str())),
-        csv_delimiter,
-    ) {
-        (Some(("application", "json")), None) => PayloadType::Json,
-        (Some(("application", "x-ndjson")), None) => PayloadType::Ndjson,
-        (Some(("text", "csv")), None) => PayloadType::Csv { delimiter: b',' },
-        (Some(("text", "csv")), Some(delimiter)) => PayloadType::Csv { delimiter },
-
-        (Some(("application", "json")), Some(_)) => {
-            return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
-                "application/json",
-            )))
-        }
-        (Some(("application", "x-ndjson")), Some(_)) => {
-            return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
-                "application/x-ndjson",
-            )))
-        }
-        (Some((type_, subtype)), _) => {
-            return Err(MeilisearchHttpError::InvalidContentType(
-                format!("{}/{}", type_, subtype),
-                ACCEPTED_CONTENT_TYPE.clone(),
-            ))
-        }
-        (None, _) => {
+    let format = match &mime_type {
+        Some(mime) => match (PayloadType::from_content_type(mime.essence_str()), csv_delimiter) {
+            (Some(PayloadType::Csv { .. }), Some(delimiter)) => PayloadType::Csv { delimiter },
+            (Some(format), None) => format,
+            (Some(_), Some(_)) => {
+                return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(
+                    mime.essence_str().to_string(),
+                ))
+            }
+            (None, _) => {
+                return Err(MeilisearchHttpError::InvalidContentType(
+                    mime.essence_str().to_string(),
+                    ACCEPTED_CONTENT_TYPE.clone(),
+                ))
+            }
+        },
+        None => {
             return Err(MeilisearchHttpError::MissingContentType(ACCEPTED_CONTENT_TYPE.clone()))
         }
     };
 
+    if csv_header.is_some() && !matches!(format, PayloadType::Csv { .. }) {
+        let mime = mime_type.as_ref().map_or("", |mime| mime.essence_str());
+        return Err(MeilisearchHttpError::CsvHeaderWithWrongContentType(mime.to_string()));
+    }
+
+    if !allow_index_creation {
+        index_scheduler.ensure_index_will_exist(&index_uid)?;
+    }
+
     let (uuid, mut update_file) = index_scheduler.create_update_file()?;
 
     let temp_file = match tempfile() {
@@ -376,6 +550,8 @@ async fn document_addition(
 
     let mut buffer_write_size: usize = 0;
     while let Some(result) = body.next().await {
+        // If the stream aborts midway, the update file is dropped before being
+        // persisted and thus removed from the disk.
         let byte = result?;
 
         if byte.is_empty() && buffer_write_size == 0 {
@@ -401,13 +577,20 @@ async fn document_addition(
     }
 
     let read_file = buffer.into_inner().into_std().await;
+    let generate_ids = generate_ids_for.is_some();
+    // The task must use the primary key for which the ids are generated.
+    let primary_key = primary_key.or(generate_ids_for.clone());
     let documents_count = tokio::task::spawn_blocking(move || {
+        let file = update_file.as_file_mut();
+        let generate_ids_for = generate_ids_for.as_deref();
         let documents_count = match format {
-            PayloadType::Json => read_json(&read_file, update_file.as_file_mut())?,
+            PayloadType::Json => read_json(&read_file, file, &field_renames, generate_ids_for)?,
             PayloadType::Csv { delimiter } => {
-                read_csv(&read_file, update_file.as_file_mut(), delimiter)?
+                let header = csv_header.as_deref();
+                read_csv(&read_file, file, delimiter, header, &field_renames, generate_ids_for)?
             }
-            PayloadType::Ndjson => read_ndjson(&read_file, update_file.as_file_mut())?,
+            PayloadType::Ndjson => read_ndjson(&read_file, file, &field_renames, generate_ids_for)?,
+            PayloadType::Tsv => read_tsv(&read_file, file, &field_renames, generate_ids_for)?,
         };
         // we NEED to persist the file here because we moved the `udpate_file` in another task.
         update_file.persist()?;
@@ -438,21 +621,35 @@ async fn document_addition(
     let task = KindWithContent::DocumentAdditionOrUpdate {
         method,
         content_file: uuid,
-        documents_count,
+        documents_count: documents_count.documents,
+        indexing_limits,
+        duplicate_documents,
+        version_field,
         primary_key,
         allow_index_creation,
         index_uid: index_uid.to_string(),
     };
 
-    let scheduler = index_scheduler.clone();
-    let task = match tokio::task::spawn_blocking(move || scheduler.register(task)).await? {
-        Ok(task) => task,
-        Err(e) => {
-            index_scheduler.delete_update_file(uuid)?;
-            return Err(e.into());
-        }
+    let details = Details::DocumentAdditionOrUpdate {
+        received_documents: documents_count.documents,
+        indexed_documents: None,
+        duplicated_documents: None,
+        rejected_documents: None,
+        generated_ids: generate_ids.then_some(documents_count.generated_ids),
     };
 
+    let scheduler = index_scheduler.clone();
+    let task =
+        match tokio::task::spawn_blocking(move || scheduler.register_with_details(task, details))
+            .await?
+        {
+            Ok(task) => task,
+            Err(e) => {
+                index_scheduler.delete_update_file(uuid)?;
+                return Err(e.into());
+            }
+        };
+
     debug!("returns: {:?}", task);
     Ok(task.into())
 }
@@ -540,23 +737,31 @@ fn some_documents<'a, 't: 'a>(
     index: &'a Index,
     rtxn: &'t RoTxn,
     doc_ids: impl IntoIterator<Item = DocumentId> + 'a,
+    cipher: Option<&'a FieldCipher>,
 ) -> Result<impl Iterator<Item = Result<Document, ResponseError>> + 'a, ResponseError> {
     let fields_ids_map = index.fields_ids_map(rtxn)?;
     let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
+    let encrypted_fields = index.encrypted_fields(rtxn)?;
 
     Ok(index.iter_documents(rtxn, doc_ids)?.map(move |ret| {
         ret.map_err(ResponseError::from).and_then(|(_key, document)| -> Result<_, ResponseError> {
-            Ok(milli::obkv_to_json(&all_fields, &fields_ids_map, document)?)
+            let mut document = milli::obkv_to_json(&all_fields, &fields_ids_map, document)?;
+            decrypt_document(cipher, &encrypted_fields, &mut document)?;
+            Ok(document)
         })
     }))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn retrieve_documents<S: AsRef<str>>(
     index: &Index,
     offset: usize,
     limit: usize,
     filter: Option<Value>,
+    sort: Option<Vec<String>>,
     attributes_to_retrieve: Option<Vec<S>>,
+    show_checksum: bool,
+    cipher: Option<&FieldCipher>,
 ) -> Result<(u64, Vec<Document>), ResponseError> {
     let rtxn = index.read_txn()?;
     let filter = &filter;
@@ -578,33 +783,75 @@ fn retrieve_documents<S: AsRef<str>>(
         index.documents_ids(&rtxn)?
     };
 
-    let (it, number_of_documents) = {
-        let number_of_documents = candidates.len();
-        (
-            some_documents(index, &rtxn, candidates.into_iter().skip(offset).take(limit))?,
-            number_of_documents,
-        )
+    let number_of_documents = candidates.len();
+    let it = match sort {
+        Some(sort) => {
+            let sort = parse_sort(index, &rtxn, &sort)?;
+            let documents_ids =
+                milli::sort_documents_by_facets(index, &rtxn, candidates, &sort, offset, limit)?;
+            itertools::Either::Left(some_documents(index, &rtxn, documents_ids, cipher)?)
+        }
+        None => {
+            let documents_ids = candidates.into_iter().skip(offset).take(limit);
+            itertools::Either::Right(some_documents(index, &rtxn, documents_ids, cipher)?)
+        }
     };
 
     let documents: Result<Vec<_>, ResponseError> = it
         .map(|document| {
-            Ok(match &attributes_to_retrieve {
+            let document = document?;
+            let checksum = show_checksum.then(|| document_checksum(&document));
+            let document = match &attributes_to_retrieve {
                 Some(attributes_to_retrieve) => permissive_json_pointer::select_values(
-                    &document?,
+                    &document,
                     attributes_to_retrieve.iter().map(|s| s.as_ref()),
                 ),
-                None => document?,
-            })
+                None => document,
+            };
+            Ok(with_checksum(document, checksum))
         })
         .collect();
 
     Ok((number_of_documents, documents?))
 }
 
+/// Parse the sort criteria of a documents browsing request and ensure every field is sortable.
+fn parse_sort(index: &Index, rtxn: &RoTxn, sort: &[String]) -> Result<Vec<AscDesc>, ResponseError> {
+    let sortable_fields = index.sortable_fields(rtxn)?;
+
+    sort.iter()
+        .map(|sort| {
+            let asc_desc = AscDesc::from_str(sort).map_err(|err| {
+                let err = milli::Error::from(SortError::from(err));
+                ResponseError::from_msg(err.to_string(), Code::InvalidDocumentSort)
+            })?;
+            match asc_desc.member() {
+                Member::Field(field) if milli::is_faceted(field, &sortable_fields) => Ok(asc_desc),
+                Member::Field(field) => {
+                    let (valid_fields, hidden_fields) =
+                        index.remove_hidden_fields(rtxn, &sortable_fields)?;
+                    let err = milli::Error::from(milli::UserError::InvalidSortableAttribute {
+                        field: field.to_string(),
+                        valid_fields,
+                        hidden_fields,
+                    });
+                    Err(ResponseError::from_msg(err.to_string(), Code::InvalidDocumentSort))
+                }
+                Member::Geo(_) => Err(ResponseError::from_msg(
+                    format!("`{sort}` cannot be used to sort documents, only fields can be used."),
+                    Code::InvalidDocumentSort,
+                )),
+            }
+        })
+        .collect()
+}
+
 fn retrieve_document<S: AsRef<str>>(
     index: &Index,
     doc_id: &str,
     attributes_to_retrieve: Option<Vec<S>>,
+    show_checksum: bool,
+    cipher: Option<&FieldCipher>,
 ) -> Result<Document, ResponseError> {
     let txn = index.read_txn()?;
 
@@ -623,7 +870,10 @@ fn retrieve_document<S: AsRef<str>>(
         .map(|(_, d)| d)
         .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(doc_id.to_string()))?;
 
-    let document = meilisearch_types::milli::obkv_to_json(&all_fields, &fields_ids_map, document)?;
+    let mut document =
+        meilisearch_types::milli::obkv_to_json(&all_fields, &fields_ids_map, document)?;
+    decrypt_document(cipher, &index.encrypted_fields(&txn)?, &mut document)?;
+    let checksum = show_checksum.then(|| document_checksum(&document));
     let document = match &attributes_to_retrieve {
         Some(attributes_to_retrieve) => permissive_json_pointer::select_values(
             &document,
@@ -632,5 +882,31 @@ fn retrieve_document<S: AsRef<str>>(
         None => document,
     };
 
-    Ok(document)
+    Ok(with_checksum(document, checksum))
+}
+
+/// Computes the checksum of the whole stored document, independently of the order of its fields,
+/// so that identical documents share the same checksum.
+fn document_checksum(document: &Document) -> String {
+    fn canonical(value: &Value) -> Value {
+        match value {
+            Value::Object(object) => {
+                let mut fields: Vec<_> = object.iter().collect();
+                fields.sort_unstable_by(|(left, _), (right, _)| left.cmp(right));
+                Value::Object(fields.into_iter().map(|(k, v)| (k.clone(), canonical(v))).collect())
+            }
+            Value::Array(values) => Value::Array(values.iter().map(canonical).collect()),
+            value => value.clone(),
+        }
+    }
+
+    let document = canonical(&Value::Object(document.clone()));
+    format!("{:x}", Sha256::digest(document.to_string().as_bytes()))
+}
+
+fn with_checksum(mut document: Document, checksum: Option<String>) -> Document {
+    if let Some(checksum) = checksum {
+        document.insert("_checksum".to_string(), Value::String(checksum));
+    }
+    document
 }
@@ -0,0 +1,224 @@
+use actix_web::web::{Bytes, Data};
+use actix_web::{web, HttpResponse};
+use deserr::Deserr;
+use futures::StreamExt;
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::document_formats::{read_ndjson, PayloadType};
+use meilisearch_types::encryption::{decrypt_document, FieldCipher};
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::{self, Index};
+use meilisearch_types::settings::{settings, Settings, Unchecked};
+use meilisearch_types::tasks::KindWithContent;
+use serde_json::{json, Value};
+use tempfile::tempfile;
+use tokio::fs::File;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::error::MeilisearchHttpError;
+use crate::error::PayloadError::{MalformedPayload, ReceivePayload};
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::payload::Payload;
+use crate::routes::SummarizedTaskView;
+
+/// The size of the chunks in which an exported index is sent.
+const EXPORT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The first line of an exported index, followed by its documents in NDJSON.
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+struct ExportHeader {
+    #[deserr(default, error = DeserrJsonError<InvalidIndexPrimaryKey>)]
+    primary_key: Option<String>,
+    settings: Settings<Unchecked>,
+}
+
+pub async fn export_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
+    // the settings are exported along with the documents.
+    _settings_access: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let index = index_scheduler.index(&index_uid)?;
+    let scheduler = index_scheduler.clone();
+    let (sender, receiver) = mpsc::channel(1);
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = write_export(&index, scheduler.field_cipher(), &sender) {
+            let _ = sender.blocking_send(Err(e));
+        }
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/x-ndjson")
+        .streaming(ReceiverStream::new(receiver)))
+}
+
+/// Sends the header and the documents of the index in chunks, until the receiver is dropped.
+fn write_export(
+    index: &Index,
+    cipher: Option<&FieldCipher>,
+    sender: &mpsc::Sender<Result<Bytes, ResponseError>>,
+) -> Result<(), ResponseError> {
+    let rtxn = index.read_txn()?;
+
+    let header = json!({
+        "primaryKey": index.primary_key(&rtxn)?,
+        "settings": settings(index, &rtxn)?,
+    });
+    let mut chunk = serde_json::to_vec(&header).map_err(MeilisearchHttpError::from)?;
+
+    let fields_ids_map = index.fields_ids_map(&rtxn)?;
+    let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
+    let encrypted_fields = index.encrypted_fields(&rtxn)?;
+    for ret in index.all_documents(&rtxn)? {
+        let (_id, document) = ret?;
+        let mut document = milli::obkv_to_json(&all_fields, &fields_ids_map, document)?;
+        decrypt_document(cipher, &encrypted_fields, &mut document)?;
+        chunk.push(b'\n');
+        serde_json::to_writer(&mut chunk, &document).map_err(MeilisearchHttpError::from)?;
+
+        if chunk.len() >= EXPORT_CHUNK_SIZE {
+            let bytes = Bytes::from(std::mem::take(&mut chunk));
+            if sender.blocking_send(Ok(bytes)).is_err() {
+                // the client is gone.
+                return Ok(());
+            }
+        }
+    }
+
+    let _ = sender.blocking_send(Ok(Bytes::from(chunk)));
+    Ok(())
+}
+
+pub async fn import_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_CREATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    mut body: Payload,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    if !index_scheduler.filters().allow_index_creation(&index_uid) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    // Only the header is kept in memory, the documents are streamed to a temporary file.
+    let temp_file =
+        tempfile().map_err(|e| MeilisearchHttpError::Payload(ReceivePayload(Box::new(e))))?;
+    let mut buffer = BufWriter::new(File::from_std(temp_file));
+    let mut header = Vec::new();
+    let mut header_read = false;
+    let mut has_documents = false;
+    while let Some(bytes) = body.next().await {
+        let bytes = bytes?;
+        let documents = if header_read {
+            &bytes[..]
+        } else {
+            match bytes.iter().position(|byte| *byte == b'\n') {
+                Some(position) => {
+                    header.extend_from_slice(&bytes[..position]);
+                    header_read = true;
+                    &bytes[position + 1..]
+                }
+                None => {
+                    header.extend_from_slice(&bytes);
+                    continue;
+                }
+            }
+        };
+        has_documents |= !documents.iter().all(u8::is_ascii_whitespace);
+        buffer
+            .write_all(documents)
+            .await
+            .map_err(|e| MeilisearchHttpError::Payload(ReceivePayload(Box::new(e))))?;
+    }
+
+    if header.iter().all(u8::is_ascii_whitespace) {
+        return Err(MeilisearchHttpError::MissingPayload(PayloadType::Ndjson).into());
+    }
+    let header: Value = serde_json::from_slice(&header)
+        .map_err(|e| MeilisearchHttpError::Payload(MalformedPayload(e)))?;
+    let ExportHeader { primary_key, settings } =
+        deserr::deserialize::<_, _, DeserrJsonError>(header)?;
+
+    let mut tasks = vec![
+        KindWithContent::IndexCreation {
+            index_uid: index_uid.to_string(),
+            primary_key: primary_key.clone(),
+        },
+        KindWithContent::SettingsUpdate {
+            index_uid: index_uid.to_string(),
+            new_settings: Box::new(settings),
+            is_deletion: false,
+            allow_index_creation: true,
+        },
+    ];
+
+    let mut content_file = None;
+    if has_documents {
+        buffer
+            .flush()
+            .await
+            .map_err(|e| MeilisearchHttpError::Payload(ReceivePayload(Box::new(e))))?;
+        let read_file = buffer.into_inner().into_std().await;
+        let (uuid, mut update_file) = index_scheduler.create_update_file()?;
+
+        let documents_count = tokio::task::spawn_blocking(move || {
+            let count =
+                read_ndjson(&read_file, update_file.as_file_mut(), &Default::default(), None)?;
+            // we NEED to persist the file here because we moved the `update_file` in another task.
+            update_file.persist()?;
+            Ok::<_, MeilisearchHttpError>(count)
+        })
+        .await?;
+        let documents_count = match documents_count {
+            Ok(documents_count) => documents_count,
+            // in this case the file has not been persisted.
+            Err(e) => return Err(e.into()),
+        };
+
+        content_file = Some(uuid);
+        tasks.push(KindWithContent::DocumentAdditionOrUpdate {
+            method: IndexDocumentsMethod::ReplaceDocuments,
+            content_file: uuid,
+            documents_count: documents_count.documents,
+            indexing_limits: Default::default(),
+            duplicate_documents: Default::default(),
+            version_field: None,
+            primary_key,
+            allow_index_creation: true,
+            index_uid: index_uid.to_string(),
+        });
+    }
+
+    // The tasks are registered together so that the documents are indexed with the imported
+    // settings in the index created by the import, the last one is returned as it is the one
+    // finishing the import.
+    let scheduler = index_scheduler.clone();
+    let index = index_uid.to_string();
+    let tasks =
+        match tokio::task::spawn_blocking(move || scheduler.register_index_creation(&index, tasks))
+            .await?
+        {
+            Ok(tasks) => tasks,
+            Err(e) => {
+                if let Some(uuid) = content_file {
+                    index_scheduler.delete_update_file(uuid)?;
+                }
+                return Err(e.into());
+            }
+        };
+
+    let task: SummarizedTaskView =
+        tasks.into_iter().last().expect("at least two tasks are registered").into();
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
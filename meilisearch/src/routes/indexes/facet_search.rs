@@ -14,8 +14,8 @@ use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::search::{
     add_search_rules, perform_facet_search, MatchingStrategy, SearchQuery, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    DEFAULT_CROP_MARKER, DEFAULT_GROUP_LIMIT, DEFAULT_HIGHLIGHT_POST_TAG,
+    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
 };
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -108,17 +108,30 @@ impl From<FacetSearchQuery> for SearchQuery {
             crop_length: DEFAULT_CROP_LENGTH(),
             attributes_to_highlight: None,
             show_matches_position: false,
+            show_match_count: false,
             show_ranking_score: false,
             show_ranking_score_details: false,
+            show_ranking_stages: false,
+            show_processed_query: false,
             filter,
             sort: None,
             facets: None,
+            disjunctive_facets: false,
+            sort_facet_values_by: None,
+            sort_facet_values_direction: None,
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             crop_marker: DEFAULT_CROP_MARKER(),
             matching_strategy,
             vector,
             attributes_to_search_on,
+            within_ids: None,
+            locales: None,
+            disabled_ranking_rules: None,
+            group_by: None,
+            group_limit: DEFAULT_GROUP_LIMIT(),
+            max_groups: None,
+            rules_filter: None,
         }
     }
 }
@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::Infallible;
 
 use actix_web::web::Data;
@@ -11,6 +12,7 @@ use meilisearch_types::deserr::{immutable_field_error, DeserrJsonError, DeserrQu
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::facet::FacetValueType;
 use meilisearch_types::milli::{self, FieldDistribution, Index};
 use meilisearch_types::tasks::KindWithContent;
 use serde::Serialize;
@@ -24,9 +26,11 @@ use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
 
 pub mod documents;
+pub mod export;
 pub mod facet_search;
 pub mod search;
 pub mod settings;
+pub mod suggest;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -43,9 +47,20 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                     .route(web::delete().to(SeqHandler(delete_index))),
             )
             .service(web::resource("/stats").route(web::get().to(SeqHandler(get_index_stats))))
+            .service(
+                web::resource("/lmdb-stats").route(web::get().to(SeqHandler(get_index_lmdb_stats))),
+            )
+            .service(web::resource("/facets").route(web::get().to(SeqHandler(get_facet_schema))))
+            .service(
+                web::resource("/export").route(web::get().to(SeqHandler(export::export_index))),
+            )
+            .service(
+                web::resource("/import").route(web::post().to(SeqHandler(export::import_index))),
+            )
             .service(web::scope("/documents").configure(documents::configure))
             .service(web::scope("/search").configure(search::configure))
             .service(web::scope("/facet-search").configure(facet_search::configure))
+            .service(web::scope("/suggest").configure(suggest::configure))
             .service(web::scope("/settings").configure(settings::configure)),
     );
 }
@@ -175,7 +190,7 @@ pub async fn get_index(
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
 
     let index = index_scheduler.index(&index_uid)?;
-    let index_view = IndexView::new(index_uid.into_inner(), &index)?;
+    let index_view = IndexView::new(index_scheduler.index_name(&index_uid)?, &index)?;
 
     debug!("returns: {:?}", index_view);
 
@@ -230,6 +245,10 @@ pub struct IndexStats {
     pub number_of_documents: u64,
     /// Whether the index is currently performing indexation, according to the scheduler.
     pub is_indexing: bool,
+    /// Number of searches performed on the index.
+    pub search_count: u64,
+    /// Estimation of the memory used by the index, in bytes.
+    pub memory_size: u64,
     /// Association of every field name with the number of times it occurs in the documents.
     pub field_distribution: FieldDistribution,
 }
@@ -239,6 +258,8 @@ impl From<index_scheduler::IndexStats> for IndexStats {
         IndexStats {
             number_of_documents: stats.inner_stats.number_of_documents,
             is_indexing: stats.is_indexing,
+            search_count: stats.search_count,
+            memory_size: stats.memory_size,
             field_distribution: stats.inner_stats.field_distribution,
         }
     }
@@ -258,3 +279,86 @@ pub async fn get_index_stats(
     debug!("returns: {:?}", stats);
     Ok(HttpResponse::Ok().json(stats))
 }
+
+/// Low level statistics of the LMDB environment of an `Index`.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexLmdbStats {
+    /// Size of the environment on disk, in bytes.
+    pub on_disk_size: u64,
+    /// Size of the pages in use, in bytes.
+    pub used_pages_size: u64,
+    /// Size of the free pages, in bytes.
+    pub free_pages_size: u64,
+    /// Size the environment was opened with, in bytes.
+    pub map_size: usize,
+    /// Number of entries in every database of the environment.
+    pub databases: BTreeMap<&'static str, DatabaseStats>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseStats {
+    pub entries: u64,
+}
+
+pub async fn get_index_lmdb_stats(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::STATS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index = index_scheduler.index(&index_uid)?;
+
+    let rtxn = index.read_txn()?;
+    let on_disk_size = index.on_disk_size()?;
+    let used_pages_size = index.used_size()?;
+    let databases = index
+        .databases_entries(&rtxn)?
+        .into_iter()
+        .map(|(name, entries)| (name, DatabaseStats { entries }))
+        .collect();
+
+    let stats = IndexLmdbStats {
+        on_disk_size,
+        used_pages_size,
+        free_pages_size: on_disk_size.saturating_sub(used_pages_size),
+        map_size: index.map_size()?,
+        databases,
+    };
+
+    debug!("returns: {:?}", stats);
+    Ok(HttpResponse::Ok().json(stats))
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetSchema {
+    pub filterable_attributes: Vec<FilterableAttributeSchema>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FilterableAttributeSchema {
+    pub name: String,
+    /// The types of the values found in the documents for this attribute.
+    pub types: BTreeSet<FacetValueType>,
+}
+
+pub async fn get_facet_schema(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index = index_scheduler.index(&index_uid)?;
+
+    let rtxn = index.read_txn()?;
+    let filterable_attributes = index
+        .filterable_fields_value_types(&rtxn)?
+        .into_iter()
+        .map(|(name, types)| FilterableAttributeSchema { name, types })
+        .collect();
+    let schema = FacetSchema { filterable_attributes };
+
+    debug!("returns: {:?}", schema);
+    Ok(HttpResponse::Ok().json(schema))
+}
@@ -1,3 +1,4 @@
+use actix_web::http::header::{Accept, Header};
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
@@ -6,9 +7,10 @@ use log::debug;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::serde_cs::vec::CS;
+use serde::Serialize;
 use serde_json::Value;
 
 use crate::analytics::{Analytics, SearchAggregator};
@@ -16,11 +18,15 @@ use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::search::{
-    add_search_rules, perform_search, MatchingStrategy, SearchQuery, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    add_search_rules, perform_search, FacetValuesDirection, FacetValuesSort, MatchingStrategy,
+    SearchQuery, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_GROUP_LIMIT,
+    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
+    DEFAULT_SEARCH_OFFSET,
 };
 
+/// The content types of the search results serialized in MessagePack, including the legacy one.
+const MSGPACK_CONTENT_TYPES: [&str; 2] = ["application/msgpack", "application/x-msgpack"];
+
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::resource("")
@@ -58,12 +64,24 @@ pub struct SearchQueryGet {
     sort: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowMatchesPosition>)]
     show_matches_position: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowMatchCount>)]
+    show_match_count: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowRankingScore>)]
     show_ranking_score: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowRankingScoreDetails>)]
     show_ranking_score_details: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowRankingStages>)]
+    show_ranking_stages: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowProcessedQuery>)]
+    show_processed_query: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacets>)]
     facets: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDisjunctiveFacets>)]
+    disjunctive_facets: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSortFacetValuesBy>)]
+    sort_facet_values_by: Option<FacetValuesSort>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSortFacetValuesDirection>)]
+    sort_facet_values_direction: Option<FacetValuesDirection>,
     #[deserr( default = DEFAULT_HIGHLIGHT_PRE_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPreTag>)]
     highlight_pre_tag: String,
     #[deserr( default = DEFAULT_HIGHLIGHT_POST_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPostTag>)]
@@ -74,6 +92,18 @@ pub struct SearchQueryGet {
     matching_strategy: MatchingStrategy,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributesToSearchOn>)]
     pub attributes_to_search_on: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchWithinIds>)]
+    pub within_ids: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchLocales>)]
+    pub locales: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDisabledRankingRules>)]
+    pub disabled_ranking_rules: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchGroupBy>)]
+    pub group_by: Option<String>,
+    #[deserr(default = Param(DEFAULT_GROUP_LIMIT()), error = DeserrQueryParamError<InvalidSearchGroupLimit>)]
+    pub group_limit: Param<usize>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchMaxGroups>)]
+    pub max_groups: Option<Param<usize>>,
 }
 
 impl From<SearchQueryGet> for SearchQuery {
@@ -100,14 +130,27 @@ impl From<SearchQueryGet> for SearchQuery {
             filter,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
             show_matches_position: other.show_matches_position.0,
+            show_match_count: other.show_match_count.0,
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
+            show_ranking_stages: other.show_ranking_stages.0,
+            show_processed_query: other.show_processed_query.0,
             facets: other.facets.map(|o| o.into_iter().collect()),
+            disjunctive_facets: other.disjunctive_facets.0,
+            sort_facet_values_by: other.sort_facet_values_by,
+            sort_facet_values_direction: other.sort_facet_values_direction,
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
             matching_strategy: other.matching_strategy,
             attributes_to_search_on: other.attributes_to_search_on.map(|o| o.into_iter().collect()),
+            within_ids: other.within_ids.map(|o| o.into_iter().collect()),
+            locales: other.locales.map(|o| o.into_iter().collect()),
+            disabled_ranking_rules: other.disabled_ranking_rules.map(|o| o.into_iter().collect()),
+            group_by: other.group_by,
+            group_limit: other.group_limit.0,
+            max_groups: other.max_groups.as_deref().copied(),
+            rules_filter: None,
         }
     }
 }
@@ -158,17 +201,22 @@ pub async fn search_with_url_query(
 
     let index = index_scheduler.index(&index_uid)?;
     let features = index_scheduler.features()?;
-    let search_result =
-        tokio::task::spawn_blocking(move || perform_search(&index, query, features)).await?;
+    let cipher = index_scheduler.field_cipher().cloned();
+    let max_facets = index_scheduler.max_facets_per_search();
+    let search_result = tokio::task::spawn_blocking(move || {
+        perform_search(&index, query, features, cipher, max_facets)
+    })
+    .await?;
     if let Ok(ref search_result) = search_result {
         aggregate.succeed(search_result);
     }
     analytics.get_search(aggregate);
 
     let search_result = search_result?;
+    index_scheduler.increment_search_count(&index_uid);
 
     debug!("returns: {:?}", search_result);
-    Ok(HttpResponse::Ok().json(search_result))
+    search_response(&req, &search_result)
 }
 
 pub async fn search_with_post(
@@ -193,17 +241,46 @@ pub async fn search_with_post(
     let index = index_scheduler.index(&index_uid)?;
 
     let features = index_scheduler.features()?;
-    let search_result =
-        tokio::task::spawn_blocking(move || perform_search(&index, query, features)).await?;
+    let cipher = index_scheduler.field_cipher().cloned();
+    let max_facets = index_scheduler.max_facets_per_search();
+    let search_result = tokio::task::spawn_blocking(move || {
+        perform_search(&index, query, features, cipher, max_facets)
+    })
+    .await?;
     if let Ok(ref search_result) = search_result {
         aggregate.succeed(search_result);
     }
     analytics.post_search(aggregate);
 
     let search_result = search_result?;
+    index_scheduler.increment_search_count(&index_uid);
 
     debug!("returns: {:?}", search_result);
-    Ok(HttpResponse::Ok().json(search_result))
+    search_response(&req, &search_result)
+}
+
+/// Serializes the search results in MessagePack if the client accepts it, in JSON otherwise.
+pub(crate) fn search_response(
+    req: &HttpRequest,
+    search_result: &impl Serialize,
+) -> Result<HttpResponse, ResponseError> {
+    // the results are sent with the MessagePack content type the client asked for.
+    let msgpack_content_type = Accept::parse(req).ok().and_then(|accept| {
+        accept.iter().find_map(|accepted| {
+            MSGPACK_CONTENT_TYPES
+                .into_iter()
+                .find(|content_type| accepted.item.essence_str().eq_ignore_ascii_case(content_type))
+        })
+    });
+
+    match msgpack_content_type {
+        Some(content_type) => {
+            let body = rmp_serde::to_vec_named(search_result)
+                .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))?;
+            Ok(HttpResponse::Ok().content_type(content_type).body(body))
+        }
+        None => Ok(HttpResponse::Ok().json(search_result)),
+    }
 }
 
 #[cfg(test)]
@@ -7,7 +7,9 @@ use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::facet_values_sort::FacetValuesSort;
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::settings::{settings, RankingRuleView, Settings, Unchecked};
+use meilisearch_types::settings::{
+    settings, validate_settings, RankingRuleView, Settings, Unchecked,
+};
 use meilisearch_types::tasks::KindWithContent;
 use serde_json::json;
 
@@ -48,6 +50,9 @@ macro_rules! make_setting_route {
 
                 let allow_index_creation =
                     index_scheduler.filters().allow_index_creation(&index_uid);
+                if !allow_index_creation {
+                    index_scheduler.ensure_index_will_exist(&index_uid)?;
+                }
 
                 let task = KindWithContent::SettingsUpdate {
                     index_uid: index_uid.to_string(),
@@ -90,6 +95,9 @@ macro_rules! make_setting_route {
 
                 let allow_index_creation =
                     index_scheduler.filters().allow_index_creation(&index_uid);
+                if !allow_index_creation {
+                    index_scheduler.ensure_index_will_exist(&index_uid)?;
+                }
 
                 let task = KindWithContent::SettingsUpdate {
                     index_uid: index_uid.to_string(),
@@ -385,6 +393,131 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/case-sensitive",
+    put,
+    bool,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsCaseSensitive,
+    >,
+    case_sensitive,
+    "caseSensitive",
+    analytics,
+    |value: &Option<bool>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "CaseSensitive Updated".to_string(),
+            json!({
+                "case_sensitive": {
+                    "enabled": value,
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/languages",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsLanguages,
+    >,
+    languages,
+    "languages",
+    analytics,
+    |languages: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Languages Updated".to_string(),
+            json!({
+                "languages": {
+                    "total": languages.as_ref().map(|languages| languages.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/searchable-numbers",
+    put,
+    bool,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsSearchableNumbers,
+    >,
+    searchable_numbers,
+    "searchableNumbers",
+    analytics,
+    |value: &Option<bool>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "SearchableNumbers Updated".to_string(),
+            json!({
+                "searchable_numbers": {
+                    "enabled": value,
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/tie-breaking-sort",
+    put,
+    Vec<meilisearch_types::settings::RankingRuleView>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsTieBreakingSort,
+    >,
+    tie_breaking_sort,
+    "tieBreakingSort",
+    analytics,
+    |setting: &Option<Vec<meilisearch_types::settings::RankingRuleView>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "TieBreakingSort Updated".to_string(),
+            json!({
+                "tie_breaking_sort": {
+                    "total": setting.as_ref().map(|rules| rules.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/encrypted-attributes",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsEncryptedAttributes,
+    >,
+    encrypted_attributes,
+    "encryptedAttributes",
+    analytics,
+    |setting: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "EncryptedAttributes Updated".to_string(),
+            json!({
+                "encrypted_attributes": {
+                    "total": setting.as_ref().map(|fields| fields.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/synonyms",
     put,
@@ -410,6 +543,31 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/bidirectional-synonyms",
+    put,
+    Vec<Vec<String>>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsBidirectionalSynonyms,
+    >,
+    bidirectional_synonyms,
+    "bidirectionalSynonyms",
+    analytics,
+    |groups: &Option<Vec<Vec<String>>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "BidirectionalSynonyms Updated".to_string(),
+            json!({
+                "bidirectional_synonyms": {
+                    "total": groups.as_ref().map(|groups| groups.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/distinct-attribute",
     put,
@@ -520,6 +678,31 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/pinned-in-memory",
+    put,
+    bool,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsPinnedInMemory,
+    >,
+    pinned_in_memory,
+    "pinnedInMemory",
+    analytics,
+    |value: &Option<bool>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "PinnedInMemory Updated".to_string(),
+            json!({
+                "pinned_in_memory": {
+                    "enabled": value,
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 macro_rules! generate_configure {
     ($($mod:ident),*) => {
         pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -529,6 +712,7 @@ macro_rules! generate_configure {
                 .route(web::patch().to(SeqHandler(update_all)))
                 .route(web::get().to(SeqHandler(get_all)))
                 .route(web::delete().to(SeqHandler(delete_all))))
+                .service(web::resource("/validate").route(web::post().to(SeqHandler(validate))))
                 $(.service($mod::resources()))*;
         }
     };
@@ -544,11 +728,18 @@ generate_configure!(
     separator_tokens,
     non_separator_tokens,
     dictionary,
+    case_sensitive,
+    languages,
+    searchable_numbers,
     synonyms,
+    bidirectional_synonyms,
     ranking_rules,
+    tie_breaking_sort,
+    encrypted_attributes,
     typo_tolerance,
     pagination,
-    faceting
+    faceting,
+    pinned_in_memory
 );
 
 pub async fn update_all(
@@ -562,101 +753,122 @@ pub async fn update_all(
 
     let new_settings = body.into_inner();
 
-    analytics.publish(
-        "Settings Updated".to_string(),
-        json!({
-           "ranking_rules": {
-                "words_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Words))),
-                "typo_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Typo))),
-                "proximity_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Proximity))),
-                "attribute_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Attribute))),
-                "sort_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Sort))),
-                "exactness_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Exactness))),
-                "values": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().filter(|s| !matches!(s, RankingRuleView::Asc(_) | RankingRuleView::Desc(_)) ).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
-            },
-            "searchable_attributes": {
-                "total": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.len()),
-                "with_wildcard": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.iter().any(|searchable| searchable == "*")),
-            },
-            "displayed_attributes": {
-                "total": new_settings.displayed_attributes.as_ref().set().map(|displayed| displayed.len()),
-                "with_wildcard": new_settings.displayed_attributes.as_ref().set().map(|displayed| displayed.iter().any(|displayed| displayed == "*")),
-            },
-           "sortable_attributes": {
-                "total": new_settings.sortable_attributes.as_ref().set().map(|sort| sort.len()),
-                "has_geo": new_settings.sortable_attributes.as_ref().set().map(|sort| sort.iter().any(|s| s == "_geo")),
-            },
-           "filterable_attributes": {
-                "total": new_settings.filterable_attributes.as_ref().set().map(|filter| filter.len()),
-                "has_geo": new_settings.filterable_attributes.as_ref().set().map(|filter| filter.iter().any(|s| s == "_geo")),
-            },
-            "distinct_attribute": {
-                "set": new_settings.distinct_attribute.as_ref().set().is_some()
-            },
-            "typo_tolerance": {
-                "enabled": new_settings.typo_tolerance
-                    .as_ref()
-                    .set()
-                    .and_then(|s| s.enabled.as_ref().set())
-                    .copied(),
-                "disable_on_attributes": new_settings.typo_tolerance
-                    .as_ref()
-                    .set()
-                    .and_then(|s| s.disable_on_attributes.as_ref().set().map(|m| !m.is_empty())),
-                "disable_on_words": new_settings.typo_tolerance
+    // the event is only published once the settings update has been registered
+    let analytics_event = json!({
+        "ranking_rules": {
+            "words_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Words))),
+            "typo_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Typo))),
+            "proximity_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Proximity))),
+            "attribute_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Attribute))),
+            "sort_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Sort))),
+            "exactness_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Exactness))),
+            "values": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().filter(|s| !matches!(s, RankingRuleView::Asc(_) | RankingRuleView::Desc(_)) ).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
+        },
+        "searchable_attributes": {
+            "total": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.len()),
+            "with_wildcard": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.iter().any(|searchable| searchable == "*")),
+        },
+        "displayed_attributes": {
+            "total": new_settings.displayed_attributes.as_ref().set().map(|displayed| displayed.len()),
+            "with_wildcard": new_settings.displayed_attributes.as_ref().set().map(|displayed| displayed.iter().any(|displayed| displayed == "*")),
+        },
+       "sortable_attributes": {
+            "total": new_settings.sortable_attributes.as_ref().set().map(|sort| sort.len()),
+            "has_geo": new_settings.sortable_attributes.as_ref().set().map(|sort| sort.iter().any(|s| s == "_geo")),
+        },
+       "filterable_attributes": {
+            "total": new_settings.filterable_attributes.as_ref().set().map(|filter| filter.len()),
+            "has_geo": new_settings.filterable_attributes.as_ref().set().map(|filter| filter.iter().any(|s| s == "_geo")),
+        },
+        "distinct_attribute": {
+            "set": new_settings.distinct_attribute.as_ref().set().is_some()
+        },
+        "typo_tolerance": {
+            "enabled": new_settings.typo_tolerance
+                .as_ref()
+                .set()
+                .and_then(|s| s.enabled.as_ref().set())
+                .copied(),
+            "disable_on_attributes": new_settings.typo_tolerance
+                .as_ref()
+                .set()
+                .and_then(|s| s.disable_on_attributes.as_ref().set().map(|m| !m.is_empty())),
+            "disable_on_words": new_settings.typo_tolerance
+                .as_ref()
+                .set()
+                .and_then(|s| s.disable_on_words.as_ref().set().map(|m| !m.is_empty())),
+            "min_word_size_for_one_typo": new_settings.typo_tolerance
+                .as_ref()
+                .set()
+                .and_then(|s| s.min_word_size_for_typos
                     .as_ref()
                     .set()
-                    .and_then(|s| s.disable_on_words.as_ref().set().map(|m| !m.is_empty())),
-                "min_word_size_for_one_typo": new_settings.typo_tolerance
+                    .map(|s| s.one_typo.set()))
+                .flatten(),
+            "min_word_size_for_two_typos": new_settings.typo_tolerance
+                .as_ref()
+                .set()
+                .and_then(|s| s.min_word_size_for_typos
                     .as_ref()
                     .set()
-                    .and_then(|s| s.min_word_size_for_typos
-                        .as_ref()
-                        .set()
-                        .map(|s| s.one_typo.set()))
-                    .flatten(),
-                "min_word_size_for_two_typos": new_settings.typo_tolerance
-                    .as_ref()
-                    .set()
-                    .and_then(|s| s.min_word_size_for_typos
-                        .as_ref()
-                        .set()
-                        .map(|s| s.two_typos.set()))
-                    .flatten(),
-            },
-            "faceting": {
-                "max_values_per_facet": new_settings.faceting
-                    .as_ref()
-                    .set()
-                    .and_then(|s| s.max_values_per_facet.as_ref().set()),
-                "sort_facet_values_by_star_count": new_settings.faceting
-                    .as_ref()
-                    .set()
-                    .and_then(|s| {
-                        s.sort_facet_values_by.as_ref().set().map(|s| s.iter().any(|(k, v)| k == "*" && v == &FacetValuesSort::Count))
-                    }),
-                "sort_facet_values_by_total": new_settings.faceting
-                    .as_ref()
-                    .set()
-                    .and_then(|s| s.sort_facet_values_by.as_ref().set().map(|s| s.len())),
-            },
-            "pagination": {
-                "max_total_hits": new_settings.pagination
-                    .as_ref()
-                    .set()
-                    .and_then(|s| s.max_total_hits.as_ref().set()),
-            },
-            "stop_words": {
-                "total": new_settings.stop_words.as_ref().set().map(|stop_words| stop_words.len()),
-            },
-            "synonyms": {
-                "total": new_settings.synonyms.as_ref().set().map(|synonyms| synonyms.len()),
-            },
-        }),
-        Some(&req),
-    );
+                    .map(|s| s.two_typos.set()))
+                .flatten(),
+        },
+        "faceting": {
+            "max_values_per_facet": new_settings.faceting
+                .as_ref()
+                .set()
+                .and_then(|s| s.max_values_per_facet.as_ref().set()),
+            "sort_facet_values_by_star_count": new_settings.faceting
+                .as_ref()
+                .set()
+                .and_then(|s| {
+                    s.sort_facet_values_by.as_ref().set().map(|s| s.iter().any(|(k, v)| k == "*" && v == &FacetValuesSort::Count))
+                }),
+            "sort_facet_values_by_total": new_settings.faceting
+                .as_ref()
+                .set()
+                .and_then(|s| s.sort_facet_values_by.as_ref().set().map(|s| s.len())),
+        },
+        "pagination": {
+            "max_total_hits": new_settings.pagination
+                .as_ref()
+                .set()
+                .and_then(|s| s.max_total_hits.as_ref().set()),
+        },
+        "stop_words": {
+            "total": new_settings.stop_words.as_ref().set().map(|stop_words| stop_words.len()),
+        },
+        "case_sensitive": {
+            "enabled": new_settings.case_sensitive.as_ref().set(),
+        },
+        "languages": {
+            "total": new_settings.languages.as_ref().set().map(|languages| languages.len()),
+        },
+        "searchable_numbers": {
+            "enabled": new_settings.searchable_numbers.as_ref().set(),
+        },
+        "tie_breaking_sort": {
+            "total": new_settings.tie_breaking_sort.as_ref().set().map(|rules| rules.len()),
+        },
+        "encrypted_attributes": {
+            "total": new_settings.encrypted_attributes.as_ref().set().map(|fields| fields.len()),
+        },
+        "synonyms": {
+            "total": new_settings.synonyms.as_ref().set().map(|synonyms| synonyms.len()),
+        },
+        "bidirectional_synonyms": {
+            "total": new_settings.bidirectional_synonyms.as_ref().set().map(|groups| groups.len()),
+        },
+        "pinned_in_memory": {
+            "enabled": new_settings.pinned_in_memory.as_ref().set(),
+        },
+    });
 
     let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+    if !allow_index_creation {
+        index_scheduler.ensure_index_will_exist(&index_uid)?;
+    }
     let index_uid = IndexUid::try_from(index_uid.into_inner())?.into_inner();
     let task = KindWithContent::SettingsUpdate {
         index_uid,
@@ -667,6 +879,8 @@ pub async fn update_all(
     let task: SummarizedTaskView =
         tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
 
+    analytics.publish("Settings Updated".to_string(), analytics_event, Some(&req));
+
     debug!("returns: {:?}", task);
     Ok(HttpResponse::Accepted().json(task))
 }
@@ -684,6 +898,20 @@ pub async fn get_all(
     Ok(HttpResponse::Ok().json(new_settings))
 }
 
+pub async fn validate(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<Settings<Unchecked>, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let warnings = validate_settings(&index, &rtxn, &body.into_inner())?;
+    debug!("returns: {:?}", warnings);
+    Ok(HttpResponse::Ok().json(json!({ "warnings": warnings })))
+}
+
 pub async fn delete_all(
     index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
@@ -693,6 +921,9 @@ pub async fn delete_all(
     let new_settings = Settings::cleared().into_unchecked();
 
     let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+    if !allow_index_creation {
+        index_scheduler.ensure_index_will_exist(&index_uid)?;
+    }
     let index_uid = IndexUid::try_from(index_uid.into_inner())?.into_inner();
     let task = KindWithContent::SettingsUpdate {
         index_uid,
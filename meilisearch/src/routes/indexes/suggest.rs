@@ -0,0 +1,61 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::search::{perform_suggest, DEFAULT_SUGGEST_LIMIT};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(suggest))));
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SuggestQuery {
+    #[deserr(error = DeserrQueryParamError<InvalidSuggestPrefix>, missing_field_error = DeserrQueryParamError::missing_suggest_prefix)]
+    pub prefix: String,
+    #[deserr(default = Param(DEFAULT_SUGGEST_LIMIT()), error = DeserrQueryParamError<InvalidSuggestLimit>)]
+    pub limit: Param<usize>,
+}
+
+pub async fn suggest(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<SuggestQuery, DeserrQueryParamError>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let SuggestQuery { prefix, limit } = params.into_inner();
+    debug!("suggest called with prefix: {:?}, limit: {:?}", prefix, limit);
+
+    if prefix.trim().is_empty() {
+        return Err(ResponseError::from_msg(
+            "Invalid value in parameter `prefix`: the prefix must not be empty.".to_string(),
+            Code::InvalidSuggestPrefix,
+        ));
+    }
+
+    // Tenant token search_rules, the suggestions are restricted to the authorized documents.
+    let filter = index_scheduler
+        .filters()
+        .get_index_search_rules(&index_uid)
+        .and_then(|search_rules| search_rules.filter);
+
+    let index = index_scheduler.index(&index_uid)?;
+    let suggest_result =
+        tokio::task::spawn_blocking(move || perform_suggest(&index, prefix, limit.0, filter))
+            .await??;
+
+    debug!("returns: {:?}", suggest_result);
+    Ok(HttpResponse::Ok().json(suggest_result))
+}
@@ -2,11 +2,12 @@ use std::collections::BTreeMap;
 
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
-use index_scheduler::IndexScheduler;
-use log::debug;
+use index_scheduler::{IndexScheduler, MemorySizes};
+use log::{debug, error};
 use meilisearch_auth::AuthController;
 use meilisearch_types::error::ResponseError;
-use meilisearch_types::settings::{Settings, Unchecked};
+use meilisearch_types::milli;
+use meilisearch_types::settings::{RankingRuleView, Settings, Unchecked};
 use meilisearch_types::tasks::{Kind, Status, Task, TaskId};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -36,6 +37,10 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(web::scope("/snapshots").configure(snapshot::configure))
         .service(web::resource("/stats").route(web::get().to(get_stats)))
         .service(web::resource("/version").route(web::get().to(get_version)))
+        .service(
+            web::resource("/ranking-rules/available")
+                .route(web::get().to(get_available_ranking_rules)),
+        )
         .service(web::scope("/indexes").configure(indexes::configure))
         .service(web::scope("/multi-search").configure(multi_search::configure))
         .service(web::scope("/swap-indexes").configure(swap_indexes::configure))
@@ -263,12 +268,20 @@ pub fn create_all_stats(
     let mut indexes = BTreeMap::new();
     let mut database_size = 0;
     let mut used_database_size = 0;
+    let memory_sizes = MemorySizes::read()?;
 
     for index_uid in index_scheduler.index_names()? {
         // Accumulate the size of all indexes, even unauthorized ones, so
         // as to return a database_size representative of the correct database size on disk.
         // See <https://github.com/meilisearch/meilisearch/pull/3541#discussion_r1126747643> for context.
-        let stats = index_scheduler.index_stats(&index_uid)?;
+        // An index failing to report its stats must not prevent the others from being reported.
+        let stats = match index_scheduler.index_stats_with(&index_uid, &memory_sizes) {
+            Ok(stats) => stats,
+            Err(e) => {
+                error!("Could not retrieve the stats of the index `{index_uid}`: {e}");
+                continue;
+            }
+        };
         database_size += stats.inner_stats.database_size;
         used_database_size += stats.inner_stats.used_database_size;
 
@@ -316,6 +329,16 @@ async fn get_version(
     })
 }
 
+/// Returns the built-in ranking rules, in the order they are applied by default.
+async fn get_available_ranking_rules(
+    _index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+) -> HttpResponse {
+    let ranking_rules: Vec<_> =
+        milli::default_criteria().into_iter().map(RankingRuleView::from).collect();
+    debug!("returns: {:?}", ranking_rules);
+    HttpResponse::Ok().json(ranking_rules)
+}
+
 #[derive(Serialize)]
 struct KeysResponse {
     private: Option<String>,
@@ -13,6 +13,7 @@ use crate::analytics::{Analytics, MultiSearchAggregator};
 use crate::extractors::authentication::policies::ActionPolicy;
 use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::indexes::search::search_response;
 use crate::search::{
     add_search_rules, perform_search, SearchQueryWithIndex, SearchResultWithIndex,
 };
@@ -76,14 +77,20 @@ pub async fn multi_search_with_post(
                     })
                     .with_index(query_index)?;
 
-                let search_result =
-                    tokio::task::spawn_blocking(move || perform_search(&index, query, features))
-                        .await
-                        .with_index(query_index)?;
+                let cipher = index_scheduler.field_cipher().cloned();
+                let max_facets = index_scheduler.max_facets_per_search();
+                let search_result = tokio::task::spawn_blocking(move || {
+                    perform_search(&index, query, features, cipher, max_facets)
+                })
+                .await
+                .with_index(query_index)?;
+
+                let search_result = search_result.with_index(query_index)?;
+                index_scheduler.increment_search_count(&index_uid);
 
                 search_results.push(SearchResultWithIndex {
                     index_uid: index_uid.into_inner(),
-                    result: search_result.with_index(query_index)?,
+                    result: search_result,
                 });
             }
             Ok(search_results)
@@ -106,7 +113,7 @@ pub async fn multi_search_with_post(
 
     debug!("returns: {:?}", search_results);
 
-    Ok(HttpResponse::Ok().json(SearchResults { results: search_results }))
+    search_response(&req, &SearchResults { results: search_results })
 }
 
 /// Local `Result` extension trait to avoid `map_err` boilerplate.
@@ -1,10 +1,16 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use actix_web::http::header::{CacheControl, CacheDirective, ContentEncoding, ContentType};
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
-use deserr::actix_web::AwebQueryParameter;
+use bytes::Bytes;
+use deserr::actix_web::{AwebJson, AwebQueryParameter};
 use deserr::Deserr;
-use index_scheduler::{IndexScheduler, Query, TaskId};
+use index_scheduler::{IndexScheduler, Query, TaskEvent, TaskId};
 use meilisearch_types::deserr::query_params::Param;
-use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{InvalidTaskDateError, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
@@ -18,6 +24,7 @@ use serde_json::json;
 use time::format_description::well_known::Rfc3339;
 use time::macros::format_description;
 use time::{Date, Duration, OffsetDateTime, Time};
+use tokio::sync::broadcast::{self, error::RecvError};
 use tokio::task;
 
 use super::SummarizedTaskView;
@@ -28,6 +35,9 @@ use crate::extractors::sequential_extractor::SeqHandler;
 
 const DEFAULT_LIMIT: u32 = 20;
 
+/// The interval without any event after which a comment keeps the connection alive.
+const TASK_EVENTS_KEEP_ALIVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
+
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::resource("")
@@ -35,6 +45,9 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::delete().to(SeqHandler(delete_tasks))),
     )
     .service(web::resource("/cancel").route(web::post().to(SeqHandler(cancel_tasks))))
+    .service(web::resource("/abort").route(web::post().to(SeqHandler(abort_tasks))))
+    .service(web::resource("/events").route(web::get().to(SeqHandler(task_events))))
+    .service(web::resource("/fetch").route(web::post().to(SeqHandler(fetch_tasks))))
     .service(web::resource("/{task_id}").route(web::get().to(SeqHandler(get_task))));
 }
 
@@ -87,6 +100,12 @@ pub struct DetailsView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indexed_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub generated_ids: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicated_documents: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejected_documents: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub primary_key: Option<Option<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub provided_ids: Option<usize>,
@@ -103,6 +122,10 @@ pub struct DetailsView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dump_uid: Option<Option<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub reindexed_documents: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changed_settings: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(flatten)]
     pub settings: Option<Box<Settings<Unchecked>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -112,16 +135,28 @@ pub struct DetailsView {
 impl From<Details> for DetailsView {
     fn from(details: Details) -> Self {
         match details {
-            Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
+            Details::DocumentAdditionOrUpdate {
+                received_documents,
+                indexed_documents,
+                duplicated_documents,
+                rejected_documents,
+                generated_ids,
+            } => DetailsView {
+                received_documents: Some(received_documents),
+                indexed_documents: Some(indexed_documents),
+                generated_ids,
+                duplicated_documents,
+                rejected_documents,
+                ..DetailsView::default()
+            },
+            Details::SettingsUpdate { settings, reindexed_documents, changed_settings } => {
                 DetailsView {
-                    received_documents: Some(received_documents),
-                    indexed_documents: Some(indexed_documents),
+                    settings: Some(settings),
+                    reindexed_documents,
+                    changed_settings,
                     ..DetailsView::default()
                 }
             }
-            Details::SettingsUpdate { settings } => {
-                DetailsView { settings: Some(settings), ..DetailsView::default() }
-            }
             Details::IndexInfo { primary_key } => {
                 DetailsView { primary_key: Some(primary_key), ..DetailsView::default() }
             }
@@ -339,6 +374,34 @@ async fn cancel_tasks(
     Ok(HttpResponse::Ok().json(task))
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbortedTasksView {
+    /// No task cancelation is registered when there is no enqueued task.
+    #[serde(flatten)]
+    task: Option<SummarizedTaskView>,
+    aborted_tasks: u64,
+}
+
+async fn abort_tasks(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_CANCEL }>, Data<IndexScheduler>>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    analytics.publish("Tasks Aborted".to_string(), json!({}), Some(&req));
+
+    let aborted = task::spawn_blocking(move || {
+        index_scheduler.abort_enqueued_tasks(index_scheduler.filters())
+    })
+    .await??;
+    let view = match aborted {
+        Some((task, aborted_tasks)) => AbortedTasksView { task: Some(task.into()), aborted_tasks },
+        None => AbortedTasksView { task: None, aborted_tasks: 0 },
+    };
+
+    Ok(HttpResponse::Ok().json(view))
+}
+
 async fn delete_tasks(
     index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_DELETE }>, Data<IndexScheduler>>,
     params: AwebQueryParameter<TaskDeletionOrCancelationQuery, DeserrQueryParamError>,
@@ -421,6 +484,153 @@ async fn get_tasks(
     Ok(HttpResponse::Ok().json(tasks))
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskEventView {
+    uid: TaskId,
+    index_uid: Option<String>,
+    status: Status,
+    #[serde(rename = "type")]
+    kind: Kind,
+}
+
+async fn task_events(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    params: AwebQueryParameter<TaskDeletionOrCancelationQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let params = params.into_inner();
+    analytics.publish(
+        "Task Events Subscribed".to_string(),
+        json!({
+            "filtered_by_uid": params.uids.is_some(),
+            "filtered_by_index_uid": params.index_uids.is_some(),
+            "filtered_by_type": params.types.is_some(),
+            "filtered_by_status": params.statuses.is_some(),
+        }),
+        Some(&req),
+    );
+
+    let mut query = params.into_query();
+    // the statuses and uids are matched against the events, not against the stored tasks.
+    let statuses = query.statuses.take();
+    let uids = query.uids.take();
+
+    // a client resuming its stream sends the id of the last event it received.
+    let last_event = req
+        .headers()
+        .get("Last-Event-ID")
+        .and_then(|id| id.to_str().ok())
+        .and_then(TaskEvent::from_id);
+    // subscribing before catching up makes sure that no event is missed in between.
+    let mut receiver = index_scheduler.subscribe_to_task_events();
+    let index_scheduler = Arc::new(index_scheduler);
+
+    let stream = async_stream::stream! {
+        let mut next_events = match last_event {
+            Some(last_event) => {
+                let index_scheduler = index_scheduler.clone();
+                match task::spawn_blocking(move || index_scheduler.task_events_after(last_event))
+                    .await
+                {
+                    Ok(Ok(events)) => Some(events),
+                    Ok(Err(e)) => {
+                        log::error!("Could not retrieve the missed task events: {e}");
+                        None
+                    }
+                    Err(e) => {
+                        log::error!("Could not retrieve the missed task events: {e}");
+                        None
+                    }
+                }
+            }
+            None => Some(Vec::new()),
+        };
+        // the events sent while catching up may be published again afterwards.
+        let caught_up: HashSet<_> = next_events.iter().flatten().copied().collect();
+
+        while let Some(events) = next_events {
+            let events: Vec<_> = events
+                .into_iter()
+                .filter(|event| statuses.as_ref().map_or(true, |s| s.contains(&event.status)))
+                .filter(|event| uids.as_ref().map_or(true, |u| u.contains(&event.task_uid)))
+                .collect();
+
+            if events.is_empty() {
+                yield Ok::<_, Infallible>(Bytes::from_static(b": keep-alive\n\n"));
+            } else {
+                // only keep the tasks matching the other filters and visible with this key.
+                let query = Query {
+                    uids: Some(events.iter().map(|event| event.task_uid).collect()),
+                    ..query.clone()
+                };
+                let index_scheduler = index_scheduler.clone();
+                let tasks = task::spawn_blocking(move || {
+                    index_scheduler.get_tasks_from_authorized_indexes(query, index_scheduler.filters())
+                })
+                .await;
+                let tasks = match tasks {
+                    Ok(Ok((tasks, _))) => tasks,
+                    Ok(Err(e)) => {
+                        log::error!("Could not retrieve the tasks of the task events: {e}");
+                        break;
+                    }
+                    Err(e) => {
+                        log::error!("Could not retrieve the tasks of the task events: {e}");
+                        break;
+                    }
+                };
+                let tasks: HashMap<_, _> = tasks.into_iter().map(|task| (task.uid, task)).collect();
+
+                for event in events {
+                    let Some(task) = tasks.get(&event.task_uid) else { continue };
+                    let view = TaskEventView {
+                        uid: task.uid,
+                        index_uid: task.index_uid().map(ToOwned::to_owned),
+                        status: event.status,
+                        kind: task.kind.as_kind(),
+                    };
+                    let data = serde_json::to_string(&view).unwrap();
+                    let id = event.id();
+                    yield Ok(Bytes::from(format!("id: {id}\nevent: task\ndata: {data}\n\n")));
+                }
+            }
+
+            next_events = next_task_events(&mut receiver, &caught_up).await;
+        }
+    };
+
+    Ok(HttpResponse::Ok()
+        .insert_header(ContentType(mime::TEXT_EVENT_STREAM))
+        .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+        // prevents the compression middleware from buffering the events.
+        .insert_header(ContentEncoding::Identity)
+        .streaming(stream))
+}
+
+/// Wait for the next status transitions of the tasks, skipping the ones already sent.
+///
+/// Returns no events when none happened for a while and `None` when the stream must be closed,
+/// a client lagging behind resumes its stream from the last event it received.
+async fn next_task_events(
+    receiver: &mut broadcast::Receiver<TaskEvent>,
+    already_sent: &HashSet<TaskEvent>,
+) -> Option<Vec<TaskEvent>> {
+    let event = match tokio::time::timeout(TASK_EVENTS_KEEP_ALIVE_INTERVAL, receiver.recv()).await {
+        Ok(Ok(event)) => event,
+        Ok(Err(RecvError::Lagged(_) | RecvError::Closed)) => return None,
+        Err(_elapsed) => return Some(Vec::new()),
+    };
+    // the events published together are sent together.
+    let mut events = vec![event];
+    while let Ok(event) = receiver.try_recv() {
+        events.push(event);
+    }
+    events.retain(|event| !already_sent.contains(event));
+    Some(events)
+}
+
 async fn get_task(
     index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
     task_uid: web::Path<String>,
@@ -450,6 +660,30 @@ async fn get_task(
     }
 }
 
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct FetchTasks {
+    #[deserr(error = DeserrJsonError<InvalidTaskUids>, missing_field_error = DeserrJsonError::missing_task_uids)]
+    pub uids: Vec<TaskId>,
+}
+
+async fn fetch_tasks(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    body: AwebJson<FetchTasks, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let FetchTasks { uids } = body.into_inner();
+
+    analytics.publish("Tasks Seen".to_string(), json!({ "per_task_uids": true }), Some(&req));
+
+    // the tasks are returned in the order of the uids, `null` for the unknown ones
+    let tasks = index_scheduler.get_tasks_by_uids(&uids, index_scheduler.filters())?;
+    let results: Vec<_> = tasks.iter().map(|task| task.as_ref().map(TaskView::from_task)).collect();
+
+    Ok(HttpResponse::Ok().json(json!({ "results": results })))
+}
+
 pub enum DeserializeDateOption {
     Before,
     After,
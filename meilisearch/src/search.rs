@@ -1,5 +1,5 @@
 use std::cmp::min;
-use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::str::FromStr;
 use std::time::Instant;
 
@@ -10,6 +10,7 @@ use indexmap::IndexMap;
 use log::warn;
 use meilisearch_auth::IndexSearchRules;
 use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::encryption::{decrypt_document, FieldCipher};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
@@ -19,10 +20,11 @@ use meilisearch_types::milli::{
 };
 use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
-use milli::tokenizer::TokenizerBuilder;
+use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds, MatcherBuilder,
-    SortError, TermsMatchingStrategy, VectorOrArrayOfVectors, DEFAULT_VALUES_PER_FACET,
+    AscDesc, Condition, Criterion, FieldId, FieldsIdsMap, Filter, FilterCondition, FormatOptions,
+    Index, MatchBounds, MatcherBuilder, SortError, TermsMatchingStrategy, Token,
+    VectorOrArrayOfVectors, DEFAULT_VALUES_PER_FACET,
 };
 use ordered_float::OrderedFloat;
 use regex::Regex;
@@ -39,6 +41,8 @@ pub const DEFAULT_CROP_LENGTH: fn() -> usize = || 10;
 pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
+pub const DEFAULT_SUGGEST_LIMIT: fn() -> usize = || 10;
+pub const DEFAULT_GROUP_LIMIT: fn() -> usize = || 3;
 
 #[derive(Debug, Clone, Default, PartialEq, Deserr)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
@@ -65,16 +69,28 @@ pub struct SearchQuery {
     pub attributes_to_highlight: Option<HashSet<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchesPosition>, default)]
     pub show_matches_position: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchCount>, default)]
+    pub show_match_count: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScore>, default)]
     pub show_ranking_score: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScoreDetails>, default)]
     pub show_ranking_score_details: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingStages>, default)]
+    pub show_ranking_stages: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowProcessedQuery>, default)]
+    pub show_processed_query: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>, default)]
+    pub disjunctive_facets: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSortFacetValuesBy>)]
+    pub sort_facet_values_by: Option<FacetValuesSort>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSortFacetValuesDirection>)]
+    pub sort_facet_values_direction: Option<FacetValuesDirection>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -85,6 +101,22 @@ pub struct SearchQuery {
     pub matching_strategy: MatchingStrategy,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
     pub attributes_to_search_on: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchWithinIds>)]
+    pub within_ids: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisabledRankingRules>)]
+    pub disabled_ranking_rules: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGroupBy>)]
+    pub group_by: Option<String>,
+    #[deserr(default = DEFAULT_GROUP_LIMIT(), error = DeserrJsonError<InvalidSearchGroupLimit>)]
+    pub group_limit: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchMaxGroups>)]
+    pub max_groups: Option<usize>,
+    /// The filter of the search rules of a tenant token, kept apart from the filter
+    /// of the query so that it always applies, even when the latter is relaxed.
+    #[deserr(skip)]
+    pub rules_filter: Option<Value>,
 }
 
 impl SearchQuery {
@@ -126,14 +158,26 @@ pub struct SearchQueryWithIndex {
     pub show_ranking_score: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScoreDetails>, default)]
     pub show_ranking_score_details: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingStages>, default)]
+    pub show_ranking_stages: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowProcessedQuery>, default)]
+    pub show_processed_query: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchesPosition>, default)]
     pub show_matches_position: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchCount>, default)]
+    pub show_match_count: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>, default)]
+    pub disjunctive_facets: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSortFacetValuesBy>)]
+    pub sort_facet_values_by: Option<FacetValuesSort>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSortFacetValuesDirection>)]
+    pub sort_facet_values_direction: Option<FacetValuesDirection>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -144,6 +188,18 @@ pub struct SearchQueryWithIndex {
     pub matching_strategy: MatchingStrategy,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
     pub attributes_to_search_on: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchWithinIds>)]
+    pub within_ids: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisabledRankingRules>)]
+    pub disabled_ranking_rules: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGroupBy>)]
+    pub group_by: Option<String>,
+    #[deserr(default = DEFAULT_GROUP_LIMIT(), error = DeserrJsonError<InvalidSearchGroupLimit>)]
+    pub group_limit: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchMaxGroups>)]
+    pub max_groups: Option<usize>,
 }
 
 impl SearchQueryWithIndex {
@@ -162,15 +218,27 @@ impl SearchQueryWithIndex {
             attributes_to_highlight,
             show_ranking_score,
             show_ranking_score_details,
+            show_ranking_stages,
+            show_processed_query,
             show_matches_position,
+            show_match_count,
             filter,
             sort,
             facets,
+            disjunctive_facets,
+            sort_facet_values_by,
+            sort_facet_values_direction,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
             attributes_to_search_on,
+            within_ids,
+            locales,
+            disabled_ranking_rules,
+            group_by,
+            group_limit,
+            max_groups,
         } = self;
         (
             index_uid,
@@ -187,15 +255,28 @@ impl SearchQueryWithIndex {
                 attributes_to_highlight,
                 show_ranking_score,
                 show_ranking_score_details,
+                show_ranking_stages,
+                show_processed_query,
                 show_matches_position,
+                show_match_count,
                 filter,
                 sort,
                 facets,
+                disjunctive_facets,
+                sort_facet_values_by,
+                sort_facet_values_direction,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
                 matching_strategy,
                 attributes_to_search_on,
+                within_ids,
+                locales,
+                disabled_ranking_rules,
+                group_by,
+                group_limit,
+                max_groups,
+                rules_filter: None,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
             },
@@ -238,6 +319,25 @@ pub enum FacetValuesSort {
     Count,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserr)]
+#[deserr(rename_all = camelCase)]
+pub enum FacetValuesDirection {
+    /// Alphabetical order from A to Z, or increasing count.
+    Asc,
+    /// Alphabetical order from Z to A, or decreasing count.
+    Desc,
+}
+
+impl FacetValuesDirection {
+    /// The direction in which milli returns the facet values of this order.
+    fn natural(order_by: OrderBy) -> Self {
+        match order_by {
+            OrderBy::Lexicographic => FacetValuesDirection::Asc,
+            OrderBy::Count => FacetValuesDirection::Desc,
+        }
+    }
+}
+
 impl From<FacetValuesSort> for OrderBy {
     fn from(val: FacetValuesSort) -> Self {
         match val {
@@ -255,6 +355,8 @@ pub struct SearchHit {
     pub formatted: Document,
     #[serde(rename = "_matchesPosition", skip_serializing_if = "Option::is_none")]
     pub matches_position: Option<MatchesPosition>,
+    #[serde(rename = "_matchCount", skip_serializing_if = "Option::is_none")]
+    pub match_count: Option<usize>,
     #[serde(rename = "_rankingScore", skip_serializing_if = "Option::is_none")]
     pub ranking_score: Option<f64>,
     #[serde(rename = "_rankingScoreDetails", skip_serializing_if = "Option::is_none")]
@@ -270,13 +372,20 @@ pub struct SearchResult {
     pub query: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vector: Option<Vec<f32>>,
-    pub processing_time_ms: u128,
+    // MessagePack has no 128 bits integers
+    pub processing_time_ms: u64,
     #[serde(flatten)]
     pub hits_info: HitsInfo,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<HitsGroup>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranking_stages: Option<Vec<RankingStage>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub processed_query: Option<Vec<ProcessedQueryTerm>>,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -296,6 +405,29 @@ pub enum HitsInfo {
     OffsetLimit { limit: usize, offset: usize, estimated_total_hits: usize },
 }
 
+/// The best hits sharing the same value of the `groupBy` attribute.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HitsGroup {
+    pub value: String,
+    pub hits: Vec<SearchHit>,
+    pub total_hits: u64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RankingStage {
+    pub ranking_rule: String,
+    pub candidates: u64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessedQueryTerm {
+    pub term: String,
+    pub synonyms: Vec<String>,
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq)]
 pub struct FacetStats {
     pub min: f64,
@@ -310,24 +442,60 @@ pub struct FacetSearchResult {
     pub processing_time_ms: u128,
 }
 
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestResult {
+    pub suggestions: Vec<Suggestion>,
+    pub prefix: String,
+    pub processing_time_ms: u128,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub term: String,
+    /// The number of documents containing the term.
+    pub count: u64,
+}
+
 /// Incorporate search rules in search query
 pub fn add_search_rules(query: &mut SearchQuery, rules: IndexSearchRules) {
-    query.filter = match (query.filter.take(), rules.filter) {
-        (None, rules_filter) => rules_filter,
-        (filter, None) => filter,
-        (Some(filter), Some(rules_filter)) => {
-            let filter = match filter {
-                Value::Array(filter) => filter,
-                filter => vec![filter],
-            };
-            let rules_filter = match rules_filter {
-                Value::Array(rules_filter) => rules_filter,
-                rules_filter => vec![rules_filter],
-            };
+    query.rules_filter = rules.filter;
+}
 
-            Some(Value::Array([filter, rules_filter].concat()))
-        }
-    }
+/// Replaces the unicode whitespaces of a query, like the non-breaking spaces, by plain spaces
+/// and trims it. Returns `None` for a query made only of whitespaces, which matches everything.
+fn normalize_query(query: &str) -> Option<String> {
+    let query: String = query.chars().map(|c| if c.is_whitespace() { ' ' } else { c }).collect();
+    let query = query.trim();
+    (!query.is_empty()).then(|| query.to_string())
+}
+
+/// Converts the locales of a search query into the languages known by the tokenizer.
+fn parse_locales(locales: &[String]) -> Result<Vec<Language>, MeilisearchHttpError> {
+    locales
+        .iter()
+        .map(|locale| {
+            let language = Language::from_name(locale);
+            if language.name() == locale {
+                Ok(language)
+            } else {
+                Err(MeilisearchHttpError::InvalidSearchLocale(locale.clone()))
+            }
+        })
+        .collect()
+}
+
+/// Converts the disabled ranking rules of a search query into the built-in ranking rules they name.
+fn parse_disabled_ranking_rules(rules: &[String]) -> Result<Vec<Criterion>, MeilisearchHttpError> {
+    rules
+        .iter()
+        .map(|rule| match Criterion::from_str(rule) {
+            Ok(Criterion::Asc(_) | Criterion::Desc(_)) | Err(_) => {
+                Err(MeilisearchHttpError::InvalidSearchDisabledRankingRule(rule.clone()))
+            }
+            Ok(criterion) => Ok(criterion),
+        })
+        .collect()
 }
 
 fn prepare_search<'t>(
@@ -346,7 +514,7 @@ fn prepare_search<'t>(
         search.vector(vector.clone());
     }
 
-    if let Some(ref query) = query.q {
+    if let Some(query) = query.q.as_deref().and_then(normalize_query) {
         search.query(query);
     }
 
@@ -369,6 +537,9 @@ fn prepare_search<'t>(
         ScoringStrategy::Skip
     });
 
+    search.ranking_stages(query.show_ranking_stages);
+    search.processed_query(query.show_processed_query);
+
     if query.show_ranking_score_details {
         features.check_score_details()?;
     }
@@ -396,10 +567,24 @@ fn prepare_search<'t>(
     search.offset(offset);
     search.limit(limit);
 
-    if let Some(ref filter) = query.filter {
-        if let Some(facets) = parse_filter(filter)? {
-            search.filter(facets);
-        }
+    let filter = query.filter.as_ref().map(parse_filter).transpose()?.flatten();
+    apply_filter(&mut search, query, filter)?;
+
+    if let Some(ref within_ids) = query.within_ids {
+        let external_documents_ids = index.external_documents_ids(rtxn)?;
+        let candidates = within_ids
+            .iter()
+            .filter_map(|external_id| external_documents_ids.get(external_id))
+            .collect();
+        search.candidates(candidates);
+    }
+
+    if let Some(ref locales) = query.locales {
+        search.locales(parse_locales(locales)?);
+    }
+
+    if let Some(ref rules) = query.disabled_ranking_rules {
+        search.disabled_criteria(parse_disabled_ranking_rules(rules)?);
     }
 
     if let Some(ref sort) = query.sort {
@@ -416,24 +601,106 @@ fn prepare_search<'t>(
     Ok((search, is_finite_pagination, max_total_hits, offset))
 }
 
+/// Restricts the search to the documents matching the filter and the filter of the search rules,
+/// the latter always applies whatever the filter given.
+fn apply_filter<'a>(
+    search: &mut milli::Search<'a>,
+    query: &'a SearchQuery,
+    filter: Option<Filter<'a>>,
+) -> Result<(), MeilisearchHttpError> {
+    let rules_filter = query.rules_filter.as_ref().map(parse_filter).transpose()?.flatten();
+    let filter = match (filter, rules_filter) {
+        (Some(filter), Some(rules_filter)) => {
+            Filter::from(FilterCondition::And(vec![filter.into(), rules_filter.into()]))
+        }
+        (Some(filter), None) | (None, Some(filter)) => filter,
+        (None, None) => return Ok(()),
+    };
+    search.filter(filter);
+    Ok(())
+}
+
 pub fn perform_search(
     index: &Index,
     query: SearchQuery,
     features: RoFeatures,
+    cipher: Option<FieldCipher>,
+    max_facets: Option<usize>,
 ) -> Result<SearchResult, MeilisearchHttpError> {
     let before_search = Instant::now();
     let rtxn = index.read_txn()?;
 
-    let (search, is_finite_pagination, max_total_hits, offset) =
-        prepare_search(index, &rtxn, &query, features)?;
+    if let (Some(facets), Some(max_facets)) = (&query.facets, max_facets) {
+        // the wildcard requests the distribution of every filterable attribute.
+        let facets_count = if facets.iter().any(|facet| facet == "*") {
+            index.filterable_fields(&rtxn)?.len()
+        } else {
+            facets.len()
+        };
+        if facets_count > max_facets {
+            return Err(MeilisearchHttpError::TooManySearchFacets(facets_count, max_facets));
+        }
+    }
 
-    let milli::SearchResult { documents_ids, matching_words, candidates, document_scores, .. } =
-        search.execute()?;
+    let mut result = perform_search_in_rtxn(index, &rtxn, query, None, features, cipher.as_ref())?;
+    result.processing_time_ms = before_search.elapsed().as_millis() as u64;
+    Ok(result)
+}
+
+/// Performs the search within the given read transaction,
+/// the `filter`, when given, is applied in place of the filter of the query.
+fn perform_search_in_rtxn(
+    index: &Index,
+    rtxn: &RoTxn,
+    query: SearchQuery,
+    filter: Option<Filter>,
+    features: RoFeatures,
+    cipher: Option<&FieldCipher>,
+) -> Result<SearchResult, MeilisearchHttpError> {
+    let before_search = Instant::now();
+    let (mut search, is_finite_pagination, max_total_hits, offset) =
+        prepare_search(index, rtxn, &query, features)?;
+    if filter.is_some() {
+        apply_filter(&mut search, &query, filter)?;
+    }
+
+    let milli::SearchResult {
+        documents_ids,
+        matching_words,
+        candidates,
+        document_scores,
+        ranking_stages,
+        processed_query,
+    } = search.execute()?;
+
+    let groups = match &query.group_by {
+        Some(group_by) => {
+            if !index.filterable_fields(rtxn)?.contains(group_by) {
+                return Err(MeilisearchHttpError::InvalidSearchGroupBy(group_by.to_string()));
+            }
+            let max_values_by_facet = index
+                .max_values_per_facet(rtxn)
+                .map_err(milli::Error::from)?
+                .unwrap_or(DEFAULT_VALUES_PER_FACET);
+            // the groups are the values of the attribute among the candidates, most populated first.
+            let max_groups = query.max_groups.unwrap_or(query.limit);
+            let values = index
+                .facets_distribution(rtxn)
+                .facets(vec![(group_by, OrderBy::Count)])
+                .candidates(candidates.clone())
+                .max_values_per_facet(min(max_groups, max_values_by_facet))
+                .execute()?
+                .remove(group_by)
+                .unwrap_or_default();
+            Some(perform_group_search(index, rtxn, &query, group_by, values, features, cipher)?)
+        }
+        None => None,
+    };
 
-    let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+    let fields_ids_map = index.fields_ids_map(rtxn).unwrap();
 
     let displayed_ids = index
-        .displayed_fields_ids(&rtxn)?
+        .displayed_fields_ids(rtxn)?
         .map(|fields| fields.into_iter().collect::<BTreeSet<_>>())
         .unwrap_or_else(|| fields_ids_map.iter().map(|(id, _)| id).collect());
 
@@ -486,36 +753,50 @@ pub fn perform_search(
     let mut tokenizer_builder = TokenizerBuilder::default();
     tokenizer_builder.create_char_map(true);
 
-    let script_lang_map = index.script_language(&rtxn)?;
+    let mut script_lang_map = index.script_language(rtxn)?;
+    let locales = match query.locales {
+        Some(ref locales) => Some(parse_locales(locales)?),
+        None => index.languages(rtxn)?,
+    };
+    if let Some(locales) = locales {
+        script_lang_map = milli::restrict_script_language(script_lang_map, &locales);
+    }
     if !script_lang_map.is_empty() {
         tokenizer_builder.allow_list(&script_lang_map);
     }
 
-    let separators = index.allowed_separators(&rtxn)?;
+    let separators = index.allowed_separators(rtxn)?;
     let separators: Option<Vec<_>> =
         separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
     if let Some(ref separators) = separators {
         tokenizer_builder.separators(separators);
     }
 
-    let dictionary = index.dictionary(&rtxn)?;
+    let dictionary = index.dictionary(rtxn)?;
     let dictionary: Option<Vec<_>> =
         dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
     if let Some(ref dictionary) = dictionary {
         tokenizer_builder.words_dict(dictionary);
     }
 
+    let case_sensitive = index.case_sensitive(rtxn)?;
+    tokenizer_builder.create_char_map(case_sensitive);
+
     let mut formatter_builder = MatcherBuilder::new(matching_words, tokenizer_builder.build());
+    formatter_builder.case_sensitive(case_sensitive);
     formatter_builder.crop_marker(query.crop_marker);
     formatter_builder.highlight_prefix(query.highlight_pre_tag);
     formatter_builder.highlight_suffix(query.highlight_post_tag);
 
+    let encrypted_fields = index.encrypted_fields(rtxn)?;
+
     let mut documents = Vec::new();
-    let documents_iter = index.documents(&rtxn, documents_ids)?;
+    let documents_iter = index.documents(rtxn, documents_ids)?;
 
     for ((_id, obkv), score) in documents_iter.into_iter().zip(document_scores.into_iter()) {
         // First generate a document with all the displayed fields
-        let displayed_document = make_document(&displayed_ids, &fields_ids_map, obkv)?;
+        let displayed_document =
+            make_document(&displayed_ids, &fields_ids_map, obkv, &encrypted_fields, cipher)?;
 
         // select the attributes to retrieve
         let attributes_to_retrieve = to_retrieve_ids
@@ -529,10 +810,16 @@ pub fn perform_search(
             &fields_ids_map,
             &formatter_builder,
             &formatted_options,
-            query.show_matches_position,
+            query.show_matches_position || query.show_match_count,
             &displayed_ids,
         )?;
 
+        // the match count is the number of query term occurrences in all the matched attributes
+        let match_count = query
+            .show_match_count
+            .then(|| matches_position.iter().flatten().map(|(_, matches)| matches.len()).sum());
+        let matches_position = matches_position.filter(|_| query.show_matches_position);
+
         if let Some(sort) = query.sort.as_ref() {
             insert_geo_distance(sort, &mut document);
         }
@@ -554,6 +841,7 @@ pub fn perform_search(
             document,
             formatted,
             matches_position,
+            match_count,
             ranking_score_details,
             ranking_score,
             semantic_score,
@@ -581,16 +869,25 @@ pub fn perform_search(
 
     let (facet_distribution, facet_stats) = match query.facets {
         Some(ref fields) => {
-            let mut facet_distribution = index.facets_distribution(&rtxn);
+            let mut facet_distribution = index.facets_distribution(rtxn);
 
             let max_values_by_facet = index
-                .max_values_per_facet(&rtxn)
+                .max_values_per_facet(rtxn)
                 .map_err(milli::Error::from)?
                 .unwrap_or(DEFAULT_VALUES_PER_FACET);
-            facet_distribution.max_values_per_facet(max_values_by_facet);
+            // The values in the reverse of the natural direction are the last ones milli returns,
+            // so all of them are fetched before being reversed and truncated.
+            let fetched_values_by_facet = match query.sort_facet_values_direction {
+                Some(_) => usize::MAX,
+                None => max_values_by_facet,
+            };
+            facet_distribution.max_values_per_facet(fetched_values_by_facet);
 
-            let sort_facet_values_by =
-                index.sort_facet_values_by(&rtxn).map_err(milli::Error::from)?;
+            // An order requested in the query overrides the one of every facet of the index.
+            let sort_facet_values_by = match &query.sort_facet_values_by {
+                Some(sort) => HashMap::from([("*".to_string(), sort.clone().into())]),
+                None => index.sort_facet_values_by(rtxn).map_err(milli::Error::from)?,
+            };
             let default_sort_facet_values_by =
                 sort_facet_values_by.get("*").copied().unwrap_or_default();
 
@@ -609,11 +906,69 @@ pub fn perform_search(
                     .collect();
                 facet_distribution.facets(fields);
             }
-            let distribution = facet_distribution
+            let mut distribution = facet_distribution
                 .candidates(candidates)
                 .default_order_by(default_sort_facet_values_by)
                 .execute()?;
             let stats = facet_distribution.compute_stats()?;
+
+            // With disjunctive facets, the distribution of a facet ignores the filters applied
+            // on itself, while the filters applied on the other facets still narrow it.
+            if query.disjunctive_facets {
+                if let Some(filter) = query.filter.as_ref().map(parse_filter).transpose()?.flatten()
+                {
+                    let unfiltered_query = SearchQuery {
+                        filter: None,
+                        offset: 0,
+                        limit: 0,
+                        page: None,
+                        hits_per_page: None,
+                        ..query.clone()
+                    };
+                    for (facet, values) in distribution.iter_mut() {
+                        let relaxed_filter = filter.without_attribute(facet);
+                        if relaxed_filter.as_ref() == Some(&filter) {
+                            continue;
+                        }
+
+                        let (mut search, ..) =
+                            prepare_search(index, rtxn, &unfiltered_query, features)?;
+                        if relaxed_filter.is_some() {
+                            apply_filter(&mut search, &unfiltered_query, relaxed_filter)?;
+                        }
+                        let relaxed_candidates = search.execute()?.candidates;
+
+                        let sort_by = sort_facet_values_by
+                            .get(facet)
+                            .copied()
+                            .unwrap_or(default_sort_facet_values_by);
+                        let mut facet_distribution = index.facets_distribution(rtxn);
+                        facet_distribution.max_values_per_facet(fetched_values_by_facet);
+                        if let Some(relaxed_values) = facet_distribution
+                            .facets(vec![(facet.as_str(), sort_by)])
+                            .candidates(relaxed_candidates)
+                            .default_order_by(default_sort_facet_values_by)
+                            .execute()?
+                            .remove(facet)
+                        {
+                            *values = relaxed_values;
+                        }
+                    }
+                }
+            }
+
+            if let Some(direction) = query.sort_facet_values_direction {
+                for (facet, values) in distribution.iter_mut() {
+                    let sort_by = sort_facet_values_by
+                        .get(facet)
+                        .copied()
+                        .unwrap_or(default_sort_facet_values_by);
+                    if direction != FacetValuesDirection::natural(sort_by) {
+                        values.reverse();
+                    }
+                    values.truncate(max_values_by_facet);
+                }
+            }
             (Some(distribution), Some(stats))
         }
         None => (None, None),
@@ -628,13 +983,82 @@ pub fn perform_search(
         hits_info,
         query: query.q.unwrap_or_default(),
         vector: query.vector,
-        processing_time_ms: before_search.elapsed().as_millis(),
+        processing_time_ms: before_search.elapsed().as_millis() as u64,
+        groups,
         facet_distribution,
         facet_stats,
+        ranking_stages: ranking_stages.map(|stages| {
+            stages
+                .into_iter()
+                .map(|milli::RankingStage { ranking_rule, candidates }| RankingStage {
+                    ranking_rule,
+                    candidates,
+                })
+                .collect()
+        }),
+        processed_query: processed_query.map(|terms| {
+            terms
+                .into_iter()
+                .map(|milli::ProcessedQueryTerm { term, synonyms }| ProcessedQueryTerm {
+                    term,
+                    synonyms,
+                })
+                .collect()
+        }),
     };
     Ok(result)
 }
 
+/// Returns the `group_limit` best hits of the search restricted to each value of the
+/// `group_by` attribute, along with the number of candidates having this value.
+/// Like any search, a group can't contain more hits than the `maxTotalHits` of the index.
+fn perform_group_search(
+    index: &Index,
+    rtxn: &RoTxn,
+    query: &SearchQuery,
+    group_by: &str,
+    values: IndexMap<String, u64>,
+    features: RoFeatures,
+    cipher: Option<&FieldCipher>,
+) -> Result<Vec<HitsGroup>, MeilisearchHttpError> {
+    let filter = query.filter.as_ref().map(parse_filter).transpose()?.flatten();
+    let group_query = SearchQuery {
+        offset: 0,
+        limit: query.group_limit,
+        page: None,
+        hits_per_page: None,
+        filter: None,
+        facets: None,
+        group_by: None,
+        ..query.clone()
+    };
+
+    let mut groups = Vec::with_capacity(values.len());
+    for (value, total_hits) in values {
+        // Each group is searched with the same query, further restricted to its own value.
+        let group_condition = FilterCondition::Condition {
+            fid: Token::from(group_by),
+            op: Condition::Equal(Token::from(value.as_str())),
+        };
+        let group_filter = match filter.clone() {
+            Some(filter) => FilterCondition::And(vec![filter.into(), group_condition]),
+            None => group_condition,
+        };
+        let hits = perform_search_in_rtxn(
+            index,
+            rtxn,
+            group_query.clone(),
+            Some(group_filter.into()),
+            features,
+            cipher,
+        )?
+        .hits;
+        groups.push(HitsGroup { value, hits, total_hits });
+    }
+
+    Ok(groups)
+}
+
 pub fn perform_facet_search(
     index: &Index,
     search_query: SearchQuery,
@@ -658,6 +1082,32 @@ pub fn perform_facet_search(
     })
 }
 
+pub fn perform_suggest(
+    index: &Index,
+    prefix: String,
+    limit: usize,
+    filter: Option<Value>,
+) -> Result<SuggestResult, MeilisearchHttpError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let candidates = match filter.as_ref().map(parse_filter).transpose()?.flatten() {
+        Some(filter) => filter.evaluate(&rtxn, index)?,
+        None => index.documents_ids(&rtxn)?,
+    };
+    let suggestions = index
+        .word_suggestions(&rtxn, &prefix, &candidates, limit)?
+        .into_iter()
+        .map(|(term, count)| Suggestion { term, count })
+        .collect();
+
+    Ok(SuggestResult {
+        suggestions,
+        prefix,
+        processing_time_ms: before_search.elapsed().as_millis(),
+    })
+}
+
 fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     lazy_static::lazy_static! {
         static ref GEO_REGEX: Regex =
@@ -793,6 +1243,8 @@ fn make_document(
     displayed_attributes: &BTreeSet<FieldId>,
     field_ids_map: &FieldsIdsMap,
     obkv: obkv::KvReaderU16,
+    encrypted_fields: &BTreeSet<String>,
+    cipher: Option<&FieldCipher>,
 ) -> Result<Document, MeilisearchHttpError> {
     let mut document = serde_json::Map::new();
 
@@ -803,6 +1255,7 @@ fn make_document(
 
         document.insert(key, value);
     }
+    decrypt_document(cipher, encrypted_fields, &mut document)?;
 
     // select the attributes to retrieve
     let displayed_attributes = displayed_attributes
@@ -1,6 +1,10 @@
 use std::{thread, time};
 
-use crate::common::{Server, Value};
+use byte_unit::{Byte, ByteUnit};
+use meilisearch::Opt;
+use tempfile::TempDir;
+
+use crate::common::{default_settings, Server, Value};
 use crate::json;
 
 #[actix_rt::test]
@@ -547,6 +551,30 @@ async fn error_add_api_key_parameters_uid_already_exist() {
     meili_snap::snapshot!(code, @"409 Conflict");
 }
 
+#[actix_rt::test]
+async fn api_keys_with_custom_auth_store_size() {
+    let dir = TempDir::new().unwrap();
+    let options = Opt {
+        max_auth_store_size: Byte::from_unit(10.0, ByteUnit::MiB).unwrap(),
+        ..default_settings(dir.path())
+    };
+    let mut server = Server::new_auth_with_options(options, dir).await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "uid": "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+        "indexes": ["products"],
+        "actions": ["search"],
+        "expiresAt": null,
+    });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", &response);
+
+    let (response, code) = server.get_api_key("4bc0887a-0e41-4f3b-935d-0c451dcee9c8").await;
+    assert_eq!(200, code, "{:?}", &response);
+    assert_eq!(response["actions"], json!(["search"]));
+}
+
 #[actix_rt::test]
 async fn get_api_key() {
     let mut server = Server::new_auth().await;
@@ -693,6 +721,59 @@ async fn error_get_api_key_no_header() {
     meili_snap::snapshot!(code, @"401 Unauthorized");
 }
 
+#[actix_rt::test]
+async fn get_api_key_last_used_at() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "uid": "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+        "indexes": ["*"],
+        "actions": ["stats.get"],
+        "expiresAt": null,
+    });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", &response);
+    let key = response["key"].as_str().unwrap().to_string();
+
+    // a key that never authorized a request has no last use date
+    let (response, code) = server.get_api_key("4bc0887a-0e41-4f3b-935d-0c451dcee9c8").await;
+    assert_eq!(200, code, "{:?}", &response);
+    assert!(response.get("lastUsedAt").is_none(), "{response}");
+
+    server.use_api_key(&key);
+    let (response, code) = server.stats().await;
+    assert_eq!(200, code, "{:?}", &response);
+
+    // the use is recorded in the background
+    server.use_api_key("MASTER_KEY");
+    let mut last_used_at = serde_json::Value::Null;
+    for _ in 0..100 {
+        let (response, code) = server.get_api_key("4bc0887a-0e41-4f3b-935d-0c451dcee9c8").await;
+        assert_eq!(200, code, "{:?}", &response);
+        last_used_at = response["lastUsedAt"].clone();
+        if last_used_at.is_string() {
+            break;
+        }
+        thread::sleep(time::Duration::from_millis(10));
+    }
+    assert!(last_used_at.is_string(), "{last_used_at}");
+
+    // the last use date is only updated once per minute
+    server.use_api_key(&key);
+    server.stats().await;
+    server.use_api_key("MASTER_KEY");
+    let (response, _) = server.get_api_key("4bc0887a-0e41-4f3b-935d-0c451dcee9c8").await;
+    assert_eq!(response["lastUsedAt"], last_used_at);
+
+    // updating the key keeps its last use date
+    let (response, code) = server
+        .patch_api_key("4bc0887a-0e41-4f3b-935d-0c451dcee9c8", json!({ "name": "stats-key" }))
+        .await;
+    assert_eq!(200, code, "{:?}", &response);
+    assert_eq!(response["lastUsedAt"], last_used_at);
+}
+
 #[actix_rt::test]
 async fn error_get_api_key_bad_key() {
     let mut server = Server::new_auth().await;
@@ -859,6 +940,248 @@ async fn list_api_keys() {
     meili_snap::snapshot!(code, @"200 OK");
 }
 
+#[actix_rt::test]
+async fn list_api_keys_paginated() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    for i in 0..100 {
+        let content = json!({
+            "name": format!("key {i}"),
+            "indexes": ["products"],
+            "actions": ["search"],
+            "expiresAt": null,
+        });
+        let (response, code) = server.add_api_key(content).await;
+        assert_eq!(201, code, "{:?}", &response);
+    }
+
+    // the 100 keys and the two default keys
+    let mut uids = std::collections::HashSet::new();
+    for offset in (0..102).step_by(30) {
+        let (response, code) = server.list_api_keys(&format!("?offset={offset}&limit=30")).await;
+        assert_eq!(200, code, "{:?}", &response);
+        assert_eq!(response["offset"], offset);
+        assert_eq!(response["limit"], 30);
+        assert_eq!(response["total"], 102);
+
+        let results = response["results"].as_array().unwrap();
+        assert_eq!(results.len(), (102 - offset).min(30));
+        for key in results {
+            assert!(uids.insert(key["uid"].as_str().unwrap().to_string()));
+        }
+    }
+    assert_eq!(uids.len(), 102);
+}
+
+#[actix_rt::test]
+async fn list_api_keys_searched_by_description() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    for (name, description) in [
+        ("ingestion-staging", Some("The ingestion key for Staging")),
+        ("ingestion-production", Some("The ingestion key for production")),
+        ("search-staging", Some("The STAGING search key")),
+        ("no-description", None),
+    ] {
+        let content = json!({
+            "name": name,
+            "description": description,
+            "indexes": ["products"],
+            "actions": ["search"],
+            "expiresAt": null,
+        });
+        let (response, code) = server.add_api_key(content).await;
+        assert_eq!(201, code, "{:?}", &response);
+    }
+
+    let (response, code) = server.list_api_keys("?search=staging").await;
+    assert_eq!(200, code, "{:?}", &response);
+    assert_eq!(response["total"], 2);
+    let mut names: Vec<_> = response["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|key| key["name"].as_str().unwrap())
+        .collect();
+    names.sort_unstable();
+    assert_eq!(names, ["ingestion-staging", "search-staging"]);
+
+    let (response, code) = server.list_api_keys("?search=ingestion%20key").await;
+    assert_eq!(200, code, "{:?}", &response);
+    assert_eq!(response["total"], 2);
+
+    let (response, code) = server.list_api_keys("?search=doggo").await;
+    assert_eq!(200, code, "{:?}", &response);
+    assert_eq!(response["total"], 0);
+    assert_eq!(response["results"], json!([]));
+}
+
+#[actix_rt::test]
+async fn list_api_keys_filtered_by_expiration() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let in_a_second = ::time::OffsetDateTime::now_utc() + ::time::Duration::seconds(1);
+    let in_an_hour = ::time::OffsetDateTime::now_utc() + ::time::Duration::hours(1);
+    let rfc3339 = ::time::format_description::well_known::Rfc3339;
+    for (name, expires_at) in [
+        ("short-lived", Some(in_a_second.format(&rfc3339).unwrap())),
+        ("long-lived", Some(in_an_hour.format(&rfc3339).unwrap())),
+        ("immortal", None),
+    ] {
+        let content = json!({
+            "name": name,
+            "indexes": ["products"],
+            "actions": ["search"],
+            "expiresAt": expires_at,
+        });
+        let (response, code) = server.add_api_key(content).await;
+        assert_eq!(201, code, "{:?}", &response);
+    }
+
+    // wait until the short-lived key is expired.
+    thread::sleep(time::Duration::new(1, 0));
+
+    let (response, code) = server.list_api_keys("?expired=true").await;
+    assert_eq!(200, code, "{:?}", &response);
+    assert_eq!(response["total"], 1);
+    assert_eq!(response["results"][0]["name"], "short-lived");
+
+    // the two default keys never expire
+    let (response, code) = server.list_api_keys("?expired=false").await;
+    assert_eq!(200, code, "{:?}", &response);
+    assert_eq!(response["total"], 4);
+    let names: Vec<_> = response["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|key| key["name"].as_str().unwrap())
+        .collect();
+    assert!(!names.contains(&"short-lived"), "{names:?}");
+
+    let (response, code) = server.list_api_keys("?expired=doggo").await;
+    assert_eq!(400, code, "{:?}", &response);
+    assert_eq!(response["code"], "invalid_api_key_expired_filter");
+}
+
+#[actix_rt::test]
+async fn purge_expired_api_keys() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let in_a_second = ::time::OffsetDateTime::now_utc() + ::time::Duration::seconds(1);
+    let in_an_hour = ::time::OffsetDateTime::now_utc() + ::time::Duration::hours(1);
+    let rfc3339 = ::time::format_description::well_known::Rfc3339;
+    let mut uids = Vec::new();
+    for (name, expires_at) in [
+        ("short-lived", Some(in_a_second.format(&rfc3339).unwrap())),
+        ("long-lived", Some(in_an_hour.format(&rfc3339).unwrap())),
+        ("immortal", None),
+    ] {
+        let content = json!({
+            "name": name,
+            "indexes": ["products"],
+            "actions": ["search"],
+            "expiresAt": expires_at,
+        });
+        let (response, code) = server.add_api_key(content).await;
+        assert_eq!(201, code, "{:?}", &response);
+        uids.push(response["uid"].as_str().unwrap().to_string());
+    }
+
+    // wait until the short-lived key is expired.
+    thread::sleep(time::Duration::new(1, 0));
+
+    let (response, code) = server.purge_expired_api_keys().await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "deletedKeys": 1
+    }
+    "###);
+
+    let (response, code) = server.get_api_key(&uids[0]).await;
+    assert_eq!(404, code, "{:?}", &response);
+    for uid in &uids[1..] {
+        let (response, code) = server.get_api_key(uid).await;
+        assert_eq!(200, code, "{:?}", &response);
+    }
+
+    // the two default keys never expire
+    let (response, code) = server.list_api_keys("").await;
+    assert_eq!(200, code, "{:?}", &response);
+    assert_eq!(response["total"], 4);
+
+    // nothing is left to purge
+    let (response, code) = server.purge_expired_api_keys().await;
+    assert_eq!(200, code, "{:?}", &response);
+    assert_eq!(response["deletedKeys"], 0);
+}
+
+#[actix_rt::test]
+async fn list_api_keys_with_a_key_restricted_to_some_indexes() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let keys = [
+        ("tenant", json!(["products", "sales_*"])),
+        ("products", json!(["products"])),
+        ("sales", json!(["sales_2023"])),
+        ("products and orders", json!(["products", "orders"])),
+        ("all indexes", json!(["*"])),
+        ("no index", json!([])),
+    ];
+    for (description, indexes) in keys {
+        let content = json!({
+            "description": description,
+            "indexes": indexes,
+            "actions": ["keys.get"],
+            "expiresAt": null,
+        });
+        let (response, code) = server.add_api_key(content).await;
+        assert_eq!(code, 201, "{}", response);
+    }
+
+    let (response, code) = server.list_api_keys("").await;
+    assert_eq!(code, 200, "{}", response);
+    // the two default keys are also listed with the master key
+    assert_eq!(response["total"], json!(8));
+
+    let key_of = |description: &str| {
+        response["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|key| key["description"] == description)
+            .map(|key| key["key"].as_str().unwrap().to_string())
+            .unwrap()
+    };
+    let products_key = key_of("products");
+    let orders_key = key_of("products and orders");
+    server.use_api_key(key_of("tenant"));
+
+    let (response, code) = server.list_api_keys("").await;
+    assert_eq!(code, 200, "{}", response);
+    let mut descriptions: Vec<_> = response["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|key| key["description"].as_str().unwrap().to_string())
+        .collect();
+    descriptions.sort();
+    meili_snap::snapshot!(format!("{descriptions:?}"), @r###"["products", "sales", "tenant"]"###);
+    meili_snap::snapshot!(response["total"], @"3");
+
+    // the same filter applies when fetching a single key
+    let (response, code) = server.get_api_key(&products_key).await;
+    assert_eq!(code, 200, "{}", response);
+    let (response, code) = server.get_api_key(&orders_key).await;
+    assert_eq!(code, 404, "{}", response);
+    assert_eq!(response["code"], "api_key_not_found", "{}", response);
+}
+
 #[actix_rt::test]
 async fn error_list_api_keys_no_header() {
     let server = Server::new_auth().await;
@@ -967,6 +1290,43 @@ async fn delete_api_key() {
     }
     "###);
     meili_snap::snapshot!(code, @"404 Not Found");
+
+    // deleting it a second time doesn't delete anything.
+    let (response, code) = server.delete_api_key(&uid).await;
+    meili_snap::snapshot!(response["code"], @r###""api_key_not_found""###);
+    meili_snap::snapshot!(code, @"404 Not Found");
+}
+
+#[actix_rt::test]
+async fn delete_api_keys() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({ "indexes": ["products"], "actions": ["search"], "expiresAt": null });
+    let (first, code) = server.add_api_key(content.clone()).await;
+    meili_snap::snapshot!(code, @"201 Created");
+    let (second, code) = server.add_api_key(content).await;
+    meili_snap::snapshot!(code, @"201 Created");
+
+    let first = first["uid"].as_str().unwrap();
+    // keys can be identified either by their uid or by the key itself.
+    let second = second["key"].as_str().unwrap();
+    let missing = "d0552b41-536b-4279-a0ad-88bd595327b9";
+
+    let (response, code) = server.delete_api_keys(json!([first, missing, second])).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    let deleted: Vec<_> = response
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| (result["key"].as_str().unwrap(), result["deleted"].as_bool().unwrap()))
+        .collect();
+    assert_eq!(deleted, [(first, true), (missing, false), (second, true)]);
+
+    let (_response, code) = server.get_api_key(first).await;
+    meili_snap::snapshot!(code, @"404 Not Found");
+    let (_response, code) = server.get_api_key(second).await;
+    meili_snap::snapshot!(code, @"404 Not Found");
 }
 
 #[actix_rt::test]
@@ -1026,6 +1386,31 @@ async fn error_delete_api_key_not_found() {
     meili_snap::snapshot!(code, @"404 Not Found");
 }
 
+#[actix_rt::test]
+async fn error_api_key_routes_short_key() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server.delete_api_key("abc").await;
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "API key `abc` not found.",
+      "code": "api_key_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#api_key_not_found"
+    }
+    "###);
+    meili_snap::snapshot!(code, @"404 Not Found");
+
+    let (response, code) = server.get_api_key("abc").await;
+    meili_snap::snapshot!(response["code"], @r###""api_key_not_found""###);
+    meili_snap::snapshot!(code, @"404 Not Found");
+
+    let (response, code) = server.patch_api_key("abc", json!({})).await;
+    meili_snap::snapshot!(response["code"], @r###""api_key_not_found""###);
+    meili_snap::snapshot!(code, @"404 Not Found");
+}
+
 #[actix_rt::test]
 async fn patch_api_key_description() {
     let mut server = Server::new_auth().await;
@@ -1393,7 +1778,7 @@ async fn error_patch_api_key_indexes() {
     let (response, code) = server.patch_api_key(&uid, content).await;
     meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
     {
-      "message": "Immutable field `indexes`: expected one of `description`, `name`",
+      "message": "Immutable field `indexes`: expected one of `description`, `name`, `expiresAt`",
       "code": "immutable_api_key_indexes",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_indexes"
@@ -1470,7 +1855,7 @@ async fn error_patch_api_key_actions() {
     let (response, code) = server.patch_api_key(&uid, content).await;
     meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
     {
-      "message": "Immutable field `actions`: expected one of `description`, `name`",
+      "message": "Immutable field `actions`: expected one of `description`, `name`, `expiresAt`",
       "code": "immutable_api_key_actions",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_actions"
@@ -1480,7 +1865,59 @@ async fn error_patch_api_key_actions() {
 }
 
 #[actix_rt::test]
-async fn error_patch_api_key_expiration_date() {
+async fn error_patch_api_key_does_not_widen_its_scope() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "description": "Search API key",
+        "indexes": ["products"],
+        "actions": ["search"],
+        "expiresAt": null
+    });
+    let (response, code) = server.add_api_key(content).await;
+    meili_snap::snapshot!(code, @"201 Created");
+    let uid = response["uid"].as_str().unwrap();
+
+    // the whole patch is rejected, including its mutable fields
+    let content = json!({
+        "description": "Admin API key",
+        "indexes": ["*"],
+        "actions": ["*"],
+    });
+    let (response, code) = server.patch_api_key(&uid, content).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response["code"], @r###""immutable_api_key_indexes""###);
+
+    let (response, code) = server.get_api_key(&uid).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]", ".uid" => "[ignored]", ".key" => "[ignored]" }), @r###"
+    {
+      "name": null,
+      "description": "Search API key",
+      "key": "[ignored]",
+      "uid": "[ignored]",
+      "actions": [
+        "search"
+      ],
+      "indexes": [
+        "products"
+      ],
+      "expiresAt": null,
+      "createdAt": "[ignored]",
+      "updatedAt": "[ignored]"
+    }
+    "###);
+
+    let (response, code) =
+        server.patch_api_key(&uid, json!({ "description": "Products search key" })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["description"], @r###""Products search key""###);
+    meili_snap::snapshot!(response["actions"], @r###"["search"]"###);
+}
+
+#[actix_rt::test]
+async fn patch_api_key_expiration_date() {
     let mut server = Server::new_auth().await;
     server.use_api_key("MASTER_KEY");
 
@@ -1537,15 +1974,30 @@ async fn error_patch_api_key_expiration_date() {
 
     thread::sleep(time::Duration::new(1, 0));
     let (response, code) = server.patch_api_key(&uid, content).await;
-    meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["expiresAt"], @r###""2055-11-13T00:00:00Z""###);
+    meili_snap::snapshot!(response["actions"].as_array().unwrap().len(), @"10");
+
+    let (response, code) = server.get_api_key(&uid).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["expiresAt"], @r###""2055-11-13T00:00:00Z""###);
+
+    // a null expiration date removes the expiration
+    let (response, code) = server.patch_api_key(&uid, json!({ "expiresAt": null })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["expiresAt"], @"null");
+
+    let (response, code) =
+        server.patch_api_key(&uid, json!({ "expiresAt": "2010-11-13T00:00:00Z" })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
     {
-      "message": "Immutable field `expiresAt`: expected one of `description`, `name`",
-      "code": "immutable_api_key_expires_at",
+      "message": "Invalid value at `.expiresAt`: `2010-11-13T00:00:00Z` is not a valid date. It should follow the RFC 3339 format to represents a date or datetime in the future or specified as a null value. e.g. 'YYYY-MM-DD' or 'YYYY-MM-DD HH:MM:SS'.\n",
+      "code": "invalid_api_key_expires_at",
       "type": "invalid_request",
-      "link": "https://docs.meilisearch.com/errors#immutable_api_key_expires_at"
+      "link": "https://docs.meilisearch.com/errors#invalid_api_key_expires_at"
     }
     "###);
-    meili_snap::snapshot!(code, @"400 Bad Request");
 }
 
 #[actix_rt::test]
@@ -1733,6 +2185,14 @@ async fn error_access_api_key_routes_no_master_key_set() {
     "###);
     meili_snap::snapshot!(code, @"401 Unauthorized");
 
+    let (response, code) = server.delete_api_key("content").await;
+    meili_snap::snapshot!(code, @"401 Unauthorized");
+    meili_snap::snapshot!(response["code"], @r###""missing_master_key""###);
+
+    let (response, code) = server.delete_api_keys(json!(["content"])).await;
+    meili_snap::snapshot!(code, @"401 Unauthorized");
+    meili_snap::snapshot!(response["code"], @r###""missing_master_key""###);
+
     server.use_api_key("MASTER_KEY");
 
     let (response, code) = server.add_api_key(json!({})).await;
@@ -14,6 +14,8 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("POST",    "/multi-search") =>                                    hashset!{"search", "*"},
             ("POST",    "/indexes/products/search") =>                         hashset!{"search", "*"},
             ("GET",     "/indexes/products/search") =>                         hashset!{"search", "*"},
+            ("GET",     "/indexes/products/suggest") =>                        hashset!{"search", "*"},
+            ("GET",     "/indexes/products/facets") =>                         hashset!{"search", "*"},
             ("POST",    "/indexes/products/documents") =>                      hashset!{"documents.add", "documents.*", "*"},
             ("GET",     "/indexes/products/documents") =>                      hashset!{"documents.get", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/fetch") =>                hashset!{"documents.get", "documents.*", "*"},
@@ -21,10 +23,13 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("DELETE",  "/indexes/products/documents/0") =>                    hashset!{"documents.delete", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/delete-batch") =>         hashset!{"documents.delete", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/delete") =>               hashset!{"documents.delete", "documents.*", "*"},
+            ("POST",    "/indexes/products/import") =>                         hashset!{"indexes.create", "indexes.*", "*"},
             ("GET",     "/tasks") =>                                           hashset!{"tasks.get", "tasks.*", "*"},
             ("DELETE",  "/tasks") =>                                           hashset!{"tasks.delete", "tasks.*", "*"},
             ("GET",     "/tasks?indexUid=products") =>                         hashset!{"tasks.get", "tasks.*", "*"},
             ("GET",     "/tasks/0") =>                                         hashset!{"tasks.get", "tasks.*", "*"},
+            ("POST",    "/tasks/fetch") =>                                     hashset!{"tasks.get", "tasks.*", "*"},
+            ("POST",    "/tasks/abort") =>                                     hashset!{"tasks.cancel", "tasks.*", "*"},
             ("PATCH",   "/indexes/products/") =>                               hashset!{"indexes.update", "indexes.*", "*"},
             ("GET",     "/indexes/products/") =>                               hashset!{"indexes.get", "indexes.*", "*"},
             ("DELETE",  "/indexes/products/") =>                               hashset!{"indexes.delete", "indexes.*", "*"},
@@ -42,6 +47,7 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("GET",     "/indexes/products/settings/synonyms") =>              hashset!{"settings.get", "settings.*", "*"},
             ("DELETE",  "/indexes/products/settings") =>                       hashset!{"settings.update", "settings.*", "*"},
             ("PATCH",   "/indexes/products/settings") =>                       hashset!{"settings.update", "settings.*", "*"},
+            ("POST",    "/indexes/products/settings/validate") =>              hashset!{"settings.get", "settings.*", "*"},
             ("PATCH",   "/indexes/products/settings/typo-tolerance") =>        hashset!{"settings.update", "settings.*", "*"},
             ("PUT",     "/indexes/products/settings/displayed-attributes") =>  hashset!{"settings.update", "settings.*", "*"},
             ("PUT",     "/indexes/products/settings/distinct-attribute") =>    hashset!{"settings.update", "settings.*", "*"},
@@ -52,14 +58,18 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("PUT",     "/indexes/products/settings/stop-words") =>            hashset!{"settings.update", "settings.*", "*"},
             ("PUT",     "/indexes/products/settings/synonyms") =>              hashset!{"settings.update", "settings.*", "*"},
             ("GET",     "/indexes/products/stats") =>                          hashset!{"stats.get", "stats.*", "*"},
+            ("GET",     "/indexes/products/lmdb-stats") =>                     hashset!{"stats.get", "stats.*", "*"},
             ("GET",     "/stats") =>                                           hashset!{"stats.get", "stats.*", "*"},
             ("POST",    "/dumps") =>                                           hashset!{"dumps.create", "dumps.*", "*"},
             ("POST",    "/snapshots") =>                                       hashset!{"snapshots.create", "snapshots.*", "*"},
             ("GET",     "/version") =>                                         hashset!{"version", "*"},
+            ("GET",     "/ranking-rules/available") =>                         hashset!{"settings.get", "settings.*", "*"},
             ("GET",     "/metrics") =>                                         hashset!{"metrics.get", "metrics.*", "*"},
             ("PATCH",   "/keys/mykey/") =>                                     hashset!{"keys.update", "*"},
             ("GET",     "/keys/mykey/") =>                                     hashset!{"keys.get", "*"},
             ("DELETE",  "/keys/mykey/") =>                                     hashset!{"keys.delete", "*"},
+            ("POST",    "/keys/delete-batch") =>                               hashset!{"keys.delete", "*"},
+            ("POST",    "/keys/purge-expired") =>                              hashset!{"keys.delete", "*"},
             ("POST",    "/keys") =>                                            hashset!{"keys.create", "*"},
             ("GET",     "/keys") =>                                            hashset!{"keys.get", "*"},
             ("GET",     "/experimental-features") =>                           hashset!{"experimental.get", "*"},
@@ -122,6 +132,69 @@ async fn error_access_expired_key() {
     }
 }
 
+#[actix_rt::test]
+async fn access_key_without_expiration() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key(MASTER_KEY);
+
+    let content = json!({
+        "indexes": ["*"],
+        "actions": ["*"],
+        "expiresAt": null,
+    });
+
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", &response);
+    assert!(response["key"].is_string());
+
+    let key = response["key"].as_str().unwrap();
+    server.use_api_key(key);
+
+    // a key without an expiration date never expires.
+    for (method, route) in AUTHORIZATIONS.keys() {
+        let (response, code) = server.dummy_request(method, route).await;
+
+        assert_ne!(response, INVALID_RESPONSE.clone(), "on route: {:?} - {:?}", method, route);
+        assert_ne!(code, 403);
+    }
+}
+
+#[actix_rt::test]
+async fn error_access_key_with_wrong_suffix() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key(MASTER_KEY);
+
+    let content = json!({
+        "indexes": ["*"],
+        "actions": ["*"],
+        "expiresAt": null,
+    });
+
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", &response);
+    let key = response["key"].as_str().unwrap().to_string();
+
+    // only the full key is accepted, a key sharing its prefix is not.
+    let last = if key.ends_with('0') { "1" } else { "0" };
+    let tampered = format!("{}{}", &key[..key.len() - 1], last);
+    let truncated = key[..8].to_string();
+
+    for wrong_key in [&tampered, &truncated] {
+        server.use_api_key(MASTER_KEY);
+        let (_response, code) = server.get_api_key(wrong_key).await;
+        assert_eq!(code, 404, "for key: {}", wrong_key);
+
+        server.use_api_key(wrong_key);
+        let (response, code) = server.dummy_request("GET", "/keys").await;
+        assert_eq!(response, INVALID_RESPONSE.clone(), "for key: {}", wrong_key);
+        assert_eq!(code, 403);
+    }
+
+    server.use_api_key(&key);
+    let (_response, code) = server.dummy_request("GET", "/keys").await;
+    assert_eq!(code, 200);
+}
+
 #[actix_rt::test]
 async fn error_access_unauthorized_index() {
     let mut server = Server::new_auth().await;
@@ -727,7 +800,7 @@ async fn error_creating_index_without_action() {
     server.use_api_key(key);
 
     let expected_error = json!({
-        "message": "Index `test` not found.",
+        "message": "Index `test` not found. The API key used is not allowed to create it, the index must be created first or the API key must be given the `indexes.create` action.",
         "code": "index_not_found",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#index_not_found"
@@ -743,34 +816,20 @@ async fn error_creating_index_without_action() {
     ]);
 
     let (response, code) = index.add_documents(documents, None).await;
-    assert_eq!(202, code, "{:?}", &response);
-    let task_id = response["taskUid"].as_u64().unwrap();
-
-    let response = index.wait_task(task_id).await;
-    assert_eq!(response["status"], "failed");
-    assert_eq!(response["error"], expected_error.clone());
+    assert_eq!(404, code, "{:?}", &response);
+    assert_eq!(response, expected_error.clone());
 
     // try to create a index via add settings route
     let settings = json!({ "distinctAttribute": "test"});
 
     let (response, code) = index.update_settings(settings).await;
-    assert_eq!(202, code, "{:?}", &response);
-    let task_id = response["taskUid"].as_u64().unwrap();
-
-    let response = index.wait_task(task_id).await;
-
-    assert_eq!(response["status"], "failed");
-    assert_eq!(response["error"], expected_error.clone());
+    assert_eq!(404, code, "{:?}", &response);
+    assert_eq!(response, expected_error.clone());
 
     // try to create a index via add specialized settings route
     let (response, code) = index.update_distinct_attribute(json!("test")).await;
-    assert_eq!(202, code, "{:?}", &response);
-    let task_id = response["taskUid"].as_u64().unwrap();
-
-    let response = index.wait_task(task_id).await;
-
-    assert_eq!(response["status"], "failed");
-    assert_eq!(response["error"], expected_error.clone());
+    assert_eq!(404, code, "{:?}", &response);
+    assert_eq!(response, expected_error.clone());
 }
 
 #[actix_rt::test]
@@ -1029,3 +1088,29 @@ async fn error_creating_index_without_index() {
     let (response, code) = index.create(None).await;
     assert_eq!(403, code, "{:?}", &response);
 }
+
+#[actix_rt::test]
+async fn export_requires_documents_and_settings_access() {
+    let mut server = Server::new_auth().await;
+
+    for (actions, expected_code) in [
+        (json!(["documents.get"]), 403),
+        (json!(["settings.get"]), 403),
+        (json!(["documents.get", "settings.get"]), 404),
+        (json!(["documents.*", "settings.*"]), 404),
+        (json!(["*"]), 404),
+    ] {
+        server.use_api_key(MASTER_KEY);
+        let content = json!({
+            "indexes": ["products"],
+            "actions": actions,
+            "expiresAt": (OffsetDateTime::now_utc() + Duration::hours(1)).format(&Rfc3339).unwrap(),
+        });
+        let (response, code) = server.add_api_key(content).await;
+        assert_eq!(201, code, "{:?}", &response);
+
+        server.use_api_key(response["key"].as_str().unwrap());
+        let (response, code) = server.dummy_request("GET", "/indexes/products/export").await;
+        assert_eq!(code, expected_code, "with actions {actions}: {response}");
+    }
+}
@@ -327,7 +327,7 @@ async fn patch_api_keys_immutable_uid() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Immutable field `uid`: expected one of `description`, `name`",
+      "message": "Immutable field `uid`: expected one of `description`, `name`, `expiresAt`",
       "code": "immutable_api_key_uid",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_uid"
@@ -344,7 +344,7 @@ async fn patch_api_keys_immutable_actions() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Immutable field `actions`: expected one of `description`, `name`",
+      "message": "Immutable field `actions`: expected one of `description`, `name`, `expiresAt`",
       "code": "immutable_api_key_actions",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_actions"
@@ -361,7 +361,7 @@ async fn patch_api_keys_immutable_indexes() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Immutable field `indexes`: expected one of `description`, `name`",
+      "message": "Immutable field `indexes`: expected one of `description`, `name`, `expiresAt`",
       "code": "immutable_api_key_indexes",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_indexes"
@@ -370,7 +370,7 @@ async fn patch_api_keys_immutable_indexes() {
 }
 
 #[actix_rt::test]
-async fn patch_api_keys_immutable_expires_at() {
+async fn patch_api_keys_bad_expires_at() {
     let mut server = Server::new_auth().await;
     server.use_admin_key("MASTER_KEY").await;
 
@@ -378,10 +378,10 @@ async fn patch_api_keys_immutable_expires_at() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Immutable field `expiresAt`: expected one of `description`, `name`",
-      "code": "immutable_api_key_expires_at",
+      "message": "Invalid value at `.expiresAt`: `doggo` is not a valid date. It should follow the RFC 3339 format to represents a date or datetime in the future or specified as a null value. e.g. 'YYYY-MM-DD' or 'YYYY-MM-DD HH:MM:SS'.\n",
+      "code": "invalid_api_key_expires_at",
       "type": "invalid_request",
-      "link": "https://docs.meilisearch.com/errors#immutable_api_key_expires_at"
+      "link": "https://docs.meilisearch.com/errors#invalid_api_key_expires_at"
     }
     "###);
 }
@@ -395,7 +395,7 @@ async fn patch_api_keys_immutable_created_at() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Immutable field `createdAt`: expected one of `description`, `name`",
+      "message": "Immutable field `createdAt`: expected one of `description`, `name`, `expiresAt`",
       "code": "immutable_api_key_created_at",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_created_at"
@@ -412,7 +412,7 @@ async fn patch_api_keys_immutable_updated_at() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Immutable field `updatedAt`: expected one of `description`, `name`",
+      "message": "Immutable field `updatedAt`: expected one of `description`, `name`, `expiresAt`",
       "code": "immutable_api_key_updated_at",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_updated_at"
@@ -429,7 +429,7 @@ async fn patch_api_keys_unknown_field() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Unknown field `doggo`: expected one of `description`, `name`",
+      "message": "Unknown field `doggo`: expected one of `description`, `name`, `expiresAt`",
       "code": "bad_request",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#bad_request"
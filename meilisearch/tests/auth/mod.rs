@@ -45,6 +45,14 @@ impl Server {
         self.service.get(url).await
     }
 
+    pub async fn delete_api_keys(&self, keys: Value) -> (Value, StatusCode) {
+        self.service.post("/keys/delete-batch", keys).await
+    }
+
+    pub async fn purge_expired_api_keys(&self) -> (Value, StatusCode) {
+        self.service.post("/keys/purge-expired", json!(null)).await
+    }
+
     pub async fn delete_api_key(&self, key: impl AsRef<str>) -> (Value, StatusCode) {
         let url = format!("/keys/{}", key.as_ref());
         self.service.delete(url).await
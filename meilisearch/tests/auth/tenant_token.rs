@@ -377,6 +377,50 @@ async fn filter_search_authorized_filter_token() {
     compute_authorized_search!(tenant_tokens, "color = yellow", 1);
 }
 
+#[actix_rt::test]
+async fn disjunctive_facets_keep_the_filter_of_the_token() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+    let index = server.index("sales");
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+    index.update_settings(json!({"filterableAttributes": ["color"]})).await;
+    index.wait_task(1).await;
+    drop(index);
+
+    server.use_api_key("MASTER_KEY");
+    let content = json!({ "indexes": ["*"], "actions": ["search"], "expiresAt": null });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(code, 201, "{}", response);
+    let key = response["key"].as_str().unwrap();
+    let uid = response["uid"].as_str().unwrap();
+
+    let tenant_token = hashmap! {
+        "searchRules" => json!({"sales": {"filter": "color = red"}}),
+        "exp" => json!((OffsetDateTime::now_utc() + Duration::hours(1)).unix_timestamp())
+    };
+    let web_token = generate_tenant_token(uid, key, tenant_token);
+    server.use_api_key(&web_token);
+
+    // the filter of the query on `color` is relaxed, but not the one of the token.
+    let index = server.index("sales");
+    index
+        .search(
+            json!({ "filter": "color = blue", "facets": ["color"], "disjunctiveFacets": true }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(response["hits"].as_array().unwrap().len(), 1, "{}", response);
+                assert_eq!(
+                    response["facetDistribution"]["color"],
+                    json!({ "blue": 1, "green": 1, "red": 3, "yellow": 1 }),
+                    "{}",
+                    response
+                );
+            },
+        )
+        .await;
+}
+
 /// Tests that those Tenant Token are incompatible with the REFUSED_KEYS defined above.
 #[actix_rt::test]
 async fn error_search_token_forbidden_parent_key() {
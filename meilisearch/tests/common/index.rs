@@ -263,6 +263,11 @@ impl Index<'_> {
         self.service.patch_encoded(url, settings, self.encoder).await
     }
 
+    pub async fn validate_settings(&self, settings: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/settings/validate", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, settings, self.encoder).await
+    }
+
     pub async fn update_settings_displayed_attributes(
         &self,
         settings: Value,
@@ -343,6 +348,21 @@ impl Index<'_> {
         self.service.get(url).await
     }
 
+    pub async fn lmdb_stats(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/lmdb-stats", urlencode(self.uid.as_ref()));
+        self.service.get(url).await
+    }
+
+    pub async fn facet_schema(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/facets", urlencode(self.uid.as_ref()));
+        self.service.get(url).await
+    }
+
+    pub async fn suggest(&self, query: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/suggest?{}", urlencode(self.uid.as_ref()), query);
+        self.service.get(url).await
+    }
+
     /// Performs both GET and POST search queries
     pub async fn search(
         &self,
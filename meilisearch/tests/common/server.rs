@@ -3,7 +3,7 @@
 use std::path::Path;
 use std::time::Duration;
 
-use actix_http::body::MessageBody;
+use actix_http::body::{BoxBody, MessageBody};
 use actix_web::dev::ServiceResponse;
 use actix_web::http::StatusCode;
 use byte_unit::{Byte, ByteUnit};
@@ -169,6 +169,19 @@ impl Server {
         self.service.post(format!("/tasks/cancel?{}", value), json!(null)).await
     }
 
+    pub async fn task_events(
+        &self,
+        value: &str,
+        last_event_id: Option<&str>,
+    ) -> (BoxBody, StatusCode) {
+        let headers: Vec<_> = last_event_id.map(|id| ("Last-Event-ID", id)).into_iter().collect();
+        self.service.get_stream(format!("/tasks/events?{}", value), &headers).await
+    }
+
+    pub async fn abort_tasks(&self) -> (Value, StatusCode) {
+        self.service.post("/tasks/abort", json!(null)).await
+    }
+
     pub async fn delete_tasks(&self, value: &str) -> (Value, StatusCode) {
         self.service.delete(format!("/tasks?{}", value)).await
     }
@@ -213,6 +226,7 @@ pub fn default_settings(dir: impl AsRef<Path>) -> Opt {
         no_analytics: true,
         max_index_size: Byte::from_unit(100.0, ByteUnit::MiB).unwrap(),
         max_task_db_size: Byte::from_unit(1.0, ByteUnit::GiB).unwrap(),
+        max_auth_store_size: Byte::from_unit(100.0, ByteUnit::MiB).unwrap(),
         http_payload_size_limit: Byte::from_unit(10.0, ByteUnit::MiB).unwrap(),
         snapshot_dir: ".".into(),
         indexer_options: IndexerOpts {
@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
+use actix_web::body::{BoxBody, MessageBody};
 use actix_web::http::header::ContentType;
 use actix_web::http::StatusCode;
 use actix_web::test;
 use actix_web::test::TestRequest;
+use actix_web::web::Bytes;
 use index_scheduler::IndexScheduler;
 use meilisearch::{analytics, create_app, Opt};
 use meilisearch_auth::AuthController;
@@ -104,7 +106,14 @@ impl Service {
         self.request(req).await
     }
 
-    pub async fn request(&self, mut req: test::TestRequest) -> (Value, StatusCode) {
+    pub async fn request(&self, req: test::TestRequest) -> (Value, StatusCode) {
+        let (body, status_code) = self.request_bytes(req).await;
+        let response = serde_json::from_slice(&body).unwrap_or_default();
+        (response, status_code)
+    }
+
+    /// Send a test request and return the raw body of the response.
+    pub async fn request_bytes(&self, mut req: test::TestRequest) -> (Bytes, StatusCode) {
         let app = test::init_service(create_app(
             self.index_scheduler.clone().into(),
             self.auth.clone().into(),
@@ -122,8 +131,34 @@ impl Service {
         let status_code = res.status();
 
         let body = test::read_body(res).await;
-        let response = serde_json::from_slice(&body).unwrap_or_default();
-        (response, status_code)
+        (body, status_code)
+    }
+
+    /// Send a test get request and return the body without waiting for its end.
+    pub async fn get_stream(
+        &self,
+        url: impl AsRef<str>,
+        headers: &[(&str, &str)],
+    ) -> (BoxBody, StatusCode) {
+        let app = test::init_service(create_app(
+            self.index_scheduler.clone().into(),
+            self.auth.clone().into(),
+            self.options.clone(),
+            analytics::MockAnalytics::new(&self.options),
+            true,
+        ))
+        .await;
+
+        let mut req = test::TestRequest::get().uri(url.as_ref());
+        if let Some(api_key) = &self.api_key {
+            req = req.insert_header(("Authorization", ["Bearer ", api_key].concat()));
+        }
+        for &header in headers {
+            req = req.insert_header(header);
+        }
+        let res = test::call_service(&app, req.to_request()).await;
+        let status_code = res.status();
+        (res.into_body().boxed(), status_code)
     }
 
     fn encode(&self, req: TestRequest, body: Value, encoder: Encoder) -> TestRequest {
@@ -1,9 +1,11 @@
 use actix_web::test;
 use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
 use crate::common::encoder::Encoder;
+use crate::common::server::default_settings;
 use crate::common::{GetAllDocumentsOptions, Server, Value};
 use crate::json;
 
@@ -216,6 +218,54 @@ async fn add_single_document_with_every_encoding() {
     }
 }
 
+#[actix_rt::test]
+async fn add_compressed_documents_in_every_format() {
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+    let payloads = [
+        ("application/json", r#"[{ "id": 1, "content": "Bouvier Bernois" }]"#),
+        ("application/x-ndjson", r#"{ "id": 1, "content": "Bouvier Bernois" }"#),
+        ("text/csv", "id,content\n1,Bouvier Bernois"),
+    ];
+
+    for encoder in [Encoder::Gzip, Encoder::Deflate] {
+        for (content_type, payload) in payloads {
+            let req = test::TestRequest::post()
+                .uri("/indexes/dog/documents")
+                .set_payload(encoder.encode(payload))
+                .insert_header(("content-type", content_type))
+                .insert_header(encoder.header().unwrap())
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), 202, "{content_type}");
+        }
+    }
+}
+
+#[actix_rt::test]
+async fn add_documents_with_corrupted_encoding() {
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    for encoder in [Encoder::Gzip, Encoder::Deflate] {
+        let req = test::TestRequest::post()
+            .uri("/indexes/dog/documents")
+            .set_payload(r#"[{ "id": 1, "content": "Bouvier Bernois" }]"#)
+            .insert_header(("content-type", "application/json"))
+            .insert_header(encoder.header().unwrap())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        let status_code = res.status();
+        let body = test::read_body(res).await;
+        let response: Value = serde_json::from_slice(&body).unwrap_or_default();
+        assert_eq!(status_code, 400, "{response}");
+        assert_eq!(response["type"], "invalid_request", "{response}");
+    }
+
+    let (response, _) = server.tasks().await;
+    assert_eq!(response["results"].as_array().unwrap().len(), 0, "{response}");
+}
+
 #[actix_rt::test]
 async fn add_csv_document() {
     let server = Server::new().await;
@@ -354,6 +404,203 @@ async fn add_csv_document_with_types() {
     "###);
 }
 
+#[actix_rt::test]
+async fn add_documents_with_renamed_fields() {
+    let server = Server::new().await;
+    let index = server.index("products");
+
+    let documents = r#"[
+        { "id": 0, "Product Name": "red running shoes", "Price": 42 },
+        { "id": 1, "Product Name": "blue hat", "Price": 12 }
+    ]"#;
+
+    let (response, code) = index
+        .raw_add_documents(
+            documents,
+            Some("application/json"),
+            "?renameFields=Product%20Name:title,Price:price",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    index
+        .search(json!({"q": "shoes"}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0,
+                "title": "red running shoes",
+                "price": 42
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    let (response, code) = index
+        .raw_add_documents(
+            documents,
+            Some("application/json"),
+            "?renameFields=Product%20Name:title,Price:title",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The fields `Product Name` and `Price` cannot both be renamed into `title`.",
+      "code": "invalid_document_rename_fields",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_rename_fields"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_documents_with_autogenerated_ids() {
+    let server = Server::new().await;
+    let index = server.index("products");
+
+    let documents = r#"[
+        { "id": 0, "title": "red running shoes" },
+        { "title": "blue hat" },
+        { "id": null, "title": "green socks" }
+    ]"#;
+
+    let (response, code) =
+        index.raw_add_documents(documents, Some("application/json"), "?autogenerateIds=true").await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "receivedDocuments": 3,
+      "indexedDocuments": 3,
+      "generatedIds": 2
+    }
+    "###);
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results[1].id" => "[uuid]", ".results[2].id" => "[uuid]" }), @r###"
+    {
+      "results": [
+        {
+          "id": 0,
+          "title": "red running shoes"
+        },
+        {
+          "id": "[uuid]",
+          "title": "blue hat"
+        },
+        {
+          "id": "[uuid]",
+          "title": "green socks"
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 3
+    }
+    "###);
+    for document in &response["results"].as_array().unwrap()[1..] {
+        assert!(uuid::Uuid::parse_str(document["id"].as_str().unwrap()).is_ok());
+    }
+
+    let (response, code) =
+        index.raw_add_documents(documents, Some("application/json"), "?autogenerateIds=yes").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_document_autogenerate_ids""###);
+}
+
+#[actix_rt::test]
+async fn add_documents_with_autogenerated_ids_for_the_default_primary_key() {
+    let temp = tempfile::tempdir().unwrap();
+    let options =
+        Opt { default_primary_key: Some(String::from("uid")), ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("products");
+
+    let documents = r#"[{ "title": "blue hat" }]"#;
+    let (response, code) =
+        index.raw_add_documents(documents, Some("application/json"), "?autogenerateIds=true").await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (response, code) = index.get().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["primaryKey"], @r###""uid""###);
+    let (response, _) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert!(uuid::Uuid::parse_str(response["results"][0]["uid"].as_str().unwrap()).is_ok());
+}
+
+#[actix_rt::test]
+async fn add_documents_with_low_indexing_limits() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents: Vec<_> = (0..2000)
+        .map(|id| json!({ "id": id, "title": format!("title number {id}"), "desc": "a b c d e" }))
+        .collect();
+    let documents = serde_json::to_string(&documents).unwrap();
+
+    // a budget of 1MiB forces the indexer to spill its intermediate data on disk
+    let (response, code) = index
+        .raw_add_documents(
+            &documents,
+            Some("application/json"),
+            "?maxIndexingMemory=1048576&maxIndexingThreads=1",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "receivedDocuments": 2000,
+      "indexedDocuments": 2000
+    }
+    "###);
+
+    let (response, code) = index.get_document(1999, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1999,
+      "title": "title number 1999",
+      "desc": "a b c d e"
+    }
+    "###);
+
+    let (response, code) = index
+        .raw_add_documents(&documents, Some("application/json"), "?maxIndexingThreads=0")
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `maxIndexingThreads`: could not parse `0` as a positive integer",
+      "code": "invalid_document_max_indexing_threads",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_max_indexing_threads"
+    }
+    "###);
+
+    let (response, code) =
+        index.raw_add_documents(&documents, Some("application/json"), "?maxIndexingMemory=0").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `maxIndexingMemory`: could not parse `0` as a positive integer",
+      "code": "invalid_document_max_indexing_memory",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_max_indexing_memory"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn add_csv_document_with_custom_delimiter() {
     let server = Server::new().await;
@@ -418,6 +665,219 @@ async fn add_csv_document_with_custom_delimiter() {
     "###);
 }
 
+#[actix_rt::test]
+async fn add_headerless_csv_document_with_csv_header() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let document = "0,jean,bernese mountain
+1,jorts,orange cat";
+
+    let (response, code) =
+        index.raw_update_documents(document, Some("text/csv"), "?csvHeader=id,name,race").await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(response["details"]["indexedDocuments"], @"2");
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": "0",
+        "name": "jean",
+        "race": "bernese mountain"
+      },
+      {
+        "id": "1",
+        "name": "jorts",
+        "race": "orange cat"
+      }
+    ]
+    "###);
+
+    // a row that doesn't have as many columns as the header is rejected
+    let document = "2,pierre,sphynx,bald";
+    let (response, code) =
+        index.raw_update_documents(document, Some("text/csv"), "?csvHeader=id,name,race").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""malformed_payload""###);
+
+    let (response, code) = index
+        .raw_update_documents(r#"[{ "id": 2 }]"#, Some("application/json"), "?csvHeader=id")
+        .await;
+    snapshot!(code, @"415 Unsupported Media Type");
+    snapshot!(response["message"], @r###""The Content-Type `application/json` does not support the use of a csv header. The csv header can only be used with the Content-Type `text/csv`.""###);
+}
+
+#[actix_rt::test]
+async fn add_tsv_document() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let document = "#id\tname\trace
+0\tjean\tbernese mountain
+1\tjorts\torange cat";
+
+    let (response, code) =
+        index.raw_update_documents(document, Some("text/tab-separated-values"), "").await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(response["details"]["indexedDocuments"], @"2");
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "#id": "0",
+        "name": "jean",
+        "race": "bernese mountain"
+      },
+      {
+        "#id": "1",
+        "name": "jorts",
+        "race": "orange cat"
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .raw_update_documents(document, Some("text/tab-separated-values"), "?csvDelimiter=;")
+        .await;
+    snapshot!(code, @"415 Unsupported Media Type");
+    snapshot!(response["code"], @r###""invalid_content_type""###);
+}
+
+#[actix_rt::test]
+async fn add_documents_with_content_type_parameters() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let (response, code) = index
+        .raw_update_documents(
+            r#"[{ "id": 0, "name": "jean" }]"#,
+            Some("application/json; charset=utf-8"),
+            "",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (response, code) = index
+        .raw_update_documents(
+            "id;name\n1;jorts",
+            Some("text/CSV; charset=utf-8"),
+            "?csvDelimiter=;",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (response, code) =
+        index.raw_update_documents("id\n2", Some("text/plain; charset=utf-8"), "").await;
+    snapshot!(code, @"415 Unsupported Media Type");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `text/tab-separated-values`",
+      "code": "invalid_content_type",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_content_type"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_documents_with_duplicate_ids() {
+    let server = Server::new().await;
+    let documents = r#"[
+        { "id": 1, "name": "kefir" },
+        { "id": 2, "name": "intel" },
+        { "id": 1, "color": "white" }
+    ]"#;
+
+    // the last document replaces the previous one.
+    let index = server.index("replace");
+    let (response, code) = index.raw_add_documents(documents, Some("application/json"), "").await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(response["details"]["duplicatedDocuments"], @"1");
+    let (response, _) = index.get_document(1, None).await;
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "color": "white"
+    }
+    "###);
+
+    // the last document is merged into the previous one.
+    let index = server.index("update");
+    let (response, code) =
+        index.raw_update_documents(documents, Some("application/json"), "").await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(response["details"]["duplicatedDocuments"], @"1");
+    let (response, _) = index.get_document(1, None).await;
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "name": "kefir",
+      "color": "white"
+    }
+    "###);
+
+    // only the first document is kept.
+    let index = server.index("keep_first");
+    let (response, code) = index
+        .raw_update_documents(documents, Some("application/json"), "?duplicates=keepFirst")
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(response["details"]["duplicatedDocuments"], @"1");
+    let (response, _) = index.get_document(1, None).await;
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "name": "kefir"
+    }
+    "###);
+
+    // the batch is rejected.
+    let index = server.index("reject");
+    let (response, code) =
+        index.raw_add_documents(documents, Some("application/json"), "?duplicates=reject").await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The document with the id: `1` is present more than once in the batch.",
+      "code": "duplicate_document_id",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#duplicate_document_id"
+    }
+    "###);
+
+    // no duplicates are reported when there are none.
+    let index = server.index("unique");
+    let (response, _) = index.add_documents(json!([{ "id": 1 }, { "id": 2 }]), None).await;
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    assert!(response["details"].get("duplicatedDocuments").is_none(), "{response}");
+
+    let (response, code) =
+        index.raw_add_documents(documents, Some("application/json"), "?duplicates=last").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_document_duplicates""###);
+}
+
 #[actix_rt::test]
 async fn add_csv_document_with_types_error() {
     let server = Server::new().await;
@@ -479,7 +939,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `text/tab-separated-values`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -500,7 +960,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `text/tab-separated-values`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -534,7 +994,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `text/tab-separated-values`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -554,7 +1014,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `text/tab-separated-values`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -612,6 +1072,29 @@ async fn error_add_malformed_csv_documents() {
     "###);
 }
 
+#[actix_rt::test]
+async fn malformed_documents_do_not_leave_update_files() {
+    let server = Server::new().await;
+    let index = server.index("dog");
+
+    let payloads = [
+        (r#"[{ "id": 1 },]"#, "application/json"),
+        ("{ \"id\": 1 }\n{ \"id\": 2, }", "application/x-ndjson"),
+        ("id, content\n1234, hello, world\n12, hello world", "text/csv"),
+    ];
+    for (payload, content_type) in payloads {
+        let (response, code) = index.raw_add_documents(payload, Some(content_type), "").await;
+        assert_eq!(code, 400, "{response}");
+        assert_eq!(response["code"], "malformed_payload", "{response}");
+        // the message points at the offending line of the payload.
+        assert!(response["message"].as_str().unwrap().contains("line"), "{response}");
+    }
+
+    let (response, _) = server.tasks().await;
+    snapshot!(response["results"], @"[]");
+    assert_eq!(server.service.index_scheduler.compute_update_file_size().unwrap(), 0);
+}
+
 #[actix_rt::test]
 async fn error_add_malformed_json_documents() {
     let document = r#"[{"id": 1}, {id: 2}]"#;
@@ -1,9 +1,11 @@
 use actix_web::test;
 use http::header::ACCEPT_ENCODING;
 use meili_snap::*;
+use meilisearch::Opt;
 use urlencoding::encode as urlencode;
 
 use crate::common::encoder::Encoder;
+use crate::common::server::default_settings;
 use crate::common::{GetAllDocumentsOptions, GetDocumentOptions, Server, Value};
 use crate::json;
 
@@ -186,6 +188,42 @@ async fn test_get_all_documents_limit() {
     assert_eq!(response["total"], json!(77));
 }
 
+#[actix_rt::test]
+async fn get_all_documents_limit_is_clamped() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        default_documents_limit: 10,
+        max_documents_limit: Some(1000),
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+    index.load_test_set().await;
+
+    let (response, code) = index.get_all_documents_raw("").await;
+    assert_eq!(code, 200);
+    assert_eq!(response["results"].as_array().unwrap().len(), 10);
+    assert_eq!(response["limit"], json!(10));
+
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::get().uri("/indexes/test/documents?limit=100000").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get("X-Meilisearch-Limit-Clamped").unwrap(), "1000");
+    let response: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(response["limit"], json!(1000));
+    assert_eq!(response["results"].as_array().unwrap().len(), 77);
+
+    let (response, code) = index.get_document_by_filter(json!({ "limit": 100000 })).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["limit"], json!(1000));
+
+    let req = test::TestRequest::get().uri("/indexes/test/documents?limit=1000").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    assert!(res.headers().get("X-Meilisearch-Limit-Clamped").is_none());
+}
+
 #[actix_rt::test]
 async fn test_get_all_documents_offset() {
     let server = Server::new().await;
@@ -540,3 +578,151 @@ async fn get_document_by_filter() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn get_documents_sorted() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.update_settings_sortable_attributes(json!(["created_at"])).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "created_at": 1674000000 },
+                { "id": 1, "created_at": 1675000000 },
+                { "id": 2, "created_at": 1673000000 },
+                { "id": 3 },
+                { "id": 4, "created_at": 1676000000 },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .get_document_by_filter(
+            json!({ "sort": ["created_at:desc"], "limit": 3, "fields": ["id"] }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": 4
+        },
+        {
+          "id": 1
+        },
+        {
+          "id": 0
+        }
+      ],
+      "offset": 0,
+      "limit": 3,
+      "total": 5
+    }
+    "###);
+
+    let (response, code) =
+        index.get_all_documents_raw("?sort=created_at:desc&offset=3&limit=3&fields=id").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": 2
+        },
+        {
+          "id": 3
+        }
+      ],
+      "offset": 3,
+      "limit": 3,
+      "total": 5
+    }
+    "###);
+
+    let (response, code) = index.get_all_documents_raw("?sort=id:asc").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `id` is not sortable. Available sortable attributes are: `created_at`.",
+      "code": "invalid_document_sort",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_sort"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn get_documents_sorted_mixing_numbers_and_strings() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.update_settings_sortable_attributes(json!(["size"])).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "size": 10 },
+                { "id": 1, "size": "big" },
+                { "id": 2, "size": 3 },
+                { "id": 3 },
+                { "id": 4, "size": "small" },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.get_all_documents_raw("?sort=size:asc&fields=id").await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["results"].as_array().unwrap().iter().map(|d| &d["id"]).collect();
+    snapshot!(format!("{ids:?}"), @"[Number(2), Number(0), Number(1), Number(4), Number(3)]");
+
+    let (response, code) = index.get_all_documents_raw("?sort=size:desc&fields=id").await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["results"].as_array().unwrap().iter().map(|d| &d["id"]).collect();
+    snapshot!(format!("{ids:?}"), @"[Number(4), Number(1), Number(0), Number(2), Number(3)]");
+}
+
+#[actix_rt::test]
+async fn get_documents_with_checksum() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    let documents = json!([
+        { "id": 0, "name": "kefir", "traits": { "age": 3, "color": "white" } },
+        { "id": 1, "name": "kefir", "traits": { "age": 4, "color": "white" } },
+    ]);
+    index.add_documents(documents, Some("id")).await;
+    index.wait_task(0).await;
+
+    // the same document, with its fields in another order.
+    let other = server.index("other_doggo");
+    let documents = json!([{ "traits": { "color": "white", "age": 3 }, "name": "kefir", "id": 0 }]);
+    other.add_documents(documents, Some("id")).await;
+    other.wait_task(1).await;
+
+    let (response, code) = index.get_all_documents_raw("?showChecksum=true&fields=name").await;
+    snapshot!(code, @"200 OK");
+    let results = response["results"].as_array().unwrap();
+    assert_eq!(results[0]["name"], "kefir", "{response}");
+    assert!(results[0]["_checksum"].is_string(), "{response}");
+    assert_ne!(results[0]["_checksum"], results[1]["_checksum"], "{response}");
+
+    let (response, code) =
+        other.get_document_by_filter(json!({ "fields": ["id"], "showChecksum": true })).await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["results"][0]["_checksum"], results[0]["_checksum"], "{response}");
+
+    let (response, code) =
+        other.service.get("/indexes/other_doggo/documents/0?showChecksum=true").await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["_checksum"], results[0]["_checksum"], "{response}");
+
+    let (response, code) = other.get_document(0, None).await;
+    snapshot!(code, @"200 OK");
+    assert!(response.get("_checksum").is_none(), "{response}");
+
+    let (response, code) = index.get_all_documents_raw("?showChecksum=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_document_show_checksum""###);
+}
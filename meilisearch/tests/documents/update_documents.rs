@@ -209,3 +209,76 @@ async fn error_update_documents_missing_document_id() {
         "https://docs.meilisearch.com/errors#missing_document_id"
     );
 }
+
+#[actix_rt::test]
+async fn update_document_with_version() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 1, "content": "foo", "_version": 0 }]"#,
+            Some("application/json"),
+            "?versionField=_version",
+        )
+        .await;
+    assert_eq!(code, 202);
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 1, "content": "foo", "_version": 1 }));
+
+    // in a single task, the stale document is rejected while the other documents are applied
+    let (task, code) = index
+        .raw_update_documents(
+            r#"[
+                { "id": 1, "content": "bar", "_version": 0 },
+                { "id": 2, "content": "baz", "_version": 0 }
+            ]"#,
+            Some("application/json"),
+            "?versionField=_version",
+        )
+        .await;
+    assert_eq!(code, 202);
+    let response = index.wait_task(task.uid()).await;
+    assert_eq!(response["status"], "succeeded");
+    assert_eq!(response["details"]["indexedDocuments"], 1);
+    assert_eq!(response["details"]["rejectedDocuments"], 1);
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 1, "content": "foo", "_version": 1 }));
+
+    // the versions of the documents of a batch are checked against each other
+    let (task, code) = index
+        .raw_update_documents(
+            r#"[
+                { "id": 1, "content": "bar", "_version": 1 },
+                { "id": 1, "content": "qux", "_version": 1 },
+                { "id": 1, "content": "quux", "_version": 2 }
+            ]"#,
+            Some("application/json"),
+            "?versionField=_version",
+        )
+        .await;
+    assert_eq!(code, 202);
+    let response = index.wait_task(task.uid()).await;
+    assert_eq!(response["status"], "succeeded");
+    assert_eq!(response["details"]["rejectedDocuments"], 1);
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 1, "content": "quux", "_version": 3 }));
+
+    // without the parameter, the field is a regular field
+    let (task, code) =
+        index.update_documents(json!([{ "id": 1, "content": "foo", "_version": 0 }]), None).await;
+    assert_eq!(code, 202);
+    let response = index.wait_task(task.uid()).await;
+    assert_eq!(response["status"], "succeeded");
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 1, "content": "foo", "_version": 0 }));
+}
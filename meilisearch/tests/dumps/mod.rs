@@ -29,7 +29,7 @@ async fn import_dump_v1_movie_raw() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "searchCount": 0, "memorySize": stats["memorySize"], "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
     );
 
     let (settings, code) = index.settings().await;
@@ -53,11 +53,17 @@ async fn import_dump_v1_movie_raw() {
         "attribute",
         "exactness"
       ],
+      "tieBreakingSort": [],
+      "encryptedAttributes": [],
       "stopWords": [],
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "caseSensitive": false,
+      "languages": [],
+      "searchableNumbers": true,
       "synonyms": {},
+      "bidirectionalSynonyms": [],
       "distinctAttribute": null,
       "typoTolerance": {
         "enabled": true,
@@ -76,7 +82,8 @@ async fn import_dump_v1_movie_raw() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "pinnedInMemory": false
     }
     "###
     );
@@ -162,11 +169,13 @@ async fn import_dump_v1_movie_with_settings() {
     let (stats, code) = index.stats().await;
     snapshot!(code, @"200 OK");
     snapshot!(
-        json_string!(stats),
+        json_string!(stats, { ".memorySize" => "[size]" }),
         @r###"
     {
       "numberOfDocuments": 53,
       "isIndexing": false,
+      "searchCount": 0,
+      "memorySize": "[size]",
       "fieldDistribution": {
         "genres": 53,
         "id": 53,
@@ -210,6 +219,8 @@ async fn import_dump_v1_movie_with_settings() {
         "attribute",
         "exactness"
       ],
+      "tieBreakingSort": [],
+      "encryptedAttributes": [],
       "stopWords": [
         "of",
         "the"
@@ -217,7 +228,11 @@ async fn import_dump_v1_movie_with_settings() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "caseSensitive": false,
+      "languages": [],
+      "searchableNumbers": true,
       "synonyms": {},
+      "bidirectionalSynonyms": [],
       "distinctAttribute": null,
       "typoTolerance": {
         "enabled": true,
@@ -236,7 +251,8 @@ async fn import_dump_v1_movie_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "pinnedInMemory": false
     }
     "###
     );
@@ -323,7 +339,7 @@ async fn import_dump_v1_rubygems_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "searchCount": 0, "memorySize": stats["memorySize"], "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
     );
 
     let (settings, code) = index.settings().await;
@@ -359,11 +375,17 @@ async fn import_dump_v1_rubygems_with_settings() {
         "exactness",
         "total_downloads:desc"
       ],
+      "tieBreakingSort": [],
+      "encryptedAttributes": [],
       "stopWords": [],
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "caseSensitive": false,
+      "languages": [],
+      "searchableNumbers": true,
       "synonyms": {},
+      "bidirectionalSynonyms": [],
       "distinctAttribute": null,
       "typoTolerance": {
         "enabled": true,
@@ -382,7 +404,8 @@ async fn import_dump_v1_rubygems_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "pinnedInMemory": false
     }
     "###
     );
@@ -467,7 +490,7 @@ async fn import_dump_v2_movie_raw() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "searchCount": 0, "memorySize": stats["memorySize"], "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
     );
 
     let (settings, code) = index.settings().await;
@@ -491,11 +514,17 @@ async fn import_dump_v2_movie_raw() {
         "attribute",
         "exactness"
       ],
+      "tieBreakingSort": [],
+      "encryptedAttributes": [],
       "stopWords": [],
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "caseSensitive": false,
+      "languages": [],
+      "searchableNumbers": true,
       "synonyms": {},
+      "bidirectionalSynonyms": [],
       "distinctAttribute": null,
       "typoTolerance": {
         "enabled": true,
@@ -514,7 +543,8 @@ async fn import_dump_v2_movie_raw() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "pinnedInMemory": false
     }
     "###
     );
@@ -601,7 +631,7 @@ async fn import_dump_v2_movie_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "searchCount": 0, "memorySize": stats["memorySize"], "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
     );
 
     let (settings, code) = index.settings().await;
@@ -632,6 +662,8 @@ async fn import_dump_v2_movie_with_settings() {
         "attribute",
         "exactness"
       ],
+      "tieBreakingSort": [],
+      "encryptedAttributes": [],
       "stopWords": [
         "of",
         "the"
@@ -639,7 +671,11 @@ async fn import_dump_v2_movie_with_settings() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "caseSensitive": false,
+      "languages": [],
+      "searchableNumbers": true,
       "synonyms": {},
+      "bidirectionalSynonyms": [],
       "distinctAttribute": null,
       "typoTolerance": {
         "enabled": true,
@@ -658,7 +694,8 @@ async fn import_dump_v2_movie_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "pinnedInMemory": false
     }
     "###
     );
@@ -745,7 +782,7 @@ async fn import_dump_v2_rubygems_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "searchCount": 0, "memorySize": stats["memorySize"], "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
     );
 
     let (settings, code) = index.settings().await;
@@ -778,11 +815,17 @@ async fn import_dump_v2_rubygems_with_settings() {
         "exactness",
         "total_downloads:desc"
       ],
+      "tieBreakingSort": [],
+      "encryptedAttributes": [],
       "stopWords": [],
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "caseSensitive": false,
+      "languages": [],
+      "searchableNumbers": true,
       "synonyms": {},
+      "bidirectionalSynonyms": [],
       "distinctAttribute": null,
       "typoTolerance": {
         "enabled": true,
@@ -801,7 +844,8 @@ async fn import_dump_v2_rubygems_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "pinnedInMemory": false
     }
     "###
     );
@@ -886,7 +930,7 @@ async fn import_dump_v3_movie_raw() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "searchCount": 0, "memorySize": stats["memorySize"], "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
     );
 
     let (settings, code) = index.settings().await;
@@ -910,11 +954,17 @@ async fn import_dump_v3_movie_raw() {
         "attribute",
         "exactness"
       ],
+      "tieBreakingSort": [],
+      "encryptedAttributes": [],
       "stopWords": [],
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "caseSensitive": false,
+      "languages": [],
+      "searchableNumbers": true,
       "synonyms": {},
+      "bidirectionalSynonyms": [],
       "distinctAttribute": null,
       "typoTolerance": {
         "enabled": true,
@@ -933,7 +983,8 @@ async fn import_dump_v3_movie_raw() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "pinnedInMemory": false
     }
     "###
     );
@@ -1020,7 +1071,7 @@ async fn import_dump_v3_movie_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "searchCount": 0, "memorySize": stats["memorySize"], "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
     );
 
     let (settings, code) = index.settings().await;
@@ -1051,6 +1102,8 @@ async fn import_dump_v3_movie_with_settings() {
         "attribute",
         "exactness"
       ],
+      "tieBreakingSort": [],
+      "encryptedAttributes": [],
       "stopWords": [
         "of",
         "the"
@@ -1058,7 +1111,11 @@ async fn import_dump_v3_movie_with_settings() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "caseSensitive": false,
+      "languages": [],
+      "searchableNumbers": true,
       "synonyms": {},
+      "bidirectionalSynonyms": [],
       "distinctAttribute": null,
       "typoTolerance": {
         "enabled": true,
@@ -1077,7 +1134,8 @@ async fn import_dump_v3_movie_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "pinnedInMemory": false
     }
     "###
     );
@@ -1164,7 +1222,7 @@ async fn import_dump_v3_rubygems_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "searchCount": 0, "memorySize": stats["memorySize"], "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
     );
 
     let (settings, code) = index.settings().await;
@@ -1197,11 +1255,17 @@ async fn import_dump_v3_rubygems_with_settings() {
         "exactness",
         "total_downloads:desc"
       ],
+      "tieBreakingSort": [],
+      "encryptedAttributes": [],
       "stopWords": [],
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "caseSensitive": false,
+      "languages": [],
+      "searchableNumbers": true,
       "synonyms": {},
+      "bidirectionalSynonyms": [],
       "distinctAttribute": null,
       "typoTolerance": {
         "enabled": true,
@@ -1220,7 +1284,8 @@ async fn import_dump_v3_rubygems_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "pinnedInMemory": false
     }
     "###
     );
@@ -1305,7 +1370,7 @@ async fn import_dump_v4_movie_raw() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "searchCount": 0, "memorySize": stats["memorySize"], "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
     );
 
     let (settings, code) = index.settings().await;
@@ -1329,11 +1394,17 @@ async fn import_dump_v4_movie_raw() {
         "attribute",
         "exactness"
       ],
+      "tieBreakingSort": [],
+      "encryptedAttributes": [],
       "stopWords": [],
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "caseSensitive": false,
+      "languages": [],
+      "searchableNumbers": true,
       "synonyms": {},
+      "bidirectionalSynonyms": [],
       "distinctAttribute": null,
       "typoTolerance": {
         "enabled": true,
@@ -1352,7 +1423,8 @@ async fn import_dump_v4_movie_raw() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "pinnedInMemory": false
     }
     "###
     );
@@ -1439,7 +1511,7 @@ async fn import_dump_v4_movie_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "searchCount": 0, "memorySize": stats["memorySize"], "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
     );
 
     let (settings, code) = index.settings().await;
@@ -1470,6 +1542,8 @@ async fn import_dump_v4_movie_with_settings() {
         "attribute",
         "exactness"
       ],
+      "tieBreakingSort": [],
+      "encryptedAttributes": [],
       "stopWords": [
         "of",
         "the"
@@ -1477,7 +1551,11 @@ async fn import_dump_v4_movie_with_settings() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "caseSensitive": false,
+      "languages": [],
+      "searchableNumbers": true,
       "synonyms": {},
+      "bidirectionalSynonyms": [],
       "distinctAttribute": null,
       "typoTolerance": {
         "enabled": true,
@@ -1496,7 +1574,8 @@ async fn import_dump_v4_movie_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "pinnedInMemory": false
     }
     "###
     );
@@ -1583,7 +1662,7 @@ async fn import_dump_v4_rubygems_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "searchCount": 0, "memorySize": stats["memorySize"], "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
     );
 
     let (settings, code) = index.settings().await;
@@ -1616,11 +1695,17 @@ async fn import_dump_v4_rubygems_with_settings() {
         "exactness",
         "total_downloads:desc"
       ],
+      "tieBreakingSort": [],
+      "encryptedAttributes": [],
       "stopWords": [],
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "caseSensitive": false,
+      "languages": [],
+      "searchableNumbers": true,
       "synonyms": {},
+      "bidirectionalSynonyms": [],
       "distinctAttribute": null,
       "typoTolerance": {
         "enabled": true,
@@ -1639,7 +1724,8 @@ async fn import_dump_v4_rubygems_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "pinnedInMemory": false
     }
     "###
     );
@@ -1726,33 +1812,38 @@ async fn import_dump_v5() {
         server.wait_task(task["uid"].as_u64().unwrap()).await;
     }
 
-    let expected_stats = json!({
-        "numberOfDocuments": 10,
-        "isIndexing": false,
-        "fieldDistribution": {
-            "cast": 10,
-            "director": 10,
-            "genres": 10,
-            "id": 10,
-            "overview": 10,
-            "popularity": 10,
-            "poster_path": 10,
-            "producer": 10,
-            "production_companies": 10,
-            "release_date": 10,
-            "tagline": 10,
-            "title": 10,
-            "vote_average": 10,
-            "vote_count": 10
-        }
-    });
+    // the memory used by the indexes varies from one run to another
+    let expected_stats = |memory_size: &serde_json::Value| {
+        json!({
+            "numberOfDocuments": 10,
+            "isIndexing": false,
+            "searchCount": 0,
+            "memorySize": memory_size,
+            "fieldDistribution": {
+                "cast": 10,
+                "director": 10,
+                "genres": 10,
+                "id": 10,
+                "overview": 10,
+                "popularity": 10,
+                "poster_path": 10,
+                "producer": 10,
+                "production_companies": 10,
+                "release_date": 10,
+                "tagline": 10,
+                "title": 10,
+                "vote_average": 10,
+                "vote_count": 10
+            }
+        })
+    };
 
     let index1 = server.index("test");
     let index2 = server.index("test2");
 
     let (stats, code) = index1.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(stats, expected_stats);
+    assert_eq!(stats, expected_stats(&stats["memorySize"]));
 
     let (docs, code) = index2.get_all_documents(GetAllDocumentsOptions::default()).await;
     snapshot!(code, @"200 OK");
@@ -1763,7 +1854,7 @@ async fn import_dump_v5() {
 
     let (stats, code) = index2.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(stats, expected_stats);
+    assert_eq!(stats, expected_stats(&stats["memorySize"]));
 
     let (keys, code) = server.list_api_keys("").await;
     snapshot!(code, @"200 OK");
@@ -1810,3 +1901,72 @@ async fn import_dump_v5() {
         json_string!(tasks, { ".results[].details.dumpUid" => "[uid]",  ".results[].duration" => "[duration]" ,  ".results[].startedAt" => "[date]" ,  ".results[].finishedAt" => "[date]"  })
     );
 }
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn import_dump_keeps_the_task_history() {
+    let temp = tempfile::tempdir().unwrap();
+    let server = Server::new_with_options(default_settings(temp.path())).await.unwrap();
+    let index = server.index("test");
+
+    index.add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    index.wait_task(0).await;
+    server.index("missing").delete().await;
+    server.wait_task(1).await;
+    let (task, code) = server.create_dump().await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+    let dump_uid = task["details"]["dumpUid"].as_str().unwrap();
+    let dump_path = temp.path().join("dumps").join(format!("{dump_uid}.dump"));
+
+    let imported = tempfile::tempdir().unwrap();
+    let options = Opt { import_dump: Some(dump_path), ..default_settings(imported.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+
+    // the finished tasks are restored as they were, without being processed again
+    let (tasks, code) = server.tasks().await;
+    snapshot!(code, @"200 OK");
+    let history: Vec<_> = tasks["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|task| json!({ "uid": task["uid"], "status": task["status"], "type": task["type"] }))
+        .collect();
+    snapshot!(json_string!(history), @r###"
+    [
+      {
+        "uid": 2,
+        "status": "succeeded",
+        "type": "dumpCreation"
+      },
+      {
+        "uid": 1,
+        "status": "failed",
+        "type": "indexDeletion"
+      },
+      {
+        "uid": 0,
+        "status": "succeeded",
+        "type": "documentAdditionOrUpdate"
+      }
+    ]
+    "###);
+    snapshot!(json_string!(tasks["results"][1]["error"]), @r###"
+    {
+      "message": "Index `missing` not found.",
+      "code": "index_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#index_not_found"
+    }
+    "###);
+
+    let (document, code) = server.index("test").get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 1,
+      "title": "Carol"
+    }
+    "###);
+}
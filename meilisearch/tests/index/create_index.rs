@@ -2,8 +2,10 @@ use actix_web::http::header::ContentType;
 use actix_web::test;
 use http::header::ACCEPT_ENCODING;
 use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
 
 use crate::common::encoder::Encoder;
+use crate::common::server::default_settings;
 use crate::common::{Server, Value};
 use crate::json;
 
@@ -24,6 +26,133 @@ async fn create_index_no_primary_key() {
     assert_eq!(response["details"]["primaryKey"], json!(null));
 }
 
+#[actix_rt::test]
+async fn create_index_with_default_primary_key() {
+    let temp = tempfile::tempdir().unwrap();
+    let options =
+        Opt { default_primary_key: Some("uid".to_string()), ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    let (response, code) = index.create(None).await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["primaryKey"], "uid");
+
+    let (response, code) = index.get().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["primaryKey"], "uid");
+
+    // an explicitly given primary key takes precedence over the default one.
+    let index = server.index("test2");
+    let (response, _) = index.create(Some("id")).await;
+    let response = index.wait_task(response.uid()).await;
+    assert_eq!(response["details"]["primaryKey"], "id");
+}
+
+#[actix_rt::test]
+async fn implicitly_created_index_gets_the_default_primary_key() {
+    let temp = tempfile::tempdir().unwrap();
+    let options =
+        Opt { default_primary_key: Some("uid".to_string()), ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    // `id` would have been inferred without the default primary key.
+    let (response, code) =
+        index.add_documents(json!([{ "uid": 1, "id": "a", "name": "kefir" }]), None).await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    let (response, code) = index.get().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["primaryKey"], "uid");
+
+    // the primary key given with the documents takes precedence over the default one.
+    let index = server.index("test2");
+    let (response, _) =
+        index.add_documents(json!([{ "uid": 1, "id": "a", "name": "kefir" }]), Some("id")).await;
+    index.wait_task(response.uid()).await;
+    let (response, _) = index.get().await;
+    assert_eq!(response["primaryKey"], "id");
+}
+
+#[actix_rt::test]
+async fn create_index_with_case_insensitive_index_uids() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt { case_insensitive_index_uids: true, ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+
+    let index = server.index("Products");
+    let (response, code) = index.create(None).await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    // the index is looked up by its lowercased uid, which `Products` and `products` both resolve to.
+    assert_eq!(response["indexUid"], "products");
+    let index = server.index("products");
+    let (response, code) = index.get().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["uid"], "Products");
+
+    let (response, _) = index.create(None).await;
+    let response = index.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "failed", "{}", response);
+    assert_eq!(response["error"]["code"], "index_already_exists");
+
+    let (response, _) = index.add_documents(json!([{ "id": 1 }]), None).await;
+    let response = index.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    let (response, code) = server.index("Products").get_document(1, None).await;
+    assert_eq!(code, 200, "{}", response);
+
+    let (response, _) = server.list_indexes(None, None).await;
+    assert_eq!(response["total"], 1, "{}", response);
+
+    // the tasks of both spellings are listed together.
+    let (response, _) = server.tasks_filter("indexUids=PRODUCTS").await;
+    assert_eq!(response["results"].as_array().unwrap().len(), 3, "{}", response);
+
+    let (response, _) = server.index("PRODUCTS").delete().await;
+    let response = server.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    let (response, code) = index.get().await;
+    assert_eq!(code, 404, "{}", response);
+}
+
+#[actix_rt::test]
+async fn case_insensitive_index_uids_keep_their_casing() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt { case_insensitive_index_uids: true, ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+
+    let (response, _) = server.index("Movies").add_documents(json!([{ "id": 1 }]), None).await;
+    let response = server.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    let (response, code) = server.list_indexes(None, None).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["results"][0]["uid"], "Movies", "{}", response);
+
+    let (response, code) = server.index("movies").get().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["uid"], "Movies");
+
+    let (response, code) = server.index("movies").get_document(1, None).await;
+    assert_eq!(code, 200, "{}", response);
+
+    // the searches of both spellings are counted together.
+    server.index("Movies").search_post(json!({})).await;
+    server.index("movies").search_post(json!({})).await;
+    let (response, code) = server.index("MOVIES").stats().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["searchCount"], 2, "{}", response);
+}
+
 #[actix_rt::test]
 async fn create_index_with_gzip_encoded_request() {
     let server = Server::new().await;
@@ -183,6 +312,24 @@ async fn error_create_existing_index() {
     assert_eq!(response["error"], expected_response);
 }
 
+#[actix_rt::test]
+async fn create_same_index_concurrently() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let ((first, first_code), (second, second_code)) =
+        tokio::join!(index.create(Some("id")), index.create(Some("id")));
+    assert_eq!(first_code, 202);
+    assert_eq!(second_code, 202);
+
+    let first = index.wait_task(first.uid()).await;
+    let second = index.wait_task(second.uid()).await;
+    let succeeded = [&first, &second].iter().filter(|task| task["status"] == "succeeded").count();
+    assert_eq!(succeeded, 1, "{first} {second}");
+    let failed = if first["status"] == "failed" { first } else { second };
+    assert_eq!(failed["error"]["code"], "index_already_exists");
+}
+
 #[actix_rt::test]
 async fn error_create_with_invalid_index_uid() {
     let server = Server::new().await;
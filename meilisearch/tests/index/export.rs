@@ -0,0 +1,114 @@
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn export_and_import_index() {
+    let server = Server::new().await;
+    let index = server.index("movies");
+    index
+        .update_settings(json!({
+            "filterableAttributes": ["genre"],
+            "rankingRules": ["words", "typo", "year:desc", "proximity", "attribute", "exactness"],
+            "stopWords": ["the"],
+        }))
+        .await;
+    let documents = json!([
+        { "id": 1, "title": "The Shining", "genre": "horror", "year": 1980 },
+        { "id": 2, "title": "Shining Through", "genre": "drama", "year": 1992 },
+        { "id": 3, "title": "The Thing", "genre": "horror", "year": 1982 },
+    ]);
+    let (response, _) = index.add_documents(documents, Some("id")).await;
+    index.wait_task(response.uid()).await;
+
+    let req = actix_web::test::TestRequest::get().uri("/indexes/movies/export");
+    let (export, code) = server.service.request_bytes(req).await;
+    assert_eq!(code, 200);
+    let export = String::from_utf8(export.to_vec()).unwrap();
+    // the settings header followed by one line per document.
+    assert_eq!(export.lines().count(), 4, "{export}");
+
+    let fresh_server = Server::new().await;
+    let (response, code) =
+        fresh_server.service.post_str("/indexes/movies/import", &export, vec![]).await;
+    assert_eq!(code, 202, "{response}");
+    let fresh_index = fresh_server.index("movies");
+    let response = fresh_index.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+
+    let (response, code) = fresh_index.get().await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["primaryKey"], "id");
+
+    let (settings, _) = index.settings().await;
+    let (fresh_settings, _) = fresh_index.settings().await;
+    assert_eq!(settings, fresh_settings);
+
+    let (documents, _) = fresh_index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(documents["total"], 3, "{documents}");
+
+    for query in [
+        json!({ "q": "the shining" }),
+        json!({ "q": "shining", "filter": "genre = drama" }),
+        json!({ "q": "" }),
+    ] {
+        let (mut response, code) = index.search_post(query.clone()).await;
+        assert_eq!(code, 200, "{response}");
+        let (mut fresh_response, code) = fresh_index.search_post(query).await;
+        assert_eq!(code, 200, "{fresh_response}");
+        response["processingTimeMs"] = json!(0);
+        fresh_response["processingTimeMs"] = json!(0);
+        assert_eq!(response, fresh_response);
+    }
+
+    // importing into an existing index is rejected.
+    let (response, code) =
+        fresh_server.service.post_str("/indexes/movies/import", &export, vec![]).await;
+    assert_eq!(code, 409, "{response}");
+    assert_eq!(response["code"], "index_already_exists");
+}
+
+#[actix_rt::test]
+async fn import_large_index() {
+    let server = Server::new().await;
+    let mut payload = String::from(r#"{ "primaryKey": "id", "settings": {} }"#);
+    for id in 0..10_000 {
+        payload.push('\n');
+        payload.push_str(&json!({ "id": id, "description": "a".repeat(100) }).to_string());
+    }
+
+    let (response, code) = server.service.post_str("/indexes/large/import", &payload, vec![]).await;
+    assert_eq!(code, 202, "{response}");
+    let index = server.index("large");
+    let response = index.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+    assert_eq!(response["details"]["receivedDocuments"], 10_000, "{response}");
+
+    let (documents, _) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(documents["total"], 10_000, "{documents}");
+
+    // an import with only a header creates the index with its settings.
+    let payload = r#"{ "primaryKey": "id", "settings": { "stopWords": ["the"] } }"#;
+    let (response, code) = server.service.post_str("/indexes/empty/import", payload, vec![]).await;
+    assert_eq!(code, 202, "{response}");
+    let index = server.index("empty");
+    let response = index.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+    let (settings, _) = index.settings().await;
+    assert_eq!(settings["stopWords"], json!(["the"]));
+}
+
+#[actix_rt::test]
+async fn import_with_invalid_settings() {
+    let server = Server::new().await;
+
+    let payload = r#"{ "primaryKey": "id", "settings": { "rankingRules": ["doggo"] } }"#;
+    let (response, code) = server.service.post_str("/indexes/test/import", payload, vec![]).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], "invalid_settings_ranking_rules", "{response}");
+
+    // nothing was registered.
+    let (response, code) = server.index("test").get().await;
+    assert_eq!(code, 404, "{response}");
+    let (response, _) = server.tasks().await;
+    assert_eq!(response["total"], 0, "{response}");
+}
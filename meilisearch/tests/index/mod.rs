@@ -1,6 +1,7 @@
 mod create_index;
 mod delete_index;
 mod errors;
+mod export;
 mod get_index;
 mod stats;
 mod update_index;
@@ -16,6 +16,7 @@ async fn stats() {
     assert_eq!(code, 200);
     assert_eq!(response["numberOfDocuments"], 0);
     assert!(response["isIndexing"] == false);
+    assert_eq!(response["searchCount"], 0);
     assert!(response["fieldDistribution"].as_object().unwrap().is_empty());
 
     let documents = json!([
@@ -45,6 +46,57 @@ async fn stats() {
     assert_eq!(response["fieldDistribution"]["age"], 1);
 }
 
+#[actix_rt::test]
+async fn stats_count_searches() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (task, code) = index.add_documents(json!([{ "id": 1, "name": "Alexey" }]), None).await;
+    assert_eq!(code, 202);
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = index.stats().await;
+    assert_eq!(code, 200);
+    assert_eq!(response["searchCount"], 0);
+
+    let (_, code) = index.search_post(json!({ "q": "alexey" })).await;
+    assert_eq!(code, 200);
+    let (_, code) = index.search_get("q=alexey").await;
+    assert_eq!(code, 200);
+    let (_, code) = server.multi_search(json!({"queries": [{ "indexUid": "test" }]})).await;
+    assert_eq!(code, 200);
+
+    // A failing search is not counted.
+    let (_, code) = index.search_post(json!({ "filter": "name = Alexey" })).await;
+    assert_eq!(code, 400);
+
+    let (response, code) = index.stats().await;
+    assert_eq!(code, 200);
+    assert_eq!(response["searchCount"], 3);
+}
+
+#[actix_rt::test]
+async fn stats_search_count_follows_index_swap() {
+    let server = Server::new().await;
+    let a = server.index("a");
+    let (task, _) = a.add_documents(json!([{ "id": 1 }]), None).await;
+    a.wait_task(task.uid()).await;
+    let b = server.index("b");
+    let (task, _) = b.create(None).await;
+    b.wait_task(task.uid()).await;
+
+    let (_, code) = a.search_post(json!({})).await;
+    assert_eq!(code, 200);
+
+    let (task, code) = server.index_swap(json!([{ "indexes": ["a", "b"] }])).await;
+    assert_eq!(code, 202);
+    server.wait_task(task.uid()).await;
+
+    let (response, _) = a.stats().await;
+    assert_eq!(response["searchCount"], 0);
+    let (response, _) = b.stats().await;
+    assert_eq!(response["searchCount"], 1);
+}
+
 #[actix_rt::test]
 async fn error_get_stats_unexisting_index() {
     let server = Server::new().await;
@@ -60,3 +112,59 @@ async fn error_get_stats_unexisting_index() {
     assert_eq!(response, expected_response);
     assert_eq!(code, 404);
 }
+
+#[actix_rt::test]
+async fn lmdb_stats() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_response, code) = index.lmdb_stats().await;
+    assert_eq!(code, 404);
+
+    let documents = json!([
+        { "id": 1, "name": "Alexey" },
+        { "id": 2, "name": "Tamo" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202);
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = index.lmdb_stats().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response["onDiskSize"].as_u64().unwrap() > 0);
+    assert!(response["usedPagesSize"].as_u64().unwrap() > 0);
+    assert!(response["mapSize"].as_u64().unwrap() > 0);
+    assert_eq!(response["databases"]["documents"]["entries"], 2);
+    assert!(response["databases"]["main"]["entries"].as_u64().unwrap() > 0);
+    assert!(response["databases"]["word-docids"]["entries"].as_u64().unwrap() > 0);
+}
+
+#[actix_rt::test]
+#[cfg(target_os = "linux")]
+async fn stats_estimate_memory_size() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "name": "Alexey" },
+        { "id": 2, "name": "Tamo" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202);
+    index.wait_task(task.uid()).await;
+
+    // reading the index brings its pages in memory.
+    let (_, code) = index.search_post(json!({ "q": "alexey" })).await;
+    assert_eq!(code, 200);
+
+    let (response, code) = index.stats().await;
+    assert_eq!(code, 200, "{}", response);
+    let memory_size = response["memorySize"].as_u64().unwrap();
+
+    let (response, code) = index.lmdb_stats().await;
+    assert_eq!(code, 200, "{}", response);
+    let map_size = response["mapSize"].as_u64().unwrap();
+
+    assert!(memory_size > 0);
+    assert!(memory_size < map_size, "{memory_size} >= {map_size}");
+}
@@ -1,6 +1,8 @@
 use meili_snap::*;
+use meilisearch::Opt;
 
 use super::DOCUMENTS;
+use crate::common::server::default_settings;
 use crate::common::Server;
 use crate::json;
 
@@ -37,6 +39,21 @@ async fn search_unexisting_parameter() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_unknown_query_parameter() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_get("limitz=5").await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "bad_request");
+    assert!(
+        response["message"].as_str().unwrap().starts_with("Unknown parameter `limitz`"),
+        "{}",
+        response
+    );
+}
+
 #[actix_rt::test]
 async fn search_bad_q() {
     let server = Server::new().await;
@@ -1038,3 +1055,38 @@ async fn search_on_unknown_field_plus_joker() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn search_with_too_many_facets() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt { max_facets_per_search: Some(2), ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    let (response, _) =
+        index.update_settings(json!({"filterableAttributes": ["title", "id", "color"]})).await;
+    index.wait_task(response.uid()).await;
+    let (response, _) =
+        index.add_documents(json!([{ "id": 1, "title": "Shazam!", "color": "red" }]), None).await;
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.search_post(json!({"facets": ["title", "id"]})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["facetDistribution"].as_object().unwrap().len(), @"2");
+
+    let (response, code) = index.search_post(json!({"facets": ["title", "id", "color"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The search requests the distribution of 3 facets, but at most 2 facets can be requested by a single search.",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+
+    // the wildcard requests the distribution of every filterable attribute.
+    let (response, code) = index.search_post(json!({"facets": ["*"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_search_facets""###);
+}
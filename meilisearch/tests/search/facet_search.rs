@@ -1,4 +1,4 @@
-use meili_snap::snapshot;
+use meili_snap::{json_string, snapshot};
 use once_cell::sync::Lazy;
 
 use crate::common::{Server, Value};
@@ -139,3 +139,95 @@ async fn facet_search_dont_support_words() {
     assert_eq!(code, 200, "{}", response);
     assert_eq!(response["facetHits"].as_array().unwrap().len(), 0);
 }
+
+#[actix_rt::test]
+async fn facet_schema() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _) = index
+        .update_settings_filterable_attributes(json!([
+            "genres",
+            "price",
+            "available",
+            "address",
+            "year",
+            "color"
+        ]))
+        .await;
+    index.wait_task(task.uid()).await;
+
+    let documents = json!([
+        {
+            "id": 1,
+            "genres": ["Action", "Adventure"],
+            "price": 12.5,
+            "available": true,
+            "address": { "city": "Paris", "zip": 75000 },
+            "year": 2019,
+        },
+        {
+            "id": 2,
+            "genres": "Horror",
+            "price": 8,
+            "available": false,
+            "year": "unknown",
+        }
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = index.facet_schema().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "filterableAttributes": [
+        {
+          "name": "address",
+          "types": []
+        },
+        {
+          "name": "address.city",
+          "types": [
+            "string"
+          ]
+        },
+        {
+          "name": "address.zip",
+          "types": [
+            "number"
+          ]
+        },
+        {
+          "name": "available",
+          "types": [
+            "boolean"
+          ]
+        },
+        {
+          "name": "color",
+          "types": []
+        },
+        {
+          "name": "genres",
+          "types": [
+            "string"
+          ]
+        },
+        {
+          "name": "price",
+          "types": [
+            "number"
+          ]
+        },
+        {
+          "name": "year",
+          "types": [
+            "string",
+            "number"
+          ]
+        }
+      ]
+    }
+    "###);
+}
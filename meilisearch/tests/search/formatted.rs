@@ -568,3 +568,54 @@ async fn test_cjk_highlight() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn show_match_count() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "title": "the cat and the other cat", "description": "a cat sleeping" },
+        { "id": 2, "title": "a dog", "description": "a dog sleeping" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .search_post(json!({ "q": "cat", "attributesToHighlight": ["*"], "showMatchCount": true }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    let hits = response["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_json_snapshot!(hits[0], { "._rankingScore" => "[score]" }, @r###"
+    {
+      "id": 1,
+      "title": "the cat and the other cat",
+      "description": "a cat sleeping",
+      "_formatted": {
+        "id": "1",
+        "title": "the <em>cat</em> and the other <em>cat</em>",
+        "description": "a <em>cat</em> sleeping"
+      },
+      "_matchCount": 3
+    }
+    "###);
+
+    // the match count is the number of highlighted occurrences
+    let highlighted = hits[0]["_formatted"]
+        .as_object()
+        .unwrap()
+        .values()
+        .filter_map(|value| value.as_str())
+        .map(|value| value.matches("<em>").count())
+        .sum::<usize>();
+    assert_eq!(hits[0]["_matchCount"], highlighted);
+
+    // the matches position are only returned when requested
+    let (response, code) = index
+        .search_post(json!({ "q": "cat", "showMatchCount": true, "showMatchesPosition": true }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"][0]["_matchCount"], 3);
+    assert_eq!(response["hits"][0]["_matchesPosition"]["title"].as_array().unwrap().len(), 2);
+}
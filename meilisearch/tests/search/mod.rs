@@ -9,6 +9,7 @@ mod geo;
 mod multi;
 mod pagination;
 mod restrict_searchable;
+mod suggest;
 
 use once_cell::sync::Lazy;
 
@@ -124,6 +125,38 @@ async fn simple_placeholder_search() {
         .await;
 }
 
+#[actix_rt::test]
+async fn whitespace_only_query_is_a_placeholder_search() {
+    let server = Server::new().await;
+    let index = server.index("basic");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(json!({ "q": "   " }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 5);
+        })
+        .await;
+
+    index
+        .search(json!({ "q": "\u{a0} \t\u{3000}" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 5);
+        })
+        .await;
+
+    // the surrounding unicode whitespaces don't change the results of a query.
+    index
+        .search(json!({ "q": "\u{a0}Captain\u{a0}" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn simple_search() {
     let server = Server::new().await;
@@ -196,6 +229,44 @@ async fn test_kanji_language_detection() {
         .await;
 }
 
+#[cfg(feature = "default")]
+#[actix_rt::test]
+async fn search_with_locales() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "title": "東京のお寿司。" },
+        { "id": 1, "title": "东京是日本的首都，也是一个非常大的城市。" }
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    // The Japanese pipeline keeps the traditional kanji as is.
+    index
+        .search(json!({"q": "東京", "locales": ["jpn"]}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let ids: Vec<_> =
+                response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+            assert_eq!(ids, [&json!(0)]);
+        })
+        .await;
+
+    // The Chinese pipeline normalizes the query into simplified Chinese.
+    index
+        .search(json!({"q": "東京", "locales": ["cmn"]}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let ids: Vec<_> =
+                response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+            assert_eq!(ids, [&json!(1)]);
+        })
+        .await;
+
+    let (response, code) = index.search_post(json!({"q": "東京", "locales": ["klingon"]})).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response["code"], @r###""invalid_search_locales""###);
+}
+
 #[cfg(feature = "default")]
 #[actix_rt::test]
 async fn test_thai_language() {
@@ -362,6 +433,38 @@ async fn search_with_filter_array_notation() {
     assert_eq!(response["hits"].as_array().unwrap().len(), 3);
 }
 
+#[actix_rt::test]
+async fn search_with_filter_on_all_filterable_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (_, code) = index.update_settings(json!({"filterableAttributes": ["*"]})).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    let res = index.wait_task(1).await;
+    meili_snap::snapshot!(res["status"], @r###""succeeded""###);
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    meili_snap::snapshot!(response["filterableAttributes"], @r###"["*"]"###);
+
+    // the `title` field was never declared as filterable
+    let (response, code) = index.search_post(json!({ "filter": "title = Gläss" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+
+    // the fields introduced by new documents are filterable too
+    index.add_documents(json!([{ "id": "1", "title": "Nope", "color": "green" }]), None).await;
+    index.wait_task(2).await;
+    let (response, code) = index.search_post(json!({ "filter": "color = green" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+    assert_eq!(response["hits"][0]["id"], json!("1"));
+}
+
 #[actix_rt::test]
 async fn search_with_sort_on_numbers() {
     let server = Server::new().await;
@@ -567,6 +670,275 @@ async fn search_facet_distribution() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_disjunctive_facet_distribution() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["brand", "category"]})).await;
+    let documents = json!([
+        { "id": 1, "brand": "acme", "category": "shoes" },
+        { "id": 2, "brand": "acme", "category": "shirts" },
+        { "id": 3, "brand": "globex", "category": "shoes" },
+        { "id": 4, "brand": "initech", "category": "hats" },
+    ]);
+    let (response, _) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(
+            json!({
+                "filter": "brand = acme",
+                "facets": ["brand", "category"],
+                "disjunctiveFacets": true,
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(response["hits"].as_array().unwrap().len(), 2);
+                let dist = response["facetDistribution"].as_object().unwrap();
+                // the brand facet ignores the filter on the brand itself.
+                assert_eq!(dist["brand"], json!({ "acme": 2, "globex": 1, "initech": 1 }));
+                // while the category facet is narrowed by the brand filter.
+                assert_eq!(dist["category"], json!({ "shirts": 1, "shoes": 1 }));
+            },
+        )
+        .await;
+
+    // without disjunctive facets, every facet is narrowed by the whole filter.
+    index
+        .search(
+            json!({
+                "filter": "brand = acme",
+                "facets": ["brand", "category"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                let dist = response["facetDistribution"].as_object().unwrap();
+                assert_eq!(dist["brand"], json!({ "acme": 2 }));
+                assert_eq!(dist["category"], json!({ "shirts": 1, "shoes": 1 }));
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_sort_facet_values_by() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index
+        .update_settings(json!({
+            "filterableAttributes": ["color"],
+            "faceting": { "sortFacetValuesBy": { "*": "count" } },
+        }))
+        .await;
+    let documents = json!([
+        { "id": 1, "color": "yellow" },
+        { "id": 2, "color": "yellow" },
+        { "id": 3, "color": "yellow" },
+        { "id": 4, "color": "red" },
+        { "id": 5, "color": "red" },
+        { "id": 6, "color": "blue" },
+    ]);
+    let (response, _) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await;
+
+    // the index sorts the facet values by count.
+    index
+        .search(json!({ "facets": ["color"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let values: Vec<_> =
+                response["facetDistribution"]["color"].as_object().unwrap().keys().collect();
+            assert_eq!(values, ["yellow", "red", "blue"]);
+        })
+        .await;
+
+    // while the query can ask for them in alphabetical order.
+    index
+        .search(json!({ "facets": ["color"], "sortFacetValuesBy": "alpha" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let values: Vec<_> =
+                response["facetDistribution"]["color"].as_object().unwrap().keys().collect();
+            assert_eq!(values, ["blue", "red", "yellow"]);
+        })
+        .await;
+
+    // the direction reverses the order of the values.
+    index
+        .search(
+            json!({ "facets": ["color"], "sortFacetValuesBy": "alpha", "sortFacetValuesDirection": "desc" }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                let values: Vec<_> =
+                    response["facetDistribution"]["color"].as_object().unwrap().keys().collect();
+                assert_eq!(values, ["yellow", "red", "blue"]);
+            },
+        )
+        .await;
+    index
+        .search(
+            json!({ "facets": ["color"], "sortFacetValuesDirection": "asc" }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                let values: Vec<_> =
+                    response["facetDistribution"]["color"].as_object().unwrap().keys().collect();
+                assert_eq!(values, ["blue", "red", "yellow"]);
+            },
+        )
+        .await;
+
+    // the values are truncated after being reversed.
+    let (response, _) =
+        index.update_settings(json!({ "faceting": { "maxValuesPerFacet": 1 } })).await;
+    index.wait_task(response.uid()).await;
+    index
+        .search(
+            json!({ "facets": ["color"], "sortFacetValuesDirection": "asc" }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                let values: Vec<_> =
+                    response["facetDistribution"]["color"].as_object().unwrap().keys().collect();
+                assert_eq!(values, ["blue"]);
+            },
+        )
+        .await;
+
+    let (response, code) = index.search_post(json!({ "sortFacetValuesBy": "random" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_sort_facet_values_by");
+
+    let (response, code) = index.search_post(json!({ "sortFacetValuesDirection": "upward" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_sort_facet_values_direction");
+}
+
+#[actix_rt::test]
+async fn search_disabled_ranking_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "searchableAttributes": ["title", "description"] })).await;
+    let documents = json!([
+        // the words are in the best attribute but far from each other.
+        { "id": 1, "title": "hello", "description": "world" },
+        // the words are next to each other but in the worst attribute.
+        { "id": 2, "title": "nothing", "description": "hello world" },
+    ]);
+    let (response, _) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(json!({ "q": "hello world" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let ids: Vec<_> =
+                response["hits"].as_array().unwrap().iter().map(|h| &h["id"]).collect();
+            assert_eq!(ids, [&json!(2), &json!(1)]);
+        })
+        .await;
+
+    index
+        .search(
+            json!({ "q": "hello world", "disabledRankingRules": ["proximity"] }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                let ids: Vec<_> =
+                    response["hits"].as_array().unwrap().iter().map(|h| &h["id"]).collect();
+                assert_eq!(ids, [&json!(1), &json!(2)]);
+            },
+        )
+        .await;
+
+    let (response, code) =
+        index.search_post(json!({ "q": "hello", "disabledRankingRules": ["title:asc"] })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response["code"], @r###""invalid_search_disabled_ranking_rules""###);
+}
+
+#[actix_rt::test]
+async fn search_group_by() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "filterableAttributes": ["category", "id"] })).await;
+    let documents = json!([
+        { "id": 1, "title": "Dune", "category": "books" },
+        { "id": 2, "title": "Hyperion", "category": "books" },
+        { "id": 3, "title": "Foundation", "category": "books" },
+        { "id": 4, "title": "Celeste", "category": "games" },
+        { "id": 5, "title": "Hades", "category": "games" },
+        { "id": 6, "title": "Discovery", "category": "music" },
+    ]);
+    let (response, _) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(json!({ "groupBy": "category", "groupLimit": 2 }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let groups = response["groups"].as_array().unwrap();
+            let values: Vec<_> = groups.iter().map(|group| &group["value"]).collect();
+            assert_eq!(values, [&json!("books"), &json!("games"), &json!("music")]);
+            let totals: Vec<_> = groups.iter().map(|group| &group["totalHits"]).collect();
+            assert_eq!(totals, [&json!(3), &json!(2), &json!(1)]);
+            for group in groups {
+                let hits = group["hits"].as_array().unwrap();
+                assert!(hits.len() <= 2, "{}", response);
+                assert!(hits.iter().all(|hit| hit["category"] == group["value"]), "{}", response);
+            }
+            assert_eq!(groups[0]["hits"].as_array().unwrap().len(), 2);
+            assert_eq!(groups[2]["hits"].as_array().unwrap().len(), 1);
+        })
+        .await;
+
+    // the groups are made of the documents matching the filter of the search.
+    index
+        .search(json!({ "filter": "id != 1", "groupBy": "category" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["groups"][0]["value"], "books");
+            assert_eq!(response["groups"][0]["totalHits"], 2);
+            let ids: Vec<_> = response["groups"][0]["hits"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|hit| hit["id"].as_u64().unwrap())
+                .collect();
+            assert!(!ids.contains(&1), "{}", response);
+        })
+        .await;
+
+    // the number of groups is capped by `maxGroups`, the most populated groups being kept.
+    index
+        .search(json!({ "groupBy": "category", "maxGroups": 2 }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let groups = response["groups"].as_array().unwrap();
+            let values: Vec<_> = groups.iter().map(|group| &group["value"]).collect();
+            assert_eq!(values, [&json!("books"), &json!("games")]);
+        })
+        .await;
+
+    // the values are matched as they are, whatever the characters they contain.
+    let documents = json!([{ "id": 7, "title": "Oddities", "category": "back\\slash \"quoted\"" }]);
+    let (response, _) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await;
+    index
+        .search(json!({ "filter": "id = 7", "groupBy": "category" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["groups"][0]["value"], "back\\slash \"quoted\"");
+            assert_eq!(response["groups"][0]["hits"][0]["id"], 7, "{}", response);
+        })
+        .await;
+
+    let (response, code) = index.search_post(json!({ "groupBy": "title" })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "Attribute `title` is not filterable. The hits can only be grouped by a filterable attribute.",
+      "code": "invalid_search_group_by",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_group_by"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn displayed_attributes() {
     let server = Server::new().await;
@@ -584,6 +956,43 @@ async fn displayed_attributes() {
     assert!(response["hits"][0].get("title").is_some());
 }
 
+#[actix_rt::test]
+async fn displayed_attributes_with_exclusions() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.update_settings(json!({ "displayedAttributes": ["*", "-secret"] })).await;
+    assert_eq!(code, 202, "{}", response);
+
+    let documents = json!([
+        { "id": 1, "title": "Shazam!", "secret": "captain marvel" },
+        { "id": 2, "title": "Escape Room", "secret": "the room" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["displayedAttributes"], json!(["*", "-secret"]));
+
+    let (response, code) = index.search_post(json!({ "q": "room" })).await;
+    assert_eq!(code, 200, "{}", response);
+    let hits = response["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 1);
+    assert!(hits[0].get("title").is_some());
+    assert!(hits[0].get("id").is_some());
+    assert!(hits[0].get("secret").is_none());
+
+    let (response, code) =
+        index.search_post(json!({ "attributesToRetrieve": ["title", "secret"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    for hit in response["hits"].as_array().unwrap() {
+        assert!(hit.get("title").is_some());
+        assert!(hit.get("secret").is_none());
+    }
+}
+
 #[actix_rt::test]
 async fn placeholder_search_is_hard_limited() {
     let server = Server::new().await;
@@ -755,6 +1164,50 @@ async fn faceting_max_values_per_facet() {
         .await;
 }
 
+#[actix_rt::test]
+async fn faceting_max_values_per_facet_set_with_dedicated_route() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "filterableAttributes": ["number"] })).await;
+
+    let documents: Vec<_> = (0..20).map(|id| json!({ "id": id, "number": id * 10 })).collect();
+    index.add_documents(json!(documents), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.update_settings_faceting(json!({ "maxValuesPerFacet": 3 })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(2).await;
+
+    let (response, code) = server.service.get("/indexes/test/settings/faceting").await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["maxValuesPerFacet"], 3);
+
+    index
+        .search(json!({ "facets": ["number"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let numbers = response["facetDistribution"]["number"].as_object().unwrap();
+            assert_eq!(numbers.len(), 3);
+        })
+        .await;
+
+    let (response, code) = server.service.delete("/indexes/test/settings/faceting").await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(3).await;
+
+    let (response, code) = server.service.get("/indexes/test/settings/faceting").await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["maxValuesPerFacet"], 100);
+
+    index
+        .search(json!({ "facets": ["number"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let numbers = response["facetDistribution"]["number"].as_object().unwrap();
+            assert_eq!(numbers.len(), 20);
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn experimental_feature_score_details() {
     let server = Server::new().await;
@@ -1161,3 +1614,299 @@ async fn simple_search_with_strange_synonyms() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn search_with_bidirectional_synonyms() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "bidirectionalSynonyms": [["nyc", "new york"]] })).await;
+    let r = index.wait_task(0).await;
+    meili_snap::snapshot!(r["status"], @r###""succeeded""###);
+
+    let documents = json!([
+        { "id": 1, "title": "Living in nyc" },
+        { "id": 2, "title": "A trip to new york" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({"q": "nyc", "attributesToRetrieve": ["id"]}), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 2
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "new york", "attributesToRetrieve": ["id"]}), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2
+              },
+              {
+                "id": 1
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    let (response, code) = index.settings().await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["bidirectionalSynonyms"]), @r###"
+    [
+      [
+        "nyc",
+        "new york"
+      ]
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_within_ids() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents: Vec<_> =
+        (0..20).map(|i| json!({ "id": i, "text": format!("I am document {i}") })).collect();
+    index.add_documents(documents.into(), None).await;
+    index.wait_task(0).await;
+
+    let within_ids = ["2", "5", "8", "13", "21"];
+
+    index
+        .search(json!({ "q": "document", "withinIds": within_ids }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let hits = response["hits"].as_array().unwrap();
+            // the id `21` does not exist and is ignored.
+            assert_eq!(hits.len(), 4);
+            for hit in hits {
+                let id = hit["id"].to_string();
+                assert!(within_ids.contains(&id.as_str()), "{} is not in the requested ids", id);
+            }
+        })
+        .await;
+
+    index
+        .search(json!({ "withinIds": within_ids }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let hits = response["hits"].as_array().unwrap();
+            assert_eq!(hits.len(), 4);
+            for hit in hits {
+                let id = hit["id"].to_string();
+                assert!(within_ids.contains(&id.as_str()), "{} is not in the requested ids", id);
+            }
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_numbers_not_searchable() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _) = index
+        .update_settings(json!({ "searchableNumbers": false, "filterableAttributes": ["year"] }))
+        .await;
+    index.wait_task(task.uid()).await;
+
+    let documents = json!([
+        { "id": 1, "title": "The best of", "year": 2021 },
+        { "id": 2, "title": "Released in 2021", "year": 1999 },
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    // only the number written in the text is found.
+    index
+        .search(json!({"q": "2021", "attributesToRetrieve": ["id"]}), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    index
+        .search(
+            json!({"filter": "year = 2021", "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_ranking_stages() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    index
+        .search(json!({"q": "the captain marvel"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert!(response.get("rankingStages").is_none(), "{}", response);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "the captain marvel", "showRankingStages": true}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let stages = response["rankingStages"].as_array().unwrap();
+            assert!(!stages.is_empty(), "{}", response);
+            assert_eq!(stages[0]["rankingRule"], "words");
+
+            let candidates: Vec<u64> =
+                stages.iter().map(|stage| stage["candidates"].as_u64().unwrap()).collect();
+            assert!(candidates.windows(2).all(|w| w[0] >= w[1]), "{:?}", candidates);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_tie_breaking_sort() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "title": "The same title" },
+        { "id": 3, "title": "The same title" },
+        { "id": 2, "title": "The same title" },
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    let (task, _) = index.update_settings(json!({ "tieBreakingSort": ["id:desc"] })).await;
+    index.wait_task(task.uid()).await;
+
+    for _ in 0..5 {
+        index
+            .search(json!({"q": "same title", "attributesToRetrieve": ["id"]}), |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 3
+                  },
+                  {
+                    "id": 2
+                  },
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+            })
+            .await;
+    }
+
+    let (task, _) = index.update_settings(json!({ "tieBreakingSort": ["words"] })).await;
+    let response = index.wait_task(task.uid()).await;
+    meili_snap::snapshot!(response["error"]["code"], @r###""invalid_settings_tie_breaking_sort""###);
+}
+
+#[actix_rt::test]
+async fn search_with_processed_query() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    let (task, _) = index
+        .update_settings(json!({ "stopWords": ["the"], "synonyms": { "marvel": ["wonder"] } }))
+        .await;
+    index.wait_task(task.uid()).await;
+
+    index
+        .search(json!({"q": "The Captain marvel"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert!(response.get("processedQuery").is_none(), "{}", response);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "The Captain marvel", "showProcessedQuery": true}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            meili_snap::snapshot!(meili_snap::json_string!(response["processedQuery"]), @r###"
+            [
+              {
+                "term": "captain",
+                "synonyms": []
+              },
+              {
+                "term": "marvel",
+                "synonyms": [
+                  "wonder"
+                ]
+              }
+            ]
+            "###);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_msgpack_response() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    let query = json!({ "q": "captain", "showRankingScore": true });
+    let (Value(mut json_response), code) = index.search_post(query.clone()).await;
+    assert_eq!(code, 200, "{}", json_response);
+
+    // the two searches may not take the same time
+    json_response["processingTimeMs"] = serde_json::Value::from(0);
+
+    for accept in [
+        "application/msgpack",
+        "Application/MsgPack",
+        "application/x-msgpack",
+        "text/html, application/x-msgpack;q=0.9",
+    ] {
+        let req = actix_web::test::TestRequest::post()
+            .uri("/indexes/test/search")
+            .set_json(&query.0)
+            .insert_header(("Accept", accept));
+        let (body, code) = server.service.request_bytes(req).await;
+        assert_eq!(code, 200, "{accept}");
+        let mut msgpack_response: serde_json::Value = rmp_serde::from_slice(&body).unwrap();
+
+        msgpack_response["processingTimeMs"] = serde_json::Value::from(0);
+        assert_eq!(msgpack_response, json_response, "{accept}");
+        assert_eq!(msgpack_response["hits"][0]["title"], "Captain Marvel");
+    }
+}
@@ -1,7 +1,7 @@
 use meili_snap::{json_string, snapshot};
 
 use super::{DOCUMENTS, NESTED_DOCUMENTS};
-use crate::common::Server;
+use crate::common::{Server, Value};
 use crate::json;
 
 #[actix_rt::test]
@@ -341,3 +341,31 @@ async fn search_multiple_query_errors() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn multi_search_with_msgpack_response() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    let query = json!({ "queries": [{ "indexUid": "test", "q": "captain" }] });
+    let (Value(mut json_response), code) = server.multi_search(query.clone()).await;
+    assert_eq!(code, 200, "{}", json_response);
+
+    let req = actix_web::test::TestRequest::post()
+        .uri("/multi-search")
+        .set_json(&query.0)
+        .insert_header(("Accept", "application/msgpack"));
+    let (body, code) = server.service.request_bytes(req).await;
+    assert_eq!(code, 200);
+    let mut msgpack_response: serde_json::Value = rmp_serde::from_slice(&body).unwrap();
+
+    // the two searches may not take the same time
+    json_response["results"][0]["processingTimeMs"] = serde_json::Value::from(0);
+    msgpack_response["results"][0]["processingTimeMs"] = serde_json::Value::from(0);
+    assert_eq!(msgpack_response, json_response);
+    assert_eq!(msgpack_response["results"][0]["hits"][0]["title"], "Captain Marvel");
+}
@@ -163,3 +163,50 @@ async fn ensure_placeholder_search_hit_count_valid() {
             .await;
     }
 }
+
+#[actix_rt::test]
+async fn max_total_hits_set_with_dedicated_route() {
+    let server = Server::new().await;
+    let index = server.index("basic");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.update_settings_pagination(json!({ "maxTotalHits": 2 })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = server.service.get("/indexes/basic/settings/pagination").await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response, json!({ "maxTotalHits": 2 }));
+
+    index
+        .search(json!({}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 2);
+        })
+        .await;
+
+    index
+        .search(json!({ "offset": 1, "limit": 5 }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+        })
+        .await;
+
+    let (response, code) = server.service.delete("/indexes/basic/settings/pagination").await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(2).await;
+
+    let (response, code) = server.service.get("/indexes/basic/settings/pagination").await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response, json!({ "maxTotalHits": 1000 }));
+
+    index
+        .search(json!({}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 5);
+        })
+        .await;
+}
@@ -0,0 +1,77 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn suggest_terms_by_frequency() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "title": "marvel movies" },
+        { "id": 2, "title": "marvelous mars" },
+        { "id": 3, "title": "mars attacks marvel" },
+        { "id": 4, "title": "martian on mars" },
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = index.suggest("prefix=MAR").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[time]" }), @r###"
+    {
+      "suggestions": [
+        {
+          "term": "mars",
+          "count": 3
+        },
+        {
+          "term": "marvel",
+          "count": 2
+        },
+        {
+          "term": "martian",
+          "count": 1
+        },
+        {
+          "term": "marvelous",
+          "count": 1
+        }
+      ],
+      "prefix": "MAR",
+      "processingTimeMs": "[time]"
+    }
+    "###);
+
+    let (response, code) = index.suggest("prefix=mar&limit=2").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "term": "mars",
+        "count": 3
+      },
+      {
+        "term": "marvel",
+        "count": 2
+      }
+    ]
+    "###);
+
+    let (response, code) = index.suggest("prefix=zorro").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @"[]");
+
+    let (response, code) = index.suggest("prefix=mar&limit=many").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_suggest_limit""###);
+
+    let (response, code) = index.suggest("limit=2").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""missing_suggest_prefix""###);
+
+    let (response, code) = index.suggest("prefix=").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_suggest_prefix""###);
+}
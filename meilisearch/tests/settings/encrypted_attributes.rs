@@ -0,0 +1,155 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+
+use crate::common::server::default_settings;
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn encrypted_attributes_are_not_stored_in_plaintext() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        encryption_key: Some(String::from("a very secret key")),
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    index.create(Some("id")).await;
+    index.update_settings(json!({ "encryptedAttributes": ["email"] })).await;
+    index
+        .add_documents(json!([{ "id": 1, "name": "jean", "email": "jean.bob@example.com" }]), None)
+        .await;
+    let response = index.wait_task(2).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    // no file of the indexes contains the plaintext value
+    for entry in walkdir::WalkDir::new(temp.path().join("db").join("indexes")) {
+        let entry = entry.unwrap();
+        if entry.file_type().is_file() {
+            let content = std::fs::read(entry.path()).unwrap();
+            assert!(
+                !content.windows(b"jean.bob".len()).any(|window| window == b"jean.bob"),
+                "{} contains the plaintext value",
+                entry.path().display()
+            );
+        }
+    }
+
+    let (response, code) = index.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "name": "jean",
+      "email": "jean.bob@example.com"
+    }
+    "###);
+
+    // the encrypted attribute is not searchable but is returned decrypted
+    let (response, _) = index.search_post(json!({ "q": "example" })).await;
+    snapshot!(json_string!(response["hits"]), @"[]");
+    let (response, _) = index.search_post(json!({ "q": "jean" })).await;
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "name": "jean",
+        "email": "jean.bob@example.com"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn encrypted_attributes_cannot_be_filterable() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        encryption_key: Some(String::from("a very secret key")),
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    index
+        .update_settings(json!({
+            "encryptedAttributes": ["email"],
+            "filterableAttributes": ["email"],
+        }))
+        .await;
+    let response = index.wait_task(0).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The `email` attribute cannot be encrypted as it is used by the `filterableAttributes` setting. Encrypted attributes cannot be searchable, filterable, sortable or used as the primary key.",
+      "code": "invalid_settings_encrypted_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_encrypted_attributes"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn nested_encrypted_attributes_are_rejected() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        encryption_key: Some(String::from("a very secret key")),
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    index.update_settings(json!({ "encryptedAttributes": ["contact.email"] })).await;
+    let response = index.wait_task(0).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The `contact.email` attribute cannot be encrypted as it is nested. Only top-level attributes can be encrypted.",
+      "code": "invalid_settings_encrypted_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_encrypted_attributes"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn encrypted_attributes_cannot_be_the_inferred_primary_key() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        encryption_key: Some(String::from("a very secret key")),
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    index.update_settings(json!({ "encryptedAttributes": ["user_id"] })).await;
+    index.add_documents(json!([{ "user_id": 1, "name": "jean" }]), None).await;
+    let response = index.wait_task(1).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The `user_id` attribute cannot be encrypted as it is used by the `primaryKey` setting. Encrypted attributes cannot be searchable, filterable, sortable or used as the primary key.",
+      "code": "invalid_settings_encrypted_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_encrypted_attributes"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn encrypted_attributes_require_an_encryption_key() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "encryptedAttributes": ["email"] })).await;
+    let response = index.wait_task(0).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The index has encrypted attributes but no encryption key is configured. The `--encryption-key` option must be set.",
+      "code": "missing_encryption_key",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_encryption_key"
+    }
+    "###);
+}
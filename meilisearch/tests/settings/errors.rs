@@ -31,6 +31,26 @@ async fn settings_bad_displayed_attributes() {
     "###);
 }
 
+#[actix_rt::test]
+async fn settings_misspelled_field() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "stopWordss": ["the"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""bad_request""###);
+    let message = response["message"].as_str().unwrap();
+    assert!(
+        message.starts_with("Unknown field `stopWordss`: did you mean `stopWords`?"),
+        "{message}"
+    );
+
+    // nothing has been enqueued
+    let (response, code) = server.tasks().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["results"], @"[]");
+}
+
 #[actix_rt::test]
 async fn settings_bad_searchable_attributes() {
     let server = Server::new().await;
@@ -143,6 +163,29 @@ async fn settings_bad_ranking_rules() {
     "###);
 }
 
+#[actix_rt::test]
+async fn settings_invalid_ranking_rules_do_not_create_a_task() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "rankingRules": ["manyTheFish"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rankingRules[0]`: `manyTheFish` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, exactness and custom ranking rules.",
+      "code": "invalid_settings_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_ranking_rules"
+    }
+    "###);
+
+    let (response, code) = server.tasks().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @"[]");
+    let (_response, code) = index.get().await;
+    snapshot!(code, @"404 Not Found");
+}
+
 #[actix_rt::test]
 async fn settings_bad_stop_words() {
     let server = Server::new().await;
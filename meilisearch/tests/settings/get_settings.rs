@@ -15,11 +15,17 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
         "ranking_rules",
         json!(["words", "typo", "proximity", "attribute", "sort", "exactness"]),
     );
+    map.insert("tie_breaking_sort", json!([]));
+    map.insert("encrypted_attributes", json!([]));
     map.insert("stop_words", json!([]));
     map.insert("non_separator_tokens", json!([]));
     map.insert("separator_tokens", json!([]));
     map.insert("dictionary", json!([]));
+    map.insert("case_sensitive", json!(false));
+    map.insert("languages", json!([]));
+    map.insert("searchable_numbers", json!(true));
     map.insert("synonyms", json!({}));
+    map.insert("bidirectional_synonyms", json!([]));
     map.insert(
         "faceting",
         json!({
@@ -29,12 +35,25 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
             }
         }),
     );
+    map.insert(
+        "typo_tolerance",
+        json!({
+            "enabled": true,
+            "minWordSizeForTypos": {
+                "oneTypo": 5,
+                "twoTypos": 9
+            },
+            "disableOnWords": [],
+            "disableOnAttributes": []
+        }),
+    );
     map.insert(
         "pagination",
         json!({
             "maxTotalHits": json!(1000),
         }),
     );
+    map.insert("pinned_in_memory", json!(false));
     map
 });
 
@@ -45,6 +64,14 @@ async fn get_settings_unexisting_index() {
     assert_eq!(code, 404, "{}", response)
 }
 
+#[actix_rt::test]
+async fn get_available_ranking_rules() {
+    let server = Server::new().await;
+    let (response, code) = server.service.get("/ranking-rules/available").await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response, json!(["words", "typo", "proximity", "attribute", "sort", "exactness"]));
+}
+
 #[actix_rt::test]
 async fn get_settings() {
     let server = Server::new().await;
@@ -54,7 +81,7 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 14);
+    assert_eq!(settings.keys().len(), 21);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
@@ -64,10 +91,30 @@ async fn get_settings() {
         settings["rankingRules"],
         json!(["words", "typo", "proximity", "attribute", "sort", "exactness"])
     );
+    assert_eq!(settings["tieBreakingSort"], json!([]));
+    assert_eq!(settings["encryptedAttributes"], json!([]));
     assert_eq!(settings["stopWords"], json!([]));
     assert_eq!(settings["nonSeparatorTokens"], json!([]));
     assert_eq!(settings["separatorTokens"], json!([]));
     assert_eq!(settings["dictionary"], json!([]));
+    assert_eq!(settings["caseSensitive"], json!(false));
+    assert_eq!(settings["languages"], json!([]));
+    assert_eq!(settings["searchableNumbers"], json!(true));
+    assert_eq!(settings["synonyms"], json!({}));
+    assert_eq!(settings["bidirectionalSynonyms"], json!([]));
+    assert_eq!(settings["pinnedInMemory"], json!(false));
+    assert_eq!(
+        settings["typoTolerance"],
+        json!({
+            "enabled": true,
+            "minWordSizeForTypos": {
+                "oneTypo": 5,
+                "twoTypos": 9
+            },
+            "disableOnWords": [],
+            "disableOnAttributes": []
+        })
+    );
     assert_eq!(
         settings["faceting"],
         json!({
@@ -281,10 +328,18 @@ test_setting_routes!(
     separator_tokens put,
     non_separator_tokens put,
     dictionary put,
+    case_sensitive put,
+    languages put,
+    searchable_numbers put,
     ranking_rules put,
+    tie_breaking_sort put,
+    encrypted_attributes put,
     synonyms put,
+    bidirectional_synonyms put,
     pagination patch,
-    faceting patch
+    faceting patch,
+    typo_tolerance patch,
+    pinned_in_memory put
 );
 
 #[actix_rt::test]
@@ -325,3 +380,126 @@ async fn set_and_reset_distinct_attribute_with_dedicated_route() {
 
     assert_eq!(response, json!(null));
 }
+
+#[actix_rt::test]
+async fn set_and_reset_typo_tolerance_with_dedicated_route() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings_typo_tolerance(json!({
+            "enabled": false,
+            "minWordSizeForTypos": { "oneTypo": 4 },
+            "disableOnWords": ["doggo"],
+            "disableOnAttributes": ["title"]
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(0).await;
+
+    let (response, code) = server.service.get("/indexes/test/settings/typo-tolerance").await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response,
+        json!({
+            "enabled": false,
+            "minWordSizeForTypos": {
+                "oneTypo": 4,
+                "twoTypos": 9
+            },
+            "disableOnWords": ["doggo"],
+            "disableOnAttributes": ["title"]
+        })
+    );
+
+    let (response, code) = server.service.delete("/indexes/test/settings/typo-tolerance").await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, _) = index.settings().await;
+    assert_eq!(response["typoTolerance"], DEFAULT_SETTINGS_VALUES["typo_tolerance"]);
+}
+
+#[actix_rt::test]
+async fn validate_settings_warns_about_unknown_fields() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _) = index
+        .add_documents(json!([{ "id": 1, "title": "Nemo", "meta": { "year": 2003 } }]), None)
+        .await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = index
+        .validate_settings(json!({
+            "searchableAttributes": ["title", "overview"],
+            "filterableAttributes": ["meta.year"],
+            "displayedAttributes": ["*", "-secret"],
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "warnings": [
+        {
+          "setting": "searchableAttributes",
+          "field": "overview",
+          "message": "The field `overview` referenced in `searchableAttributes` is not present in any document."
+        }
+      ]
+    }
+    "###);
+
+    // the settings are only validated, not applied.
+    let (response, _) = index.settings().await;
+    assert_eq!(response["searchableAttributes"], json!(["*"]));
+}
+
+#[actix_rt::test]
+async fn settings_task_reports_the_reindexed_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents: Vec<_> = (0..100)
+        .map(|id| serde_json::json!({ "id": id, "title": format!("movie {id}") }))
+        .collect();
+    let (task, _) = index.add_documents(json!(documents), None).await;
+    index.wait_task(task.uid()).await;
+
+    let (task, _) = index.update_settings(json!({ "searchableAttributes": ["title"] })).await;
+    let response = index.wait_task(task.uid()).await;
+    assert_eq!(response["status"], "succeeded");
+    assert_eq!(response["details"]["reindexedDocuments"], 100);
+    assert_eq!(response["details"]["changedSettings"], json!(["searchableAttributes"]));
+
+    // setting the same value again doesn't change anything
+    let (task, _) = index.update_settings(json!({ "searchableAttributes": ["title"] })).await;
+    let response = index.wait_task(task.uid()).await;
+    assert_eq!(response["status"], "succeeded");
+    assert_eq!(response["details"]["changedSettings"], json!([]));
+
+    // the displayed attributes don't require any reindexing
+    let (task, _) = index.update_settings(json!({ "displayedAttributes": ["title"] })).await;
+    let response = index.wait_task(task.uid()).await;
+    assert_eq!(response["status"], "succeeded");
+    assert_eq!(response["details"]["reindexedDocuments"], 0);
+    assert_eq!(response["details"]["changedSettings"], json!(["displayedAttributes"]));
+}
+
+#[actix_rt::test]
+async fn pin_index_in_memory() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, code) = index.update_settings(json!({ "pinnedInMemory": true })).await;
+    assert_eq!(code, 202, "{}", task);
+    let response = index.wait_task(task.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    let (response, _) = index.settings().await;
+    assert_eq!(response["pinnedInMemory"], json!(true));
+
+    let (response, code) = index.update_settings(json!({ "pinnedInMemory": "yes" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_settings_pinned_in_memory");
+}
@@ -1,4 +1,5 @@
 mod distinct;
+mod encrypted_attributes;
 mod errors;
 mod get_settings;
 mod tokenizer_customization;
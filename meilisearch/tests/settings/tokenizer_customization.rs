@@ -465,3 +465,170 @@ async fn advanced_synergies() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn case_sensitive() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "title": "iOS" },
+        { "id": 2, "title": "ios" },
+        { "id": 3, "title": "Éclair" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    // by default the case is ignored
+    index
+        .search(json!({"q": "iOS", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 2
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    let (_response, _code) = index.update_settings(json!({ "caseSensitive": true })).await;
+    index.wait_task(1).await;
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["caseSensitive"]), @"true");
+
+    index
+        .search(json!({"q": "iOS", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "ios", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    // only the case folding is disabled, the diacritics are still removed
+    index
+        .search(json!({"q": "Eclair", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 3
+              }
+            ]
+            "###);
+        })
+        .await;
+}
+
+#[cfg(feature = "default")]
+#[actix_rt::test]
+async fn languages() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_response, _code) = index.update_settings(json!({ "languages": ["jpn"] })).await;
+    index.wait_task(0).await;
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["languages"]), @r###"
+    [
+      "jpn"
+    ]
+    "###);
+
+    // without the setting this title would be detected as Chinese
+    // and normalized into simplified Chinese.
+    let documents = json!([
+        { "id": 1, "title": "東京大学" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({"q": "東京", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "东京", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @"[]");
+        })
+        .await;
+
+    let (_response, _code) = index.update_settings(json!({ "languages": ["klingon"] })).await;
+    let response = index.wait_task(2).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""invalid_settings_languages""###);
+
+    // the declared Japanese doesn't cover the Latin script, which is still detected.
+    let documents = json!([
+        { "id": 2, "title": "Tokyo university" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(3).await;
+
+    index
+        .search(json!({"q": "university", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    // an empty list is a reset.
+    let (_response, _code) = index.update_settings(json!({ "languages": [] })).await;
+    index.wait_task(4).await;
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["languages"]), @"[]");
+
+    index
+        .search(json!({"q": "东京", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              }
+            ]
+            "###);
+        })
+        .await;
+}
@@ -184,3 +184,28 @@ async fn perform_on_demand_snapshot() {
         server.index("doggo").settings(),
     );
 }
+
+#[actix_rt::test]
+async fn successive_snapshots_replace_the_previous_one() {
+    let temp = tempfile::tempdir().unwrap();
+    let snapshot_dir = tempfile::tempdir().unwrap();
+
+    let options =
+        Opt { snapshot_dir: snapshot_dir.path().to_owned(), ..default_settings(temp.path()) };
+
+    let server = Server::new_with_options(options).await.unwrap();
+
+    for _ in 0..3 {
+        let (task, code) = server.create_snapshot().await;
+        snapshot!(code, @"202 Accepted");
+        let task = server.wait_task(task.uid()).await;
+        snapshot!(task["status"], @r###""succeeded""###);
+    }
+
+    // A snapshot is always written over the previous one, they never accumulate.
+    let snapshots: Vec<String> = std::fs::read_dir(&snapshot_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path().file_name().unwrap().to_str().unwrap().to_string())
+        .collect();
+    meili_snap::snapshot!(format!("{snapshots:?}"), @r###"["db.snapshot"]"###);
+}
@@ -74,3 +74,28 @@ async fn stats() {
     assert_eq!(response["indexes"]["test"]["fieldDistribution"]["name"], 1);
     assert_eq!(response["indexes"]["test"]["fieldDistribution"]["age"], 1);
 }
+
+#[actix_rt::test]
+async fn stats_of_multiple_indexes() {
+    let server = Server::new().await;
+    let first = server.index("first");
+    let (response, _) = first.add_documents(json!([{ "id": 1 }, { "id": 2 }]), None).await;
+    first.wait_task(response.uid()).await;
+    let second = server.index("second");
+    let (response, _) = second.add_documents(json!([{ "id": 1, "doggo": "kefir" }]), None).await;
+    second.wait_task(response.uid()).await;
+
+    let (response, code) = server.stats().await;
+    assert_eq!(code, 200, "{}", response);
+
+    let indexes = response["indexes"].as_object().unwrap();
+    assert_eq!(indexes.keys().collect::<Vec<_>>(), ["first", "second"]);
+    assert_eq!(indexes["first"]["numberOfDocuments"], 2);
+    assert_eq!(indexes["second"]["numberOfDocuments"], 1);
+
+    assert!(response["databaseSize"].as_u64().unwrap() > 0);
+
+    // the last update is the one of the most recently updated index.
+    let (second_index, _) = second.get().await;
+    assert_eq!(response["lastUpdate"], second_index["updatedAt"]);
+}
@@ -1,9 +1,12 @@
 mod errors;
 
+use actix_web::body::MessageBody;
 use meili_snap::insta::assert_json_snapshot;
+use meilisearch::Opt;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
+use crate::common::server::default_settings;
 use crate::common::Server;
 use crate::json;
 
@@ -46,6 +49,29 @@ async fn get_task_status() {
     // TODO check response format, as per #48
 }
 
+#[actix_rt::test]
+async fn fetch_tasks_by_uids() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.add_documents(json!([{ "id": 1, "content": "foobar" }]), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = server.service.post("/tasks/fetch", json!({ "uids": [1, 42, 0] })).await;
+    assert_eq!(code, 200, "{response}");
+    let results = response["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["uid"], 1);
+    assert_eq!(results[0]["type"], "documentAdditionOrUpdate");
+    assert!(results[1].is_null());
+    assert_eq!(results[2]["uid"], 0);
+    assert_eq!(results[2]["type"], "indexCreation");
+
+    let (response, code) = server.service.post("/tasks/fetch", json!({})).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], "missing_task_uids");
+}
+
 #[actix_rt::test]
 async fn list_tasks() {
     let server = Server::new().await;
@@ -60,6 +86,44 @@ async fn list_tasks() {
     assert_eq!(response["results"].as_array().unwrap().len(), 2);
 }
 
+#[actix_rt::test]
+async fn list_tasks_paginated() {
+    let server = Server::new().await;
+    for i in 0..50 {
+        let (task, code) = server.index(format!("test-{i}")).create(None).await;
+        assert_eq!(code, 202, "{}", task);
+    }
+
+    let (response, code) = server.tasks_filter("limit=20").await;
+    assert_eq!(code, 200, "{}", response);
+    let uids: Vec<_> = response["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["uid"].as_u64().unwrap())
+        .collect();
+    assert_eq!(uids, (30..50).rev().collect::<Vec<_>>());
+    assert_eq!(response["limit"], 20);
+    assert_eq!(response["from"], 49);
+    assert_eq!(response["next"], 29);
+
+    let (response, code) = server.tasks_filter("limit=20&from=29").await;
+    assert_eq!(code, 200, "{}", response);
+    let uids: Vec<_> = response["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["uid"].as_u64().unwrap())
+        .collect();
+    assert_eq!(uids, (10..30).rev().collect::<Vec<_>>());
+    assert_eq!(response["next"], 9);
+
+    let (response, code) = server.tasks_filter("limit=20&from=9").await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["results"].as_array().unwrap().len(), 10);
+    assert_eq!(response["next"], json!(null));
+}
+
 #[actix_rt::test]
 async fn list_tasks_with_star_filters() {
     let server = Server::new().await;
@@ -131,6 +195,58 @@ async fn list_tasks_status_filtered() {
     assert_eq!(response["results"].as_array().unwrap().len(), 2);
 }
 
+#[actix_rt::test]
+async fn list_tasks_statuses_and_index_filtered() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    // no primary key can be inferred from these documents.
+    let (task, _) = index.add_documents(json!([{ "name": "kefir" }]), None).await;
+    index.wait_task(task.uid()).await;
+    let (task, _) = server.index("other").create(None).await;
+    server.wait_task(task.uid()).await;
+
+    let (response, code) = server.tasks_filter("statuses=failed&indexUids=other").await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["results"], json!([]));
+
+    let (response, code) = server.tasks_filter("statuses=failed,canceled&indexUids=test").await;
+    assert_eq!(code, 200, "{}", response);
+    let results = response["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["type"], "documentAdditionOrUpdate");
+
+    let (response, code) = server.tasks_filter("statuses=failed,succeeded&indexUids=test").await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["results"].as_array().unwrap().len(), 2);
+
+    let (response, code) = server.tasks_filter("statuses=succeeded&indexUids=test,other").await;
+    assert_eq!(code, 200, "{}", response);
+    let index_uids: Vec<_> =
+        response["results"].as_array().unwrap().iter().map(|task| &task["indexUid"]).collect();
+    assert_eq!(index_uids, [&json!("other"), &json!("test")]);
+}
+
+#[actix_rt::test]
+async fn list_tasks_uids_filtered() {
+    let server = Server::new().await;
+    for index_uid in ["test", "other", "test2"] {
+        let (task, _) = server.index(index_uid).create(None).await;
+        server.wait_task(task.uid()).await;
+    }
+
+    // the task 42 doesn't exist and the task 1 belongs to another index
+    let (response, code) = server.tasks_filter("uids=0,42,1,2&indexUids=test,test2").await;
+    assert_eq!(code, 200, "{}", response);
+    let uids: Vec<_> =
+        response["results"].as_array().unwrap().iter().map(|task| &task["uid"]).collect();
+    assert_eq!(uids, [&json!(2), &json!(0)]);
+
+    let (response, code) = server.tasks_filter("uids=42,43").await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["results"], json!([]));
+}
+
 #[actix_rt::test]
 async fn list_tasks_type_filtered() {
     let server = Server::new().await;
@@ -149,6 +265,12 @@ async fn list_tasks_type_filtered() {
         index.filtered_tasks(&["indexCreation", "documentAdditionOrUpdate"], &[], &[]).await;
     assert_eq!(code, 200, "{}", response);
     assert_eq!(response["results"].as_array().unwrap().len(), 2);
+
+    // the task types are matched case-insensitively.
+    let (response, code) =
+        server.tasks_filter("types=INDEXCREATION,documentadditionorupdate").await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["results"].as_array().unwrap().len(), 2);
 }
 
 #[actix_rt::test]
@@ -656,6 +778,12 @@ async fn test_summarized_settings_update() {
       "type": "settingsUpdate",
       "canceledBy": null,
       "details": {
+        "reindexedDocuments": 0,
+        "changedSettings": [
+          "displayedAttributes",
+          "filterableAttributes",
+          "sortableAttributes"
+        ],
         "displayedAttributes": [
           "doggos",
           "name"
@@ -1038,6 +1166,27 @@ async fn test_summarized_task_cancelation() {
     "###);
 }
 
+#[actix_web::test]
+async fn cancel_finished_task_leaves_it_untouched() {
+    let server = Server::new().await;
+    let index = server.index("doggos");
+    index.create(None).await;
+    index.wait_task(0).await;
+    let (response, code) = server.cancel_tasks("uids=0").await;
+    assert_eq!(code, 200, "{}", response);
+    index.wait_task(response.uid()).await;
+
+    let (task, code) = index.get_task(0).await;
+    assert_eq!(code, 200, "{}", task);
+    assert_eq!(task["status"], "succeeded");
+    assert_eq!(task["canceledBy"], json!(null));
+
+    let (task, _) = index.get_task(response.uid()).await;
+    assert_eq!(task["status"], "succeeded");
+    assert_eq!(task["details"]["matchedTasks"], 1);
+    assert_eq!(task["details"]["canceledTasks"], 0);
+}
+
 #[actix_web::test]
 async fn test_summarized_task_deletion() {
     let server = Server::new().await;
@@ -1097,3 +1246,163 @@ async fn test_summarized_dump_creation() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn oldest_finished_tasks_are_deleted_when_max_task_history_is_reached() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt { max_task_history: 10, ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+
+    for i in 0..10 {
+        let (task, code) = server.index(format!("test-{i}")).create(None).await;
+        assert_eq!(code, 202, "{}", task);
+        server.wait_task(task.uid()).await;
+    }
+
+    // the task queue is full, the oldest finished tasks must have been deleted.
+    let (task, code) = server.index("test-10").create(None).await;
+    assert_eq!(code, 202, "{}", task);
+    let response = server.wait_task(task.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    // only the oldest tasks exceeding the history are deleted.
+    for uid in 0..2 {
+        let (response, code) = server.get_task(uid).await;
+        assert_eq!(code, 404, "{}", response);
+    }
+    for uid in 2..10 {
+        let (response, code) = server.get_task(uid).await;
+        assert_eq!(code, 200, "{}", response);
+        assert_eq!(response["status"], "succeeded", "{}", response);
+    }
+
+    let (response, code) = server.get_task(task.uid()).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["status"], "succeeded", "{}", response);
+}
+
+#[actix_rt::test]
+async fn abort_enqueued_tasks() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents: Vec<_> = (0..1000)
+        .map(|id| serde_json::json!({ "id": id, "title": format!("title {id}") }))
+        .collect();
+    let (task, code) = index.add_documents(json!(documents), None).await;
+    assert_eq!(code, 202, "{}", task);
+    let mut uids = vec![task.uid()];
+    // tasks on other indexes are never batched with the document addition.
+    for i in 0..5 {
+        let (task, code) = server.index(format!("test{i}")).create(None).await;
+        assert_eq!(code, 202, "{}", task);
+        uids.push(task.uid());
+    }
+
+    let (response, code) = server.abort_tasks().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["type"], "taskCancelation", "{}", response);
+    // at most the document addition started processing.
+    let aborted = response["abortedTasks"].as_u64().unwrap();
+    assert!(aborted >= 5, "{}", response);
+    let response = server.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["canceledTasks"], aborted, "{}", response);
+
+    // the tasks that were not aborted were already processing or processed.
+    let mut canceled = 0;
+    for uid in uids {
+        let response = server.wait_task(uid).await;
+        match response["status"].as_str().unwrap() {
+            "canceled" => canceled += 1,
+            status => assert_eq!(status, "succeeded", "{}", response),
+        }
+    }
+    assert_eq!(canceled, aborted);
+}
+
+#[actix_rt::test]
+async fn abort_without_enqueued_tasks() {
+    let server = Server::new().await;
+
+    let (response, code) = server.abort_tasks().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response, json!({ "abortedTasks": 0 }));
+
+    // no task cancelation is registered.
+    let (response, code) = server.tasks().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["total"], 0, "{}", response);
+}
+
+/// Read the task events of the stream until the given status is received,
+/// returns the raw stream and the received events.
+async fn read_task_events_until(
+    events: &mut std::pin::Pin<Box<actix_web::body::BoxBody>>,
+    status: &str,
+) -> (String, Vec<serde_json::Value>) {
+    let mut received = String::new();
+    let mut parsed = Vec::new();
+    while !parsed.iter().any(|event: &serde_json::Value| event["status"] == status) {
+        let chunk = tokio::time::timeout(
+            std::time::Duration::from_secs(10),
+            std::future::poll_fn(|cx| events.as_mut().poll_next(cx)),
+        )
+        .await
+        .expect("no task event received")
+        .unwrap()
+        .unwrap();
+        received.push_str(std::str::from_utf8(&chunk).unwrap());
+
+        parsed = received
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .map(|data| serde_json::from_str(data).unwrap())
+            .collect();
+    }
+    (received, parsed)
+}
+
+#[actix_rt::test]
+async fn task_events_stream_the_status_transitions() {
+    let server = Server::new().await;
+
+    let (events, code) = server.task_events("indexUids=test", None).await;
+    assert_eq!(code, 200);
+    let mut events = Box::pin(events);
+
+    let index = server.index("test");
+    let (task, code) = index.create(None).await;
+    assert_eq!(code, 202, "{}", task);
+    // a task on another index must not be streamed.
+    let (other, _) = server.index("other").create(None).await;
+
+    let (received, events) = read_task_events_until(&mut events, "succeeded").await;
+    let statuses: Vec<_> = events
+        .iter()
+        .inspect(|event| assert_ne!(event["uid"], other.uid()))
+        .map(|event| event["status"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(statuses, ["enqueued", "processing", "succeeded"], "{received}");
+    assert!(received.starts_with("id: 0-enqueued\n"), "{received}");
+}
+
+#[actix_rt::test]
+async fn task_events_resume_from_the_last_event_id() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+    let (task, code) = index.create(None).await;
+    assert_eq!(code, 202, "{}", task);
+    index.wait_task(task.uid()).await;
+
+    // the client only received the enqueued event, the missed ones are retrieved from the tasks.
+    let (events, code) = server.task_events("indexUids=test", Some("0-enqueued")).await;
+    assert_eq!(code, 200);
+    let mut events = Box::pin(events);
+
+    let (received, events) = read_task_events_until(&mut events, "succeeded").await;
+    assert_eq!(events.len(), 1, "{received}");
+    assert!(received.contains("id: 0-succeeded\n"), "{received}");
+}
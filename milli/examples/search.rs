@@ -58,6 +58,8 @@ fn main() -> Result<(), Box<dyn Error>> {
                 false,
                 &None,
                 &None,
+                None,
+                &None,
                 GeoSortStrategy::default(),
                 0,
                 20,
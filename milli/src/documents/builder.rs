@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::io::{self, Write};
 
 use grenad::{CompressionType, WriterBuilder};
@@ -33,6 +34,10 @@ pub struct DocumentsBatchBuilder<W> {
     /// The number of documents that were added to this builder,
     /// it doesn't take the primary key of the documents into account at this point.
     documents_count: u32,
+    /// The primary key for which an UUID is generated when a document is missing it.
+    generated_primary_key: Option<String>,
+    /// The number of documents for which a primary key value was generated.
+    generated_ids_count: u32,
 
     /// A buffer to store a temporary obkv buffer and avoid reallocating.
     obkv_buffer: Vec<u8>,
@@ -47,6 +52,8 @@ impl<W: Write> DocumentsBatchBuilder<W> {
             writer: WriterBuilder::new().compression_type(CompressionType::None).build(writer),
             fields_index: DocumentsBatchIndex::default(),
             documents_count: 0,
+            generated_primary_key: None,
+            generated_ids_count: 0,
             obkv_buffer: Vec::new(),
             value_buffer: Vec::new(),
         }
@@ -57,8 +64,31 @@ impl<W: Write> DocumentsBatchBuilder<W> {
         self.documents_count
     }
 
+    /// Returns the number of documents for which a primary key value was generated.
+    pub fn generated_ids_count(&self) -> u32 {
+        self.generated_ids_count
+    }
+
+    /// Generates an UUID v4 as the value of the given primary key for the documents
+    /// that are missing it or where it is `null`.
+    pub fn generate_missing_ids(&mut self, primary_key: impl Into<String>) {
+        self.generated_primary_key = Some(primary_key.into());
+    }
+
     /// Appends a new JSON object into the batch and updates the `DocumentsBatchIndex` accordingly.
     pub fn append_json_object(&mut self, object: &Object) -> io::Result<()> {
+        match &self.generated_primary_key {
+            Some(primary_key) if object.get(primary_key).map_or(true, Value::is_null) => {
+                let mut object = object.clone();
+                object.insert(primary_key.clone(), Value::String(generate_id()));
+                self.generated_ids_count += 1;
+                self.write_json_object(&object)
+            }
+            _ => self.write_json_object(object),
+        }
+    }
+
+    fn write_json_object(&mut self, object: &Object) -> io::Result<()> {
         // Make sure that we insert the fields ids in order as the obkv writer has this requirement.
         let mut fields_ids: Vec<_> = object.keys().map(|k| self.fields_index.insert(k)).collect();
         fields_ids.sort_unstable();
@@ -97,6 +127,20 @@ impl<W: Write> DocumentsBatchBuilder<W> {
             .map(|(k, t)| (self.fields_index.insert(k), t))
             .enumerate()
             .collect();
+
+        // The primary key for which we must generate the missing values. When there is no such
+        // column we register one out of the record bounds, it will always be considered empty.
+        let generated_field_id = match &self.generated_primary_key {
+            Some(primary_key) => {
+                let field_id = self.fields_index.insert(primary_key);
+                if typed_fields_ids.iter().all(|(_, (fid, _))| *fid != field_id) {
+                    typed_fields_ids.push((usize::MAX, (field_id, AllowedType::String)));
+                }
+                Some(field_id)
+            }
+            None => None,
+        };
+
         // Make sure that we insert the fields ids in order as the obkv writer has this requirement.
         typed_fields_ids.sort_unstable_by_key(|(_, (fid, _))| *fid);
 
@@ -113,8 +157,15 @@ impl<W: Write> DocumentsBatchBuilder<W> {
             for (i, (field_id, type_)) in typed_fields_ids.iter() {
                 self.value_buffer.clear();
 
-                let value = &record[*i];
+                let value = record.get(*i).unwrap_or_default();
                 let trimmed_value = value.trim();
+                if generated_field_id == Some(*field_id) && trimmed_value.is_empty() {
+                    to_writer(&mut self.value_buffer, &generate_id())?;
+                    self.generated_ids_count += 1;
+                    writer.insert(*field_id, &self.value_buffer)?;
+                    continue;
+                }
+
                 match type_ {
                     AllowedType::Number => {
                         if trimmed_value.is_empty() {
@@ -176,6 +227,22 @@ impl<W: Write> DocumentsBatchBuilder<W> {
         Ok(())
     }
 
+    /// Renames the fields of all the documents inserted into this builder.
+    ///
+    /// Returns an error if a field is renamed into the name of another field.
+    pub fn rename_fields(&mut self, renames: &BTreeMap<String, String>) -> Result<(), Error> {
+        let mut fields_index = DocumentsBatchIndex::default();
+        for (field_id, name) in self.fields_index.iter() {
+            let name = renames.get(name).unwrap_or(name);
+            if fields_index.0.insert_no_overwrite(*field_id, name.clone()).is_err() {
+                return Err(Error::ConflictingFieldRename { field: name.clone() });
+            }
+        }
+        self.fields_index = fields_index;
+
+        Ok(())
+    }
+
     /// Flushes the content on disk and stores the final version of the `DocumentsBatchIndex`.
     pub fn into_inner(mut self) -> io::Result<W> {
         let DocumentsBatchBuilder { mut writer, fields_index, .. } = self;
@@ -189,6 +256,10 @@ impl<W: Write> DocumentsBatchBuilder<W> {
     }
 }
 
+fn generate_id() -> String {
+    uuid::Uuid::new_v4().hyphenated().to_string()
+}
+
 #[derive(Debug)]
 enum AllowedType {
     String,
@@ -278,6 +349,69 @@ mod test {
         assert!(cursor.next_document().unwrap().is_none());
     }
 
+    #[test]
+    fn rename_fields() {
+        let json = json!({ "id": 1, "Product Name": "hello!", "Price": 10 });
+
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        builder.append_json_object(json.as_object().unwrap()).unwrap();
+
+        let renames = BTreeMap::from([
+            ("Product Name".to_string(), "title".to_string()),
+            ("Price".to_string(), "price".to_string()),
+        ]);
+        builder.rename_fields(&renames).unwrap();
+        let vector = builder.into_inner().unwrap();
+
+        let (mut cursor, index) = DocumentsBatchReader::from_reader(Cursor::new(vector))
+            .unwrap()
+            .into_cursor_and_fields_index();
+
+        let doc = cursor.next_document().unwrap().unwrap();
+        let val = obkv_to_object(&doc, &index).map(Value::from).unwrap();
+        assert_eq!(val, json!({ "id": 1, "title": "hello!", "price": 10 }));
+
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        builder.append_json_object(json.as_object().unwrap()).unwrap();
+        let renames = BTreeMap::from([("Product Name".to_string(), "id".to_string())]);
+        assert!(matches!(
+            builder.rename_fields(&renames),
+            Err(Error::ConflictingFieldRename { field }) if field == "id"
+        ));
+    }
+
+    #[test]
+    fn generate_missing_ids() {
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        builder.generate_missing_ids("id");
+        builder.append_json_object(json!({ "id": 1, "title": "a" }).as_object().unwrap()).unwrap();
+        builder.append_json_object(json!({ "title": "b" }).as_object().unwrap()).unwrap();
+        builder
+            .append_json_object(json!({ "id": null, "title": "c" }).as_object().unwrap())
+            .unwrap();
+        let csv = csv::Reader::from_reader(Cursor::new("title,id\nd,\ne,2"));
+        builder.append_csv(csv).unwrap();
+        let csv = csv::Reader::from_reader(Cursor::new("title\nf"));
+        builder.append_csv(csv).unwrap();
+        assert_eq!(builder.documents_count(), 6);
+        assert_eq!(builder.generated_ids_count(), 4);
+        let vector = builder.into_inner().unwrap();
+
+        let (mut cursor, index) = DocumentsBatchReader::from_reader(Cursor::new(vector))
+            .unwrap()
+            .into_cursor_and_fields_index();
+        let mut ids = Vec::new();
+        while let Some(doc) = cursor.next_document().unwrap() {
+            let doc = obkv_to_object(&doc, &index).unwrap();
+            ids.push(doc["id"].clone());
+        }
+        assert_eq!(ids[0], json!(1));
+        assert_eq!(ids[4], json!("2"));
+        for id in [&ids[1], &ids[2], &ids[3], &ids[5]] {
+            assert!(uuid::Uuid::parse_str(id.as_str().unwrap()).is_ok());
+        }
+    }
+
     #[test]
     fn simple_csv_document() {
         let csv_content = r#"city,country,pop
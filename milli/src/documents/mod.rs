@@ -97,6 +97,8 @@ pub enum Error {
     InvalidDocumentFormat,
     #[error("Invalid enriched data.")]
     InvalidEnrichedData,
+    #[error("Cannot rename a field into `{field}` as another field already has this name.")]
+    ConflictingFieldRename { field: String },
     #[error(transparent)]
     InvalidUtf8(#[from] Utf8Error),
     #[error(transparent)]
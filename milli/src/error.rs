@@ -103,6 +103,8 @@ A document identifier can be of type integer or string, \
 only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and underscores (_).", .document_id.to_string()
     )]
     InvalidDocumentId { document_id: Value },
+    #[error("The document with the id: `{document_id}` is present more than once in the batch.")]
+    DuplicateDocumentId { document_id: String },
     #[error("Invalid facet distribution, {}", format_invalid_filter_distribution(.invalid_facets_name, .valid_facets_name))]
     InvalidFacetsDistribution {
         invalid_facets_name: BTreeSet<String>,
@@ -182,6 +184,16 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     UnknownInternalDocumentId { document_id: DocumentId },
     #[error("`minWordSizeForTypos` setting is invalid. `oneTypo` and `twoTypos` fields should be between `0` and `255`, and `twoTypos` should be greater or equals to `oneTypo` but found `oneTypo: {0}` and twoTypos: {1}`.")]
     InvalidMinTypoWordLenSetting(u8, u8),
+    #[error("Unknown language `{0}` in the `languages` setting.")]
+    InvalidLanguage(String),
+    #[error("The `{0}` rule of the `tieBreakingSort` setting is invalid. Only `attribute:asc` and `attribute:desc` rules are accepted.")]
+    InvalidTieBreakingSort(String),
+    #[error("The `{field}` attribute cannot be encrypted as it is used by the `{setting}` setting. Encrypted attributes cannot be searchable, filterable, sortable or used as the primary key.")]
+    InvalidEncryptedAttribute { field: String, setting: &'static str },
+    #[error("The `encryptedAttributes` setting can only be changed while the index contains no documents.")]
+    EncryptedAttributesOnNonEmptyIndex,
+    #[error("The `{0}` attribute cannot be encrypted as it is nested. Only top-level attributes can be encrypted.")]
+    NestedEncryptedAttribute(String),
 }
 
 #[derive(Error, Debug)]
@@ -10,6 +10,15 @@ pub enum FacetType {
     Number,
 }
 
+/// The type of the values held by a faceted field, as seen in the documents.
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FacetValueType {
+    String,
+    Number,
+    Boolean,
+}
+
 impl fmt::Display for FacetType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -3,18 +3,22 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::File;
 use std::mem::size_of;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 
-use charabia::{Language, Script};
+use charabia::normalizer::NormalizerOption;
+use charabia::{Language, Normalize, Script};
+use fst::{Automaton, IntoStreamer, Streamer};
 use heed::flags::Flags;
 use heed::types::*;
 use heed::{CompactionOption, Database, PolyDatabase, RoTxn, RwTxn};
+use memmap2::{Mmap, MmapOptions};
 use roaring::RoaringBitmap;
 use rstar::RTree;
 use time::OffsetDateTime;
 
 use crate::distance::NDotProductPoint;
 use crate::error::{InternalError, UserError};
-use crate::facet::FacetType;
+use crate::facet::{FacetType, FacetValueType};
 use crate::fields_ids_map::FieldsIdsMap;
 use crate::heed_codec::facet::{
     FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec,
@@ -37,11 +41,15 @@ pub type Hnsw = instant_distance::Hnsw<NDotProductPoint>;
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
 pub const DEFAULT_MIN_WORD_LEN_TWO_TYPOS: u8 = 9;
 
+/// The maximum number of words starting with a prefix that are looked up to suggest terms.
+pub const MAX_SUGGESTION_WORDS: usize = 1000;
+
 pub mod main_key {
     pub const CRITERIA_KEY: &str = "criteria";
     pub const DISPLAYED_FIELDS_KEY: &str = "displayed-fields";
     pub const DISTINCT_FIELD_KEY: &str = "distinct-field-key";
     pub const DOCUMENTS_IDS_KEY: &str = "documents-ids";
+    pub const ENCRYPTED_FIELDS_KEY: &str = "encrypted-fields";
     pub const SOFT_DELETED_DOCUMENTS_IDS_KEY: &str = "soft-deleted-documents-ids";
     pub const HIDDEN_FACETED_FIELDS_KEY: &str = "hidden-faceted-fields";
     pub const FILTERABLE_FIELDS_KEY: &str = "filterable-fields";
@@ -64,9 +72,14 @@ pub mod main_key {
     pub const NON_SEPARATOR_TOKENS_KEY: &str = "non-separator-tokens";
     pub const SEPARATOR_TOKENS_KEY: &str = "separator-tokens";
     pub const DICTIONARY_KEY: &str = "dictionary";
+    pub const CASE_SENSITIVE_KEY: &str = "case-sensitive";
+    pub const LANGUAGES_KEY: &str = "languages";
+    pub const SEARCHABLE_NUMBERS_KEY: &str = "searchable-numbers";
     pub const STRING_FACETED_DOCUMENTS_IDS_PREFIX: &str = "string-faceted-documents-ids";
     pub const SYNONYMS_KEY: &str = "synonyms";
+    pub const TIE_BREAKING_SORT_KEY: &str = "tie-breaking-sort";
     pub const USER_DEFINED_SYNONYMS_KEY: &str = "user-defined-synonyms";
+    pub const BIDIRECTIONAL_SYNONYMS_KEY: &str = "bidirectional-synonyms";
     pub const WORDS_FST_KEY: &str = "words-fst";
     pub const WORDS_PREFIXES_FST_KEY: &str = "words-prefixes-fst";
     pub const CREATED_AT_KEY: &str = "created-at";
@@ -79,6 +92,7 @@ pub mod main_key {
     pub const MAX_VALUES_PER_FACET: &str = "max-values-per-facet";
     pub const SORT_FACET_VALUES_BY: &str = "sort-facet-values-by";
     pub const PAGINATION_MAX_TOTAL_HITS: &str = "pagination-max-total-hits";
+    pub const PINNED_IN_MEMORY: &str = "pinned-in-memory";
 }
 
 pub mod db_name {
@@ -178,6 +192,9 @@ pub struct Index {
 
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<OwnedType<BEU32>, ObkvCodec>,
+
+    /// A read-only mapping of the LMDB file, kept while the index is pinned in memory.
+    pub(crate) pinned_mapping: Arc<Mutex<Option<Mmap>>>,
 }
 
 impl Index {
@@ -238,7 +255,7 @@ impl Index {
 
         Index::set_creation_dates(&env, main, created_at, updated_at)?;
 
-        Ok(Index {
+        let index = Index {
             env,
             main,
             word_docids,
@@ -265,7 +282,11 @@ impl Index {
             field_id_docid_facet_strings,
             vector_id_docid,
             documents,
-        })
+            pinned_mapping: Default::default(),
+        };
+        index.refresh_pinned_pages()?;
+
+        Ok(index)
     }
 
     pub fn new<P: AsRef<Path>>(options: heed::EnvOpenOptions, path: P) -> Result<Index> {
@@ -323,6 +344,76 @@ impl Index {
         Ok(self.env.real_disk_size()?)
     }
 
+    /// Returns the number of entries stored in each database of the index.
+    pub fn databases_entries(&self, rtxn: &RoTxn) -> Result<BTreeMap<&'static str, u64>> {
+        let Index {
+            env: _,
+            main,
+            word_docids,
+            exact_word_docids,
+            word_prefix_docids,
+            exact_word_prefix_docids,
+            word_pair_proximity_docids,
+            word_prefix_pair_proximity_docids,
+            prefix_word_pair_proximity_docids,
+            word_position_docids,
+            word_fid_docids,
+            field_id_word_count_docids,
+            word_prefix_position_docids,
+            word_prefix_fid_docids,
+            script_language_docids,
+            facet_id_exists_docids,
+            facet_id_is_null_docids,
+            facet_id_is_empty_docids,
+            facet_id_f64_docids,
+            facet_id_string_docids,
+            facet_id_normalized_string_strings,
+            facet_id_string_fst,
+            field_id_docid_facet_f64s,
+            field_id_docid_facet_strings,
+            vector_id_docid,
+            documents,
+            pinned_mapping: _,
+        } = self;
+
+        Ok(BTreeMap::from([
+            (db_name::MAIN, main.len(rtxn)?),
+            (db_name::WORD_DOCIDS, word_docids.len(rtxn)?),
+            (db_name::EXACT_WORD_DOCIDS, exact_word_docids.len(rtxn)?),
+            (db_name::WORD_PREFIX_DOCIDS, word_prefix_docids.len(rtxn)?),
+            (db_name::EXACT_WORD_PREFIX_DOCIDS, exact_word_prefix_docids.len(rtxn)?),
+            (db_name::WORD_PAIR_PROXIMITY_DOCIDS, word_pair_proximity_docids.len(rtxn)?),
+            (
+                db_name::WORD_PREFIX_PAIR_PROXIMITY_DOCIDS,
+                word_prefix_pair_proximity_docids.len(rtxn)?,
+            ),
+            (
+                db_name::PREFIX_WORD_PAIR_PROXIMITY_DOCIDS,
+                prefix_word_pair_proximity_docids.len(rtxn)?,
+            ),
+            (db_name::WORD_POSITION_DOCIDS, word_position_docids.len(rtxn)?),
+            (db_name::WORD_FIELD_ID_DOCIDS, word_fid_docids.len(rtxn)?),
+            (db_name::FIELD_ID_WORD_COUNT_DOCIDS, field_id_word_count_docids.len(rtxn)?),
+            (db_name::WORD_PREFIX_POSITION_DOCIDS, word_prefix_position_docids.len(rtxn)?),
+            (db_name::WORD_PREFIX_FIELD_ID_DOCIDS, word_prefix_fid_docids.len(rtxn)?),
+            (db_name::SCRIPT_LANGUAGE_DOCIDS, script_language_docids.len(rtxn)?),
+            (db_name::FACET_ID_EXISTS_DOCIDS, facet_id_exists_docids.len(rtxn)?),
+            (db_name::FACET_ID_IS_NULL_DOCIDS, facet_id_is_null_docids.len(rtxn)?),
+            (db_name::FACET_ID_IS_EMPTY_DOCIDS, facet_id_is_empty_docids.len(rtxn)?),
+            (db_name::FACET_ID_F64_DOCIDS, facet_id_f64_docids.len(rtxn)?),
+            (db_name::FACET_ID_STRING_DOCIDS, facet_id_string_docids.len(rtxn)?),
+            (
+                db_name::FACET_ID_NORMALIZED_STRING_STRINGS,
+                facet_id_normalized_string_strings.len(rtxn)?,
+            ),
+            (db_name::FACET_ID_STRING_FST, facet_id_string_fst.len(rtxn)?),
+            (db_name::FIELD_ID_DOCID_FACET_F64S, field_id_docid_facet_f64s.len(rtxn)?),
+            (db_name::FIELD_ID_DOCID_FACET_STRINGS, field_id_docid_facet_strings.len(rtxn)?),
+            (db_name::VECTOR_ID_DOCID, vector_id_docid.len(rtxn)?),
+            (db_name::DOCUMENTS, documents.len(rtxn)?),
+        ]))
+    }
+
     /// Returns the map size the underlying environment was opened with, in bytes.
     ///
     /// This value does not represent the current on-disk size of the index.
@@ -641,6 +732,15 @@ impl Index {
     /// Identical to `displayed_fields`, but returns the ids instead.
     pub fn displayed_fields_ids(&self, rtxn: &RoTxn) -> Result<Option<Vec<FieldId>>> {
         match self.displayed_fields(rtxn)? {
+            Some(fields) if fields.contains(&"*") => {
+                let fields_ids_map = self.fields_ids_map(rtxn)?;
+                let fields_ids = fields_ids_map
+                    .iter()
+                    .filter(|(_, name)| crate::is_displayed(name, &fields))
+                    .map(|(id, _)| id)
+                    .collect();
+                Ok(Some(fields_ids))
+            }
             Some(fields) => {
                 let fields_ids_map = self.fields_ids_map(rtxn)?;
                 let mut fields_ids = Vec::new();
@@ -667,8 +767,7 @@ impl Index {
         let fields_len = valid_fields.len();
 
         if let Some(dn) = self.displayed_fields(rtxn)? {
-            let displayable_names = dn.iter().map(|s| s.to_string()).collect();
-            valid_fields = &valid_fields & &displayable_names;
+            valid_fields.retain(|field| crate::is_displayed(field, &dn));
         }
 
         let hidden_fields = fields_len > valid_fields.len();
@@ -797,7 +896,26 @@ impl Index {
     }
 
     /// Returns the filterable fields names.
+    ///
+    /// When the filterable fields contain the `*` wildcard every known field is filterable,
+    /// except the encrypted ones.
     pub fn filterable_fields(&self, rtxn: &RoTxn) -> heed::Result<HashSet<String>> {
+        let mut fields = self.user_defined_filterable_fields(rtxn)?;
+        if fields.remove("*") {
+            let encrypted_fields = self.encrypted_fields(rtxn)?;
+            let fields_ids_map = self.fields_ids_map(rtxn)?;
+            fields.extend(
+                fields_ids_map
+                    .names()
+                    .filter(|name| !crate::is_faceted(name, &encrypted_fields))
+                    .map(String::from),
+            );
+        }
+        Ok(fields)
+    }
+
+    /// Returns the filterable fields names as defined by the user, the `*` wildcard included.
+    pub fn user_defined_filterable_fields(&self, rtxn: &RoTxn) -> heed::Result<HashSet<String>> {
         Ok(self
             .main
             .get::<_, Str, SerdeJson<_>>(rtxn, main_key::FILTERABLE_FIELDS_KEY)?
@@ -819,6 +937,55 @@ impl Index {
         Ok(fields_ids)
     }
 
+    /// Returns the filterable fields along with the types of the values found in the documents.
+    ///
+    /// The booleans are faceted as strings, a field is considered boolean when its
+    /// only string values are `true` and `false`.
+    pub fn filterable_fields_value_types(
+        &self,
+        rtxn: &RoTxn,
+    ) -> Result<BTreeMap<String, BTreeSet<FacetValueType>>> {
+        let filterable_fields = self.filterable_fields(rtxn)?;
+        let fields_ids_map = self.fields_ids_map(rtxn)?;
+
+        let mut fields = BTreeMap::new();
+        for name in &filterable_fields {
+            if fields_ids_map.id(name).is_none() {
+                // no document contains this field yet
+                fields.insert(name.clone(), BTreeSet::new());
+            }
+        }
+
+        for (field_id, name) in fields_ids_map.iter() {
+            if !crate::is_faceted(name, &filterable_fields) {
+                continue;
+            }
+
+            let mut types = BTreeSet::new();
+            if !self.faceted_documents_ids(rtxn, field_id, FacetType::Number)?.is_empty() {
+                types.insert(FacetValueType::Number);
+            }
+            if !self.faceted_documents_ids(rtxn, field_id, FacetType::String)?.is_empty() {
+                let fst = self.facet_id_string_fst.get(rtxn, &BEU16::new(field_id))?;
+                let is_boolean = fst.map_or(false, |fst| {
+                    ["true", "false"].iter().filter(|b| fst.contains(b)).count() == fst.len()
+                });
+                types.insert(if is_boolean {
+                    FacetValueType::Boolean
+                } else {
+                    FacetValueType::String
+                });
+            }
+
+            // The objects containing the filterable fields are only listed when declared.
+            if !types.is_empty() || filterable_fields.contains(name) {
+                fields.insert(name.to_string(), types);
+            }
+        }
+
+        Ok(fields)
+    }
+
     /* sortable fields */
 
     /// Writes the sortable fields names in the database.
@@ -893,10 +1060,13 @@ impl Index {
         let filterable_fields = self.filterable_fields(rtxn)?;
         let sortable_fields = self.sortable_fields(rtxn)?;
         let distinct_field = self.distinct_field(rtxn)?;
+        let tie_breaking_sort = self.tie_breaking_sort(rtxn)?;
         let asc_desc_fields =
-            self.criteria(rtxn)?.into_iter().filter_map(|criterion| match criterion {
-                Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
-                _otherwise => None,
+            self.criteria(rtxn)?.into_iter().chain(tie_breaking_sort).filter_map(|criterion| {
+                match criterion {
+                    Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
+                    _otherwise => None,
+                }
             });
 
         let mut faceted_fields = filterable_fields;
@@ -1039,6 +1209,56 @@ impl Index {
         }
     }
 
+    /* tie breaking sort */
+
+    pub(crate) fn put_tie_breaking_sort(
+        &self,
+        wtxn: &mut RwTxn,
+        criteria: &[Criterion],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<&[Criterion]>>(
+            wtxn,
+            main_key::TIE_BREAKING_SORT_KEY,
+            &criteria,
+        )
+    }
+
+    pub(crate) fn delete_tie_breaking_sort(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::TIE_BREAKING_SORT_KEY)
+    }
+
+    /// Returns the `Asc`/`Desc` criteria applied after the ranking rules to order
+    /// the documents that are still tied. By default no tie breaking sort is applied.
+    pub fn tie_breaking_sort(&self, rtxn: &RoTxn) -> heed::Result<Vec<Criterion>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<Vec<Criterion>>>(rtxn, main_key::TIE_BREAKING_SORT_KEY)?
+            .unwrap_or_default())
+    }
+
+    /* encrypted fields */
+
+    pub(crate) fn put_encrypted_fields(
+        &self,
+        wtxn: &mut RwTxn,
+        fields: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(wtxn, main_key::ENCRYPTED_FIELDS_KEY, fields)
+    }
+
+    pub(crate) fn delete_encrypted_fields(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::ENCRYPTED_FIELDS_KEY)
+    }
+
+    /// Returns the fields whose values are encrypted before being stored in the index.
+    /// These fields are never searchable nor faceted.
+    pub fn encrypted_fields(&self, rtxn: &RoTxn) -> heed::Result<BTreeSet<String>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<_>>(rtxn, main_key::ENCRYPTED_FIELDS_KEY)?
+            .unwrap_or_default())
+    }
+
     /* words fst */
 
     /// Writes the FST which is the words dictionary of the engine.
@@ -1058,6 +1278,47 @@ impl Index {
         }
     }
 
+    /// Returns the words starting with the given prefix along with the number of candidates
+    /// containing them, the most frequent words first.
+    ///
+    /// Only the first [`MAX_SUGGESTION_WORDS`] words starting with the prefix are considered.
+    pub fn word_suggestions(
+        &self,
+        rtxn: &RoTxn,
+        prefix: &str,
+        candidates: &RoaringBitmap,
+        limit: usize,
+    ) -> Result<Vec<(String, u64)>> {
+        // The prefix must be normalized the same way the words were when indexed.
+        let options = NormalizerOption { lossy: !self.case_sensitive(rtxn)?, ..Default::default() };
+        let prefix = prefix.trim().normalize(&options);
+
+        let words_fst = self.words_fst(rtxn)?;
+        let automaton = fst::automaton::Str::new(&prefix).starts_with();
+        let mut stream = words_fst.search(automaton).into_stream();
+        let mut suggestions = Vec::new();
+        let mut scanned_words = 0;
+        while let Some(word) = stream.next() {
+            scanned_words += 1;
+            if scanned_words > MAX_SUGGESTION_WORDS {
+                break;
+            }
+            let word = std::str::from_utf8(word)?;
+            if let Some(docids) = self.word_docids.get(rtxn, word)? {
+                let count = candidates.intersection_len(&docids);
+                if count > 0 {
+                    suggestions.push((word.to_string(), count));
+                }
+            }
+        }
+
+        suggestions.sort_unstable_by(|(lword, lcount), (rword, rcount)| {
+            rcount.cmp(lcount).then_with(|| lword.cmp(rword))
+        });
+        suggestions.truncate(limit);
+        Ok(suggestions)
+    }
+
     /* stop words */
 
     pub(crate) fn put_stop_words<A: AsRef<[u8]>>(
@@ -1198,6 +1459,28 @@ impl Index {
             .unwrap_or_default())
     }
 
+    /* bidirectional synonyms */
+
+    pub(crate) fn put_bidirectional_synonyms(
+        &self,
+        wtxn: &mut RwTxn,
+        groups: &[Vec<String>],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<_>>(wtxn, main_key::BIDIRECTIONAL_SYNONYMS_KEY, groups)
+    }
+
+    pub(crate) fn delete_bidirectional_synonyms(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::BIDIRECTIONAL_SYNONYMS_KEY)
+    }
+
+    /// Returns the groups of words that are all synonyms of each other.
+    pub fn bidirectional_synonyms(&self, rtxn: &RoTxn) -> heed::Result<Vec<Vec<String>>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeBincode<_>>(rtxn, main_key::BIDIRECTIONAL_SYNONYMS_KEY)?
+            .unwrap_or_default())
+    }
+
     pub fn words_synonyms<S: AsRef<str>>(
         &self,
         rtxn: &RoTxn,
@@ -1334,6 +1617,69 @@ impl Index {
         Ok(())
     }
 
+    /// Returns `true` if the documents and queries keep their original case once tokenized,
+    /// the other normalizations are still applied. By default the index is case insensitive.
+    pub fn case_sensitive(&self, txn: &RoTxn) -> heed::Result<bool> {
+        match self.main.get::<_, Str, OwnedType<u8>>(txn, main_key::CASE_SENSITIVE_KEY)? {
+            Some(0) | None => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    pub(crate) fn put_case_sensitive(&self, txn: &mut RwTxn, flag: bool) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u8>>(txn, main_key::CASE_SENSITIVE_KEY, &(flag as u8))
+    }
+
+    pub(crate) fn delete_case_sensitive(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::CASE_SENSITIVE_KEY)
+    }
+
+    /// Returns `true` if the numbers of the searchable fields are indexed as text.
+    /// By default the numbers are searchable.
+    pub fn searchable_numbers(&self, txn: &RoTxn) -> heed::Result<bool> {
+        match self.main.get::<_, Str, OwnedType<u8>>(txn, main_key::SEARCHABLE_NUMBERS_KEY)? {
+            Some(0) => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    pub(crate) fn put_searchable_numbers(&self, txn: &mut RwTxn, flag: bool) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u8>>(txn, main_key::SEARCHABLE_NUMBERS_KEY, &(flag as u8))
+    }
+
+    pub(crate) fn delete_searchable_numbers(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::SEARCHABLE_NUMBERS_KEY)
+    }
+
+    /* languages */
+
+    pub(crate) fn put_languages(
+        &self,
+        wtxn: &mut RwTxn,
+        languages: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<_>>(wtxn, main_key::LANGUAGES_KEY, languages)
+    }
+
+    pub(crate) fn delete_languages(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::LANGUAGES_KEY)
+    }
+
+    /// Returns the names of the languages declared as being present in the documents.
+    pub fn languages_names(&self, rtxn: &RoTxn) -> Result<Option<BTreeSet<String>>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeBincode<BTreeSet<String>>>(rtxn, main_key::LANGUAGES_KEY)?)
+    }
+
+    /// Returns the languages declared as being present in the documents,
+    /// the language detection is restricted to them.
+    pub fn languages(&self, rtxn: &RoTxn) -> Result<Option<Vec<Language>>> {
+        Ok(self
+            .languages_names(rtxn)?
+            .map(|names| names.iter().map(|name| Language::from_name(name)).collect()))
+    }
+
     pub fn min_word_len_one_typo(&self, txn: &RoTxn) -> heed::Result<u8> {
         // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead. We
         // identify 0 as being false, and anything else as true. The absence of a value is true,
@@ -1470,6 +1816,57 @@ impl Index {
         self.main.delete::<_, Str>(txn, main_key::PAGINATION_MAX_TOTAL_HITS)
     }
 
+    /* pinned in memory */
+
+    /// Returns whether the pages of the index must be pinned in memory every time it is opened.
+    pub fn pinned_in_memory(&self, txn: &RoTxn) -> heed::Result<bool> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<bool>>(txn, main_key::PINNED_IN_MEMORY)?
+            .unwrap_or(false))
+    }
+
+    pub(crate) fn put_pinned_in_memory(&self, txn: &mut RwTxn, flag: bool) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<bool>>(txn, main_key::PINNED_IN_MEMORY, &flag)
+    }
+
+    pub(crate) fn delete_pinned_in_memory(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::PINNED_IN_MEMORY)
+    }
+
+    /// Returns whether the pages of the index are currently pinned in memory.
+    pub fn is_pinned_in_memory(&self) -> bool {
+        self.pinned_mapping.lock().unwrap().is_some()
+    }
+
+    /// Pins or releases the pages of the index according to the committed `pinned_in_memory`
+    /// setting, it must be called after committing a change of this setting.
+    ///
+    /// Pinning asks the OS to load the pages of the index in memory in advance. It is only a hint
+    /// on unix platforms and a no-op on the other platforms.
+    pub fn refresh_pinned_pages(&self) -> Result<()> {
+        let rtxn = self.read_txn()?;
+        let pinned_in_memory = self.pinned_in_memory(&rtxn)?;
+        drop(rtxn);
+
+        let mut pinned_mapping = self.pinned_mapping.lock().unwrap();
+        if !pinned_in_memory {
+            *pinned_mapping = None;
+        } else if pinned_mapping.is_none() {
+            let file = File::open(self.path().join("data.mdb"))?;
+            // The whole map size is mapped, rather than the current file, so that the mapping
+            // keeps covering the file when LMDB grows it. The pages written since are already
+            // in memory, and a resize of the map reopens the index and maps it again.
+            // Safety: the mapping is only used to advise the OS and is never read.
+            let mapping = unsafe { MmapOptions::new().len(self.map_size()?).map(&file)? };
+            #[cfg(unix)]
+            mapping.advise(memmap2::Advice::WillNeed)?;
+            *pinned_mapping = Some(mapping);
+        }
+
+        Ok(())
+    }
+
     /* script  language docids */
     /// Retrieve all the documents ids that correspond with (Script, Language) key, `None` if it is any.
     pub fn script_language_documents_ids(
@@ -2719,6 +3116,8 @@ pub(crate) mod tests {
             candidates: _,
             document_scores: _,
             mut documents_ids,
+            ranking_stages: _,
+            processed_query: _,
         } = search.execute().unwrap();
         let primary_key_id = index.fields_ids_map(&rtxn).unwrap().id("primary_key").unwrap();
         documents_ids.sort_unstable();
@@ -2794,4 +3193,41 @@ pub(crate) mod tests {
 
         db_snap!(index, geo_faceted_documents_ids); // ensure that no documents were inserted
     }
+
+    #[test]
+    fn pin_index_in_memory() {
+        let index = TempIndex::new();
+        index.add_documents(documents!([{ "id": 0, "doggo": "kefir" }])).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(!index.pinned_in_memory(&rtxn).unwrap());
+        assert!(!index.is_pinned_in_memory());
+        drop(rtxn);
+
+        // the pages are only pinned once the setting is committed.
+        let mut wtxn = index.write_txn().unwrap();
+        index.put_pinned_in_memory(&mut wtxn, true).unwrap();
+        index.refresh_pinned_pages().unwrap();
+        assert!(!index.is_pinned_in_memory());
+        wtxn.commit().unwrap();
+        index.refresh_pinned_pages().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.pinned_in_memory(&rtxn).unwrap());
+        assert!(index.is_pinned_in_memory());
+        drop(rtxn);
+
+        // the mapping covers the documents added after the pinning.
+        index.add_documents(documents!([{ "id": 1, "doggo": "bob" }])).unwrap();
+        assert!(index.is_pinned_in_memory());
+
+        let mut wtxn = index.write_txn().unwrap();
+        index.delete_pinned_in_memory(&mut wtxn).unwrap();
+        wtxn.commit().unwrap();
+        index.refresh_pinned_pages().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(!index.pinned_in_memory(&rtxn).unwrap());
+        assert!(!index.is_pinned_in_memory());
+    }
 }
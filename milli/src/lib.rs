@@ -27,6 +27,7 @@ pub mod update;
 #[macro_use]
 pub mod snapshot_tests;
 
+use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
 use std::convert::{TryFrom, TryInto};
 use std::hash::BuildHasherDefault;
@@ -37,7 +38,8 @@ pub use filter_parser::{Condition, FilterCondition, Span, Token};
 use fxhash::{FxHasher32, FxHasher64};
 pub use grenad::CompressionType;
 pub use search::new::{
-    execute_search, DefaultSearchLogger, GeoSortStrategy, SearchContext, SearchLogger,
+    execute_search, processed_query_terms, DefaultSearchLogger, GeoSortStrategy,
+    ProcessedQueryTerm, RankingStage, RankingStagesLogger, SearchContext, SearchLogger,
     VisualSearchLogger,
 };
 use serde_json::Value;
@@ -58,9 +60,9 @@ pub use self::heed_codec::{
 };
 pub use self::index::Index;
 pub use self::search::{
-    FacetDistribution, FacetValueHit, Filter, FormatOptions, MatchBounds, MatcherBuilder,
-    MatchingWords, OrderBy, Search, SearchForFacetValues, SearchResult, TermsMatchingStrategy,
-    DEFAULT_VALUES_PER_FACET,
+    restrict_script_language, sort_documents_by_facets, FacetDistribution, FacetValueHit, Filter,
+    FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, OrderBy, Search,
+    SearchForFacetValues, SearchResult, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 
 pub type Result<T> = std::result::Result<T, error::Error>;
@@ -285,10 +287,98 @@ pub fn is_faceted_by(field: &str, facet: &str) -> bool {
         && field[facet.len()..].chars().next().map(|c| c == '.').unwrap_or(true)
 }
 
+/// Returns `true` if the field is part of the displayed fields.
+///
+/// When the displayed fields contain the `*` wildcard every field is displayed except
+/// the ones excluded with a `-` prefix, an exclusion can itself end with a `*` to
+/// exclude every field starting with the given prefix.
+/// ```
+/// use milli::is_displayed;
+/// assert!(is_displayed("title", &["title", "overview"]));
+/// assert!(!is_displayed("secret", &["title", "overview"]));
+///
+/// assert!(is_displayed("title", &["*", "-secret"]));
+/// assert!(!is_displayed("secret", &["*", "-secret"]));
+/// assert!(!is_displayed("internal_id", &["*", "-internal_*"]));
+/// assert!(is_displayed("internal", &["*", "-internal_*"]));
+/// ```
+pub fn is_displayed(field: &str, displayed_fields: &[impl AsRef<str>]) -> bool {
+    let has_wildcard = displayed_fields.iter().any(|f| f.as_ref() == "*");
+    if !has_wildcard {
+        return displayed_fields.iter().any(|f| f.as_ref() == field);
+    }
+
+    !displayed_fields.iter().filter_map(|f| f.as_ref().strip_prefix('-')).any(|excluded| {
+        match excluded.strip_suffix('*') {
+            Some(prefix) => field.starts_with(prefix),
+            None => field == excluded,
+        }
+    })
+}
+
 pub fn normalize_facet(original: &str) -> String {
     CompatibilityDecompositionNormalizer.normalize_str(original.trim()).to_lowercase()
 }
 
+/// Restores the case of the original text in the lemmas of the tokens if the index is case
+/// sensitive. Only the case folding is undone, the other normalizations (diacritics, etc.) are kept.
+///
+/// The tokenizer must create the char map of the tokens for the case to be restored precisely,
+/// see [`charabia::TokenizerBuilder::create_char_map`].
+pub fn with_original_case<'o>(
+    text: &'o str,
+    tokens: impl Iterator<Item = charabia::Token<'o>>,
+    case_sensitive: bool,
+) -> impl Iterator<Item = charabia::Token<'o>> {
+    tokens.map(move |mut token| {
+        if case_sensitive {
+            token.lemma = original_case_lemma(text, &token);
+        }
+        token
+    })
+}
+
+fn original_case_lemma<'o>(text: &str, token: &charabia::Token<'o>) -> Cow<'o, str> {
+    let original = match text.get(token.byte_start..token.byte_end) {
+        Some(original) if original.chars().any(char::is_uppercase) => original,
+        _ => return token.lemma.clone(),
+    };
+
+    fn push_cased(cased: &mut String, normalized: &str, uppercase: bool) {
+        if uppercase {
+            cased.extend(normalized.chars().flat_map(char::to_uppercase));
+        } else {
+            cased.push_str(normalized);
+        }
+    }
+
+    let lemma = token.lemma();
+    let mut cased = String::with_capacity(lemma.len());
+    match &token.char_map {
+        // every original char is associated with the number of bytes it has been normalized into.
+        Some(char_map) => {
+            let mut rest = lemma;
+            for (c, (_, normalized_len)) in original.chars().zip(char_map) {
+                let len = *normalized_len as usize;
+                let (Some(normalized), Some(tail)) = (rest.get(..len), rest.get(len..)) else {
+                    break;
+                };
+                push_cased(&mut cased, normalized, c.is_uppercase());
+                rest = tail;
+            }
+            cased.push_str(rest);
+        }
+        None if original.chars().count() == lemma.chars().count() => {
+            for (c, normalized) in original.chars().zip(lemma.chars()) {
+                push_cased(&mut cased, normalized.encode_utf8(&mut [0; 4]), c.is_uppercase());
+            }
+        }
+        None => return token.lemma.clone(),
+    }
+
+    Cow::Owned(cased)
+}
+
 /// Represents either a vector or an array of multiple vectors.
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 #[serde(transparent)]
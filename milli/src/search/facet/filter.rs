@@ -218,6 +218,43 @@ impl<'a> Filter<'a> {
 
         Ok(Some(Self { condition }))
     }
+
+    /// Returns the filter without its top-level conditions applying to the given attribute,
+    /// or `None` if no condition is left. It is used to compute disjunctive facet distributions.
+    pub fn without_attribute(&self, attribute: &str) -> Option<Self> {
+        fn conjuncts<'a>(condition: &FilterCondition<'a>) -> Vec<&FilterCondition<'a>> {
+            match condition {
+                FilterCondition::And(conditions) => conditions.iter().flat_map(conjuncts).collect(),
+                condition => vec![condition],
+            }
+        }
+
+        fn applies_to(condition: &FilterCondition, attribute: &str) -> bool {
+            match condition {
+                FilterCondition::Not(condition) => applies_to(condition, attribute),
+                FilterCondition::Condition { fid, .. } | FilterCondition::In { fid, .. } => {
+                    fid.value() == attribute
+                }
+                FilterCondition::Or(conditions) | FilterCondition::And(conditions) => {
+                    conditions.iter().any(|condition| applies_to(condition, attribute))
+                }
+                FilterCondition::GeoLowerThan { .. } | FilterCondition::GeoBoundingBox { .. } => {
+                    attribute == "_geo"
+                }
+            }
+        }
+
+        let mut conditions: Vec<_> = conjuncts(&self.condition)
+            .into_iter()
+            .filter(|condition| !applies_to(condition, attribute))
+            .cloned()
+            .collect();
+        match conditions.len() {
+            0 => None,
+            1 => conditions.pop().map(Self::from),
+            _ => Some(Self::from(FilterCondition::And(conditions))),
+        }
+    }
 }
 
 impl<'a> Filter<'a> {
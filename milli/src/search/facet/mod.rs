@@ -8,7 +8,7 @@ pub use self::facet_distribution::{FacetDistribution, OrderBy, DEFAULT_VALUES_PE
 pub use self::filter::{BadGeoError, Filter};
 use crate::heed_codec::facet::{FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec};
 use crate::heed_codec::ByteSliceRefCodec;
-use crate::{Index, Result};
+use crate::{AscDesc, DocumentId, FieldId, Index, Member, Result};
 mod facet_distribution;
 mod facet_distribution_iter;
 mod facet_range_search;
@@ -48,6 +48,112 @@ pub fn facet_max_value<'t>(
     facet_extreme_value(it)
 }
 
+/// Sort the candidates according to the given sort criteria by only using the facet databases
+/// and return the `limit` documents following the first `offset` ones.
+///
+/// Contrary to a search, no ranking rules nor distinct attribute are applied: for every criterion
+/// the documents are ordered by their numbers then their strings in ascending order, and by their
+/// strings then their numbers in descending order. The documents that don't have a value for the
+/// sorted field come last, in their internal order. The `_geoPoint` criteria are ignored.
+///
+/// The facet values are lazily iterated, the buckets that are entirely skipped by the `offset`
+/// or that come after the requested page are never sorted.
+pub fn sort_documents_by_facets(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    candidates: RoaringBitmap,
+    sort_criteria: &[AscDesc],
+    offset: usize,
+    limit: usize,
+) -> Result<Vec<DocumentId>> {
+    let fields_ids_map = index.fields_ids_map(rtxn)?;
+    let criteria: Vec<_> = sort_criteria
+        .iter()
+        .filter_map(|asc_desc| match asc_desc {
+            AscDesc::Asc(Member::Field(field)) => Some((field, true)),
+            AscDesc::Desc(Member::Field(field)) => Some((field, false)),
+            AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => None,
+        })
+        // a field that is not present in any document can't discriminate the documents.
+        .filter_map(|(field, ascending)| Some((fields_ids_map.id(field)?, ascending)))
+        .collect();
+
+    let mut sorter = FacetSorter { index, rtxn, offset, limit, documents_ids: Vec::new() };
+    sorter.sort_bucket(&criteria, candidates)?;
+    Ok(sorter.documents_ids)
+}
+
+struct FacetSorter<'t> {
+    index: &'t Index,
+    rtxn: &'t heed::RoTxn<'t>,
+    /// The number of sorted documents that still must be skipped.
+    offset: usize,
+    limit: usize,
+    documents_ids: Vec<DocumentId>,
+}
+
+impl<'t> FacetSorter<'t> {
+    fn is_full(&self) -> bool {
+        self.documents_ids.len() >= self.limit
+    }
+
+    fn sort_bucket(&mut self, criteria: &[(FieldId, bool)], bucket: RoaringBitmap) -> Result<()> {
+        if self.is_full() {
+            return Ok(());
+        }
+        // the whole bucket is before the requested page, there is no need to sort it.
+        if bucket.len() <= self.offset as u64 {
+            self.offset -= bucket.len() as usize;
+            return Ok(());
+        }
+
+        let ((field_id, ascending), criteria) = match criteria.split_first() {
+            Some((criterion, criteria)) if bucket.len() > 1 => (*criterion, criteria),
+            _ => {
+                let remaining = self.limit - self.documents_ids.len();
+                self.documents_ids.extend(bucket.into_iter().skip(self.offset).take(remaining));
+                self.offset = 0;
+                return Ok(());
+            }
+        };
+
+        let number_db = self
+            .index
+            .facet_id_f64_docids
+            .remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>();
+        let string_db = self
+            .index
+            .facet_id_string_docids
+            .remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>();
+        let dbs = if ascending { [number_db, string_db] } else { [string_db, number_db] };
+
+        let mut remaining = bucket.clone();
+        for db in dbs {
+            let facet_buckets: Box<
+                dyn Iterator<Item = heed::Result<(RoaringBitmap, &'t [u8])>> + 't,
+            > = if ascending {
+                Box::new(ascending_facet_sort(self.rtxn, db, field_id, bucket.clone())?)
+            } else {
+                Box::new(descending_facet_sort(self.rtxn, db, field_id, bucket.clone())?)
+            };
+            for result in facet_buckets {
+                let (docids, _) = result?;
+                // a document with multiple values is ranked by the first one we encounter.
+                let docids = docids & &remaining;
+                remaining -= &docids;
+                if !docids.is_empty() {
+                    self.sort_bucket(criteria, docids)?;
+                }
+                if self.is_full() {
+                    return Ok(());
+                }
+            }
+        }
+
+        self.sort_bucket(criteria, remaining)
+    }
+}
+
 /// Get the first facet value in the facet database
 pub(crate) fn get_first_facet_value<'t, BoundCodec>(
     txn: &'t RoTxn,
@@ -1,8 +1,9 @@
+use std::collections::HashMap;
 use std::fmt;
 use std::ops::ControlFlow;
 
 use charabia::normalizer::NormalizerOption;
-use charabia::Normalize;
+use charabia::{Language, Normalize, Script};
 use fst::automaton::{Automaton, Str};
 use fst::{IntoStreamer, Streamer};
 use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
@@ -10,15 +11,18 @@ use log::error;
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 
-pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet::{
+    sort_documents_by_facets, FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET,
+};
 pub use self::new::matches::{FormatOptions, MatchBounds, Matcher, MatcherBuilder, MatchingWords};
 use self::new::PartialSearchResult;
 use crate::error::UserError;
 use crate::heed_codec::facet::{FacetGroupKey, FacetGroupValue};
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::{
-    execute_search, AscDesc, DefaultSearchLogger, DocumentId, FieldId, Index, Result,
-    SearchContext, BEU16,
+    execute_search, processed_query_terms, AscDesc, Criterion, DefaultSearchLogger, DocumentId,
+    FieldId, Index, ProcessedQueryTerm, RankingStage, RankingStagesLogger, Result, SearchContext,
+    SearchLogger, BEU16,
 };
 
 // Building these factories is not free.
@@ -38,15 +42,20 @@ pub struct Search<'a> {
     vector: Option<Vec<f32>>,
     // this should be linked to the String in the query
     filter: Option<Filter<'a>>,
+    candidates: Option<RoaringBitmap>,
+    locales: Option<Vec<Language>>,
     offset: usize,
     limit: usize,
     sort_criteria: Option<Vec<AscDesc>>,
+    disabled_criteria: Vec<Criterion>,
     searchable_attributes: Option<&'a [String]>,
     geo_strategy: new::GeoSortStrategy,
     terms_matching_strategy: TermsMatchingStrategy,
     scoring_strategy: ScoringStrategy,
     words_limit: usize,
     exhaustive_number_hits: bool,
+    ranking_stages: bool,
+    processed_query: bool,
     rtxn: &'a heed::RoTxn<'a>,
     index: &'a Index,
 }
@@ -57,15 +66,20 @@ impl<'a> Search<'a> {
             query: None,
             vector: None,
             filter: None,
+            candidates: None,
+            locales: None,
             offset: 0,
             limit: 20,
             sort_criteria: None,
+            disabled_criteria: Vec::new(),
             searchable_attributes: None,
             geo_strategy: new::GeoSortStrategy::default(),
             terms_matching_strategy: TermsMatchingStrategy::default(),
             scoring_strategy: Default::default(),
             exhaustive_number_hits: false,
             words_limit: 10,
+            ranking_stages: false,
+            processed_query: false,
             rtxn,
             index,
         }
@@ -96,6 +110,12 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Removes the given ranking rules of the settings from the ranking of this search only.
+    pub fn disabled_criteria(&mut self, criteria: Vec<Criterion>) -> &mut Search<'a> {
+        self.disabled_criteria = criteria;
+        self
+    }
+
     pub fn searchable_attributes(&mut self, searchable: &'a [String]) -> &mut Search<'a> {
         self.searchable_attributes = Some(searchable);
         self
@@ -121,6 +141,19 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Restricts the search to the given documents, they are still ranked by relevancy.
+    pub fn candidates(&mut self, candidates: RoaringBitmap) -> &mut Search<'a> {
+        self.candidates = Some(candidates);
+        self
+    }
+
+    /// Hints the languages of the query, they are used to pick the
+    /// tokenization pipeline instead of relying on language detection.
+    pub fn locales(&mut self, locales: Vec<Language>) -> &mut Search<'a> {
+        self.locales = Some(locales);
+        self
+    }
+
     #[cfg(test)]
     pub fn geo_sort_strategy(&mut self, strategy: new::GeoSortStrategy) -> &mut Search<'a> {
         self.geo_strategy = strategy;
@@ -134,13 +167,41 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Records the number of candidates remaining after each ranking rule.
+    pub fn ranking_stages(&mut self, ranking_stages: bool) -> &mut Search<'a> {
+        self.ranking_stages = ranking_stages;
+        self
+    }
+
+    /// Describes the terms of the query as they are searched, after the removal
+    /// of the stop words and the expansion of the synonyms.
+    pub fn processed_query(&mut self, processed_query: bool) -> &mut Search<'a> {
+        self.processed_query = processed_query;
+        self
+    }
+
     pub fn execute(&self) -> Result<SearchResult> {
         let mut ctx = SearchContext::new(self.index, self.rtxn);
+        ctx.disabled_criteria = self.disabled_criteria.clone();
 
         if let Some(searchable_attributes) = self.searchable_attributes {
             ctx.searchable_attributes(searchable_attributes)?;
         }
 
+        let mut placeholder_stages = RankingStagesLogger::default();
+        let mut query_graph_stages = RankingStagesLogger::default();
+        let (placeholder_search_logger, query_graph_logger) = if self.ranking_stages {
+            (
+                &mut placeholder_stages as &mut dyn SearchLogger<_>,
+                &mut query_graph_stages as &mut dyn SearchLogger<_>,
+            )
+        } else {
+            (
+                &mut DefaultSearchLogger as &mut dyn SearchLogger<_>,
+                &mut DefaultSearchLogger as &mut dyn SearchLogger<_>,
+            )
+        };
+
         let PartialSearchResult { located_query_terms, candidates, documents_ids, document_scores } =
             execute_search(
                 &mut ctx,
@@ -150,22 +211,44 @@ impl<'a> Search<'a> {
                 self.scoring_strategy,
                 self.exhaustive_number_hits,
                 &self.filter,
+                &self.candidates,
+                self.locales.as_deref(),
                 &self.sort_criteria,
                 self.geo_strategy,
                 self.offset,
                 self.limit,
                 Some(self.words_limit),
-                &mut DefaultSearchLogger,
-                &mut DefaultSearchLogger,
+                placeholder_search_logger,
+                query_graph_logger,
             )?;
 
+        let ranking_stages = self.ranking_stages.then(|| {
+            if query_graph_stages.stages.is_empty() {
+                placeholder_stages.stages
+            } else {
+                query_graph_stages.stages
+            }
+        });
+
+        let processed_query = self.processed_query.then(|| match &located_query_terms {
+            Some(located_query_terms) => processed_query_terms(&ctx, located_query_terms),
+            None => Vec::new(),
+        });
+
         // consume context and located_query_terms to build MatchingWords.
         let matching_words = match located_query_terms {
             Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
             None => MatchingWords::default(),
         };
 
-        Ok(SearchResult { matching_words, candidates, document_scores, documents_ids })
+        Ok(SearchResult {
+            matching_words,
+            candidates,
+            document_scores,
+            documents_ids,
+            ranking_stages,
+            processed_query,
+        })
     }
 }
 
@@ -175,15 +258,20 @@ impl fmt::Debug for Search<'_> {
             query,
             vector: _,
             filter,
+            candidates,
+            locales,
             offset,
             limit,
             sort_criteria,
+            disabled_criteria,
             searchable_attributes,
             geo_strategy: _,
             terms_matching_strategy,
             scoring_strategy,
             words_limit,
             exhaustive_number_hits,
+            ranking_stages,
+            processed_query,
             rtxn: _,
             index: _,
         } = self;
@@ -191,24 +279,54 @@ impl fmt::Debug for Search<'_> {
             .field("query", query)
             .field("vector", &"[...]")
             .field("filter", filter)
+            .field("candidates", candidates)
+            .field("locales", locales)
             .field("offset", offset)
             .field("limit", limit)
             .field("sort_criteria", sort_criteria)
+            .field("disabled_criteria", disabled_criteria)
             .field("searchable_attributes", searchable_attributes)
             .field("terms_matching_strategy", terms_matching_strategy)
             .field("scoring_strategy", scoring_strategy)
             .field("exhaustive_number_hits", exhaustive_number_hits)
             .field("words_limit", words_limit)
+            .field("ranking_stages", ranking_stages)
+            .field("processed_query", processed_query)
             .finish()
     }
 }
 
+/// Restricts the languages detected in the index to the given locales.
+///
+/// Scripts for which none of the locales were detected keep all their languages.
+pub fn restrict_script_language(
+    script_language: HashMap<Script, Vec<Language>>,
+    locales: &[Language],
+) -> HashMap<Script, Vec<Language>> {
+    script_language
+        .into_iter()
+        .map(|(script, languages)| {
+            let restricted: Vec<_> =
+                languages.iter().copied().filter(|lang| locales.contains(lang)).collect();
+            if restricted.is_empty() {
+                (script, languages)
+            } else {
+                (script, restricted)
+            }
+        })
+        .collect()
+}
+
 #[derive(Default, Debug)]
 pub struct SearchResult {
     pub matching_words: MatchingWords,
     pub candidates: RoaringBitmap,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
+    /// The number of candidates remaining after each ranking rule, if requested.
+    pub ranking_stages: Option<Vec<RankingStage>>,
+    /// The terms of the query as they are searched, if requested.
+    pub processed_query: Option<Vec<ProcessedQueryTerm>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
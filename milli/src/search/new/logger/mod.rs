@@ -79,3 +79,55 @@ impl<Q: RankingRuleQueryTrait> SearchLogger<Q> for DefaultSearchLogger {
 
     fn log_internal_state(&mut self, _rr: &dyn Any) {}
 }
+
+/// The number of candidates remaining after a ranking rule was applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankingStage {
+    pub ranking_rule: String,
+    pub candidates: u64,
+}
+
+/// A [`SearchLogger`] recording the first non-empty bucket returned by each ranking rule.
+///
+/// Only the buckets nested in the previously recorded ones are kept, the recorded
+/// number of candidates therefore never increases from one stage to the next.
+#[derive(Default)]
+pub struct RankingStagesLogger {
+    pub stages: Vec<RankingStage>,
+    diverged: bool,
+}
+
+impl<Q: RankingRuleQueryTrait> SearchLogger<Q> for RankingStagesLogger {
+    fn initial_query(&mut self, _query: &Q) {}
+
+    fn initial_universe(&mut self, _universe: &RoaringBitmap) {}
+
+    fn query_for_initial_universe(&mut self, _query: &Q) {}
+
+    fn ranking_rules(&mut self, _rr: &[BoxRankingRule<Q>]) {}
+
+    fn next_bucket_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        ranking_rule: &dyn RankingRule<Q>,
+        _universe: &RoaringBitmap,
+        candidates: &RoaringBitmap,
+    ) {
+        if candidates.is_empty() || self.diverged {
+            return;
+        }
+        if ranking_rule_idx < self.stages.len() {
+            // a sibling of a recorded bucket, its sub-buckets are not nested in the recorded ones
+            self.diverged = true;
+        } else if ranking_rule_idx == self.stages.len() {
+            self.stages.push(RankingStage {
+                ranking_rule: ranking_rule.id(),
+                candidates: candidates.len(),
+            });
+        }
+    }
+
+    fn add_to_results(&mut self, _docids: &[u32]) {}
+
+    fn log_internal_state(&mut self, _rr: &dyn Any) {}
+}
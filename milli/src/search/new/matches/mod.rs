@@ -5,6 +5,8 @@ pub use matching_words::MatchingWords;
 use matching_words::{MatchType, PartialMatch, WordId};
 use serde::Serialize;
 
+use crate::with_original_case;
+
 pub mod matching_words;
 
 const DEFAULT_CROP_MARKER: &str = "…";
@@ -15,6 +17,7 @@ const DEFAULT_HIGHLIGHT_SUFFIX: &str = "</em>";
 pub struct MatcherBuilder<'m> {
     matching_words: MatchingWords,
     tokenizer: Tokenizer<'m>,
+    case_sensitive: bool,
     crop_marker: Option<String>,
     highlight_prefix: Option<String>,
     highlight_suffix: Option<String>,
@@ -25,12 +28,20 @@ impl<'m> MatcherBuilder<'m> {
         Self {
             matching_words,
             tokenizer,
+            case_sensitive: false,
             crop_marker: None,
             highlight_prefix: None,
             highlight_suffix: None,
         }
     }
 
+    /// Restores the original case of the tokens before matching them, the tokenizer must
+    /// create the char map of the tokens, see [`crate::with_original_case`].
+    pub fn case_sensitive(&mut self, case_sensitive: bool) -> &Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
     pub fn crop_marker(&mut self, marker: String) -> &Self {
         self.crop_marker = Some(marker);
         self
@@ -64,6 +75,7 @@ impl<'m> MatcherBuilder<'m> {
             text,
             matching_words: &self.matching_words,
             tokenizer: &self.tokenizer,
+            case_sensitive: self.case_sensitive,
             crop_marker,
             highlight_prefix,
             highlight_suffix,
@@ -107,6 +119,7 @@ pub struct Matcher<'t, 'm> {
     text: &'t str,
     matching_words: &'m MatchingWords,
     tokenizer: &'m Tokenizer<'m>,
+    case_sensitive: bool,
     crop_marker: &'m str,
     highlight_prefix: &'m str,
     highlight_suffix: &'m str,
@@ -169,7 +182,8 @@ impl<'t> Matcher<'t, '_> {
             false
         }
 
-        let tokens: Vec<_> = self.tokenizer.tokenize(self.text).collect();
+        let tokens = self.tokenizer.tokenize(self.text);
+        let tokens: Vec<_> = with_original_case(self.text, tokens, self.case_sensitive).collect();
         let mut matches = Vec::new();
 
         let mut words_positions = tokens
@@ -451,18 +465,11 @@ impl<'t> Matcher<'t, '_> {
                                 formatted.push(&self.text[byte_index..token.byte_start]);
                             }
 
-                            let highlight_byte_index = self.text[token.byte_start..]
-                                .char_indices()
-                                .enumerate()
-                                .find(|(i, _)| *i == m.match_len)
-                                .map_or(token.byte_end, |(_, (i, _))| i + token.byte_start);
+                            // the whole token is highlighted, even if only its prefix matches,
+                            // so that the highlight markers never split a word.
                             formatted.push(self.highlight_prefix);
-                            formatted.push(&self.text[token.byte_start..highlight_byte_index]);
+                            formatted.push(&self.text[token.byte_start..token.byte_end]);
                             formatted.push(self.highlight_suffix);
-                            // if it's a prefix highlight, we put the end of the word after the highlight marker.
-                            if highlight_byte_index < token.byte_end {
-                                formatted.push(&self.text[highlight_byte_index..token.byte_end]);
-                            }
 
                             byte_index = token.byte_end;
                         }
@@ -512,6 +519,8 @@ mod tests {
                 false,
                 &None,
                 &None,
+                None,
+                &None,
                 crate::search::new::GeoSortStrategy::default(),
                 0,
                 100,
@@ -614,7 +623,7 @@ mod tests {
         // no crop should return complete text with highlighted matches.
         insta::assert_snapshot!(
             matcher.format(format_options),
-            @"<em>Ŵôřlḑ</em>ôle"
+            @"<em>Ŵôřlḑôle</em>"
         );
 
         // Text containing unicode match.
@@ -635,7 +644,7 @@ mod tests {
         // no crop should return complete text with highlighted matches.
         insta::assert_snapshot!(
             matcher.format(format_options),
-            @"<em>Westfáli</em>a"
+            @"<em>Westfália</em>"
         );
     }
 
@@ -797,6 +806,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_highlight_crop_prefix_match() {
+        let temp_index = temp_index_with_documents();
+        let rtxn = temp_index.read_txn().unwrap();
+        let builder = MatcherBuilder::new_test(&rtxn, &temp_index, "world");
+
+        let format_options = FormatOptions { highlight: true, crop: Some(5) };
+
+        // Text containing a prefix match inside of the crop window.
+        let text = "Natalie risk her future to build a worldwide empire with the boy she loves.";
+        let mut matcher = builder.build(text);
+        let formatted = matcher.format(format_options);
+        // the whole matched word is highlighted, not only the matching prefix.
+        assert!(formatted.contains("<em>worldwide</em>"), "{formatted}");
+        assert!(formatted.starts_with('…') && formatted.ends_with('…'), "{formatted}");
+    }
+
     #[test]
     fn format_highlight_crop_phrase_query() {
         //! testing: https://github.com/meilisearch/meilisearch/issues/3975
@@ -23,7 +23,7 @@ mod tests;
 use std::collections::HashSet;
 
 use bucket_sort::{bucket_sort, BucketSortOutput};
-use charabia::TokenizerBuilder;
+use charabia::{Language, TokenizerBuilder};
 use db_cache::DatabaseCache;
 use exact_attribute::ExactAttribute;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
@@ -31,9 +31,10 @@ use heed::RoTxn;
 use instant_distance::Search;
 use interner::{DedupInterner, Interner};
 pub use logger::visual::VisualSearchLogger;
-pub use logger::{DefaultSearchLogger, SearchLogger};
+pub use logger::{DefaultSearchLogger, RankingStage, RankingStagesLogger, SearchLogger};
 use query_graph::{QueryGraph, QueryNode};
 use query_term::{located_query_terms_from_tokens, LocatedQueryTerm, Phrase, QueryTerm};
+pub use query_term::{processed_query_terms, ProcessedQueryTerm};
 use ranking_rules::{
     BoxRankingRule, PlaceholderQuery, RankingRule, RankingRuleOutput, RankingRuleQueryTrait,
 };
@@ -50,8 +51,10 @@ use crate::distance::NDotProductPoint;
 use crate::error::FieldIdMapMissingEntry;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::apply_distinct_rule;
+use crate::search::restrict_script_language;
 use crate::{
-    AscDesc, DocumentId, Filter, Index, Member, Result, TermsMatchingStrategy, UserError, BEU32,
+    with_original_case, AscDesc, Criterion, DocumentId, Filter, Index, Member, Result,
+    TermsMatchingStrategy, UserError, BEU32,
 };
 
 /// A structure used throughout the execution of a search query.
@@ -64,6 +67,10 @@ pub struct SearchContext<'ctx> {
     pub term_interner: Interner<QueryTerm>,
     pub phrase_docids: PhraseDocIdsCache,
     pub restricted_fids: Option<Vec<u16>>,
+    /// The ranking rules of the settings that are ignored by this search.
+    ///
+    /// The `words` ranking rule is still applied before the rules that depend on it.
+    pub disabled_criteria: Vec<Criterion>,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -77,6 +84,7 @@ impl<'ctx> SearchContext<'ctx> {
             term_interner: <_>::default(),
             phrase_docids: <_>::default(),
             restricted_fids: None,
+            disabled_criteria: Vec::new(),
         }
     }
 
@@ -199,8 +207,11 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
     let mut sorted_fields = HashSet::new();
     let mut geo_sorted = false;
     let mut ranking_rules: Vec<BoxRankingRule<PlaceholderQuery>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
-    for rr in settings_ranking_rules {
+    let mut settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    settings_ranking_rules.retain(|rr| !ctx.disabled_criteria.contains(rr));
+    // The tie breaking sort only orders the documents that the ranking rules left tied
+    let tie_breaking_sort = ctx.index.tie_breaking_sort(ctx.txn)?;
+    for rr in settings_ranking_rules.into_iter().chain(tie_breaking_sort) {
         match rr {
             // These rules need a query to have an effect; ignore them in placeholder search
             crate::Criterion::Words
@@ -264,8 +275,11 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
     }
 
     let mut ranking_rules: Vec<BoxRankingRule<QueryGraph>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
-    for rr in settings_ranking_rules {
+    let mut settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    settings_ranking_rules.retain(|rr| !ctx.disabled_criteria.contains(rr));
+    // The tie breaking sort only orders the documents that the ranking rules left tied
+    let tie_breaking_sort = ctx.index.tie_breaking_sort(ctx.txn)?;
+    for rr in settings_ranking_rules.into_iter().chain(tie_breaking_sort) {
         // Add Words before any of: typo, proximity, attribute
         match rr {
             crate::Criterion::Typo
@@ -414,6 +428,8 @@ pub fn execute_search(
     scoring_strategy: ScoringStrategy,
     exhaustive_number_hits: bool,
     filters: &Option<Filter>,
+    candidates: &Option<RoaringBitmap>,
+    locales: Option<&[Language]>,
     sort_criteria: &Option<Vec<AscDesc>>,
     geo_strategy: geo_sort::Strategy,
     from: usize,
@@ -428,6 +444,10 @@ pub fn execute_search(
         ctx.index.documents_ids(ctx.txn)?
     };
 
+    if let Some(candidates) = candidates {
+        universe &= candidates;
+    }
+
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
     if let Some(vector) = vector {
@@ -489,13 +509,21 @@ pub fn execute_search(
             tokbuilder.words_dict(dictionary);
         }
 
-        let script_lang_map = ctx.index.script_language(ctx.txn)?;
+        // the query locales take precedence over the languages declared by the index.
+        let index_languages = ctx.index.languages(ctx.txn)?;
+        let mut script_lang_map = ctx.index.script_language(ctx.txn)?;
+        if let Some(locales) = locales.or(index_languages.as_deref()) {
+            script_lang_map = restrict_script_language(script_lang_map, locales);
+        }
         if !script_lang_map.is_empty() {
             tokbuilder.allow_list(&script_lang_map);
         }
 
+        let case_sensitive = ctx.index.case_sensitive(ctx.txn)?;
+        tokbuilder.create_char_map(case_sensitive);
+
         let tokenizer = tokbuilder.build();
-        let tokens = tokenizer.tokenize(query);
+        let tokens = with_original_case(query, tokenizer.tokenize(query), case_sensitive);
 
         let query_terms = located_query_terms_from_tokens(ctx, tokens, words_limit)?;
         if query_terms.is_empty() {
@@ -455,6 +455,30 @@ impl Interned<QueryTerm> {
     }
 }
 
+/// A term of the query as it is searched, once normalized and expanded with its synonyms.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessedQueryTerm {
+    /// The normalized word or phrase.
+    pub term: String,
+    pub synonyms: Vec<String>,
+}
+
+/// Describes the terms of the query left after the removal of the stop words, ignoring the ngrams.
+pub fn processed_query_terms(
+    ctx: &SearchContext,
+    located_terms: &[LocatedQueryTerm],
+) -> Vec<ProcessedQueryTerm> {
+    located_terms
+        .iter()
+        .map(|located_term| ctx.term_interner.get(located_term.value))
+        .filter(|term| term.ngram_words.is_none())
+        .map(|term| ProcessedQueryTerm {
+            term: term.original_word(ctx),
+            synonyms: term.zero_typo.synonyms.iter().map(|s| s.description(ctx)).collect(),
+        })
+        .collect()
+}
+
 /// A query term coupled with its position in the user's search query.
 #[derive(Clone)]
 pub struct LocatedQueryTerm {
@@ -1,5 +1,4 @@
-use charabia::normalizer::NormalizedTokenIter;
-use charabia::{SeparatorKind, TokenKind};
+use charabia::{SeparatorKind, Token, TokenKind};
 
 use super::*;
 use crate::{Result, SearchContext, MAX_WORD_LENGTH};
@@ -7,7 +6,7 @@ use crate::{Result, SearchContext, MAX_WORD_LENGTH};
 /// Convert the tokenised search query into a list of located query terms.
 pub fn located_query_terms_from_tokens(
     ctx: &mut SearchContext,
-    query: NormalizedTokenIter,
+    query: impl Iterator<Item = Token<'_>>,
     words_limit: Option<usize>,
 ) -> Result<Vec<LocatedQueryTerm>> {
     let nbr_typos = number_of_typos_allowed(ctx)?;
@@ -45,6 +45,7 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
             field_id_docid_facet_strings,
             vector_id_docid,
             documents,
+            pinned_mapping: _,
         } = self.index;
 
         let empty_roaring = RoaringBitmap::default();
@@ -250,6 +250,7 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             facet_id_is_empty_docids,
             vector_id_docid,
             documents,
+            pinned_mapping: _,
         } = self.index;
         // Remove from the documents database
         for docid in &self.to_delete_docids {
@@ -99,10 +99,12 @@ pub fn enrich_documents_batch<R: Read + Seek>(
 
     // If the settings specifies that a _geo field must be used therefore we must check the
     // validity of it in all the documents of this batch and this is when we return `Some`.
+    let filterable_fields = index.user_defined_filterable_fields(rtxn)?;
     let geo_field_id = match documents_batch_index.id("_geo") {
         Some(geo_field_id)
             if index.sortable_fields(rtxn)?.contains("_geo")
-                || index.filterable_fields(rtxn)?.contains("_geo") =>
+                || filterable_fields.contains("_geo")
+                || filterable_fields.contains("*") =>
         {
             Some(geo_field_id)
         }
@@ -13,7 +13,8 @@ use super::helpers::{concat_u32s_array, create_sorter, sorter_into_reader, Grena
 use crate::error::{InternalError, SerializationError};
 use crate::update::index_documents::MergeFn;
 use crate::{
-    absolute_from_relative_position, FieldId, Result, MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH,
+    absolute_from_relative_position, with_original_case, FieldId, Result,
+    MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH,
 };
 
 pub type ScriptLanguageDocidsMap = HashMap<(Script, Language), RoaringBitmap>;
@@ -31,6 +32,9 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     stop_words: Option<&fst::Set<&[u8]>>,
     allowed_separators: Option<&[&str]>,
     dictionary: Option<&[&str]>,
+    case_sensitive: bool,
+    languages: Option<&[Language]>,
+    searchable_numbers: bool,
     max_positions_per_attributes: Option<u32>,
 ) -> Result<(RoaringBitmap, grenad::Reader<BufReader<File>>, ScriptLanguageDocidsMap)> {
     puffin::profile_function!();
@@ -61,6 +65,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     if let Some(separators) = allowed_separators {
         tokenizer_builder.separators(separators);
     }
+    tokenizer_builder.create_char_map(case_sensitive);
     let tokenizer = tokenizer_builder.build();
 
     let mut cursor = obkv_documents.into_cursor()?;
@@ -81,48 +86,72 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
             &obkv,
             searchable_fields,
             &tokenizer,
+            case_sensitive,
+            searchable_numbers,
             max_positions_per_attributes,
             &mut buffers,
             &mut script_language_word_count,
             &mut docid_word_positions_sorter,
         )?;
 
-        // if we detect a potetial mistake in the language detection,
-        // we rerun the extraction forcing the tokenizer to detect the most frequently detected Languages.
-        // context: https://github.com/meilisearch/meilisearch/issues/3565
-        if script_language_word_count
-            .values()
-            .map(Vec::as_slice)
-            .any(potential_language_detection_error)
-        {
-            // build an allow list with the most frequent detected languages in the document.
-            let script_language: HashMap<_, _> =
-                script_language_word_count.iter().filter_map(most_frequent_languages).collect();
+        let script_language: HashMap<_, _> = match languages {
+            // if the index declares its languages and we detected another one in a Script
+            // covered by the declared Languages, we rerun the extraction forcing the tokenizer
+            // to pick one of the declared Languages for this Script only.
+            Some(languages) => script_language_word_count
+                .iter()
+                .filter_map(|(script, languages_frequency)| {
+                    let covering: Vec<_> = languages
+                        .iter()
+                        .copied()
+                        .filter(|language| language_script(*language) == *script)
+                        .collect();
+                    let mistaken = languages_frequency
+                        .iter()
+                        .any(|(language, _)| !covering.contains(language));
+                    (!covering.is_empty() && mistaken).then_some((*script, covering))
+                })
+                .collect(),
+            // if we detect a potetial mistake in the language detection,
+            // we rerun the extraction forcing the tokenizer to detect the most frequently detected Languages.
+            // context: https://github.com/meilisearch/meilisearch/issues/3565
+            None if script_language_word_count
+                .values()
+                .map(Vec::as_slice)
+                .any(potential_language_detection_error) =>
+            {
+                // build an allow list with the most frequent detected languages in the document.
+                script_language_word_count.iter().filter_map(most_frequent_languages).collect()
+            }
+            None => HashMap::new(),
+        };
 
-            // if the allow list is empty, meaning that no Language is considered frequent,
-            // then we don't rerun the extraction.
-            if !script_language.is_empty() {
-                // build a new temporary tokenizer including the allow list.
-                let mut tokenizer_builder = TokenizerBuilder::new();
-                if let Some(stop_words) = stop_words {
-                    tokenizer_builder.stop_words(stop_words);
-                }
-                tokenizer_builder.allow_list(&script_language);
-                let tokenizer = tokenizer_builder.build();
+        // if the allow list is empty, meaning that no Language has to be enforced,
+        // then we don't rerun the extraction.
+        if !script_language.is_empty() {
+            // build a new temporary tokenizer including the allow list.
+            let mut tokenizer_builder = TokenizerBuilder::new();
+            if let Some(stop_words) = stop_words {
+                tokenizer_builder.stop_words(stop_words);
+            }
+            tokenizer_builder.allow_list(&script_language);
+            tokenizer_builder.create_char_map(case_sensitive);
+            let tokenizer = tokenizer_builder.build();
 
-                script_language_word_count.clear();
+            script_language_word_count.clear();
 
-                // rerun the extraction.
-                extract_tokens_from_document(
-                    &obkv,
-                    searchable_fields,
-                    &tokenizer,
-                    max_positions_per_attributes,
-                    &mut buffers,
-                    &mut script_language_word_count,
-                    &mut docid_word_positions_sorter,
-                )?;
-            }
+            // rerun the extraction.
+            extract_tokens_from_document(
+                &obkv,
+                searchable_fields,
+                &tokenizer,
+                case_sensitive,
+                searchable_numbers,
+                max_positions_per_attributes,
+                &mut buffers,
+                &mut script_language_word_count,
+                &mut docid_word_positions_sorter,
+            )?;
         }
 
         for (script, languages_frequency) in script_language_word_count {
@@ -143,6 +172,8 @@ fn extract_tokens_from_document(
     obkv: &KvReader<FieldId>,
     searchable_fields: &Option<HashSet<FieldId>>,
     tokenizer: &Tokenizer,
+    case_sensitive: bool,
+    searchable_numbers: bool,
     max_positions_per_attributes: u32,
     buffers: &mut Buffers,
     script_language_word_count: &mut HashMap<Script, Vec<(Language, usize)>>,
@@ -152,8 +183,11 @@ fn extract_tokens_from_document(
         if searchable_fields.as_ref().map_or(true, |sf| sf.contains(&field_id)) {
             let value = serde_json::from_slice(field_bytes).map_err(InternalError::SerdeJson)?;
             buffers.field_buffer.clear();
-            if let Some(field) = json_to_string(&value, &mut buffers.field_buffer) {
-                let tokens = process_tokens(tokenizer.tokenize(field))
+            if let Some(field) =
+                json_to_string(&value, searchable_numbers, &mut buffers.field_buffer)
+            {
+                let tokens = tokenizer.tokenize(field);
+                let tokens = process_tokens(with_original_case(field, tokens, case_sensitive))
                     .take_while(|(p, _)| (*p as u32) < max_positions_per_attributes);
 
                 for (index, token) in tokens {
@@ -187,19 +221,25 @@ fn extract_tokens_from_document(
     Ok(())
 }
 
-/// Transform a JSON value into a string that can be indexed.
-fn json_to_string<'a>(value: &'a Value, buffer: &'a mut String) -> Option<&'a str> {
-    fn inner(value: &Value, output: &mut String) -> bool {
+/// Transform a JSON value into a string that can be indexed,
+/// the numbers are skipped when they must not be searchable.
+fn json_to_string<'a>(
+    value: &'a Value,
+    searchable_numbers: bool,
+    buffer: &'a mut String,
+) -> Option<&'a str> {
+    fn inner(value: &Value, searchable_numbers: bool, output: &mut String) -> bool {
         use std::fmt::Write;
         match value {
             Value::Null | Value::Object(_) => false,
             Value::Bool(boolean) => write!(output, "{}", boolean).is_ok(),
+            Value::Number(_) if !searchable_numbers => false,
             Value::Number(number) => write!(output, "{}", number).is_ok(),
             Value::String(string) => write!(output, "{}", string).is_ok(),
             Value::Array(array) => {
                 let mut count = 0;
                 for value in array {
-                    if inner(value, output) {
+                    if inner(value, searchable_numbers, output) {
                         output.push_str(". ");
                         count += 1;
                     }
@@ -212,7 +252,7 @@ fn json_to_string<'a>(value: &'a Value, buffer: &'a mut String) -> Option<&'a st
 
     if let Value::String(string) = value {
         Some(string)
-    } else if inner(value, buffer) {
+    } else if inner(value, searchable_numbers, buffer) {
         Some(buffer)
     } else {
         None
@@ -280,6 +320,40 @@ fn most_frequent_languages(
     }
 }
 
+/// Returns the Script a Language is written in.
+fn language_script(language: Language) -> Script {
+    match language {
+        Language::Cmn | Language::Jpn => Script::Cj,
+        Language::Kor => Script::Hangul,
+        Language::Rus
+        | Language::Ukr
+        | Language::Bel
+        | Language::Bul
+        | Language::Mkd
+        | Language::Srp => Script::Cyrillic,
+        Language::Ara | Language::Pes | Language::Urd => Script::Arabic,
+        Language::Heb | Language::Yid => Script::Hebrew,
+        Language::Hin | Language::Mar | Language::Nep => Script::Devanagari,
+        Language::Ell => Script::Greek,
+        Language::Tha => Script::Thai,
+        Language::Ben => Script::Bengali,
+        Language::Guj => Script::Gujarati,
+        Language::Pan => Script::Gurmukhi,
+        Language::Kan => Script::Kannada,
+        Language::Tam => Script::Tamil,
+        Language::Tel => Script::Telugu,
+        Language::Mal => Script::Malayalam,
+        Language::Ori => Script::Oriya,
+        Language::Sin => Script::Sinhala,
+        Language::Khm => Script::Khmer,
+        Language::Mya => Script::Myanmar,
+        Language::Kat => Script::Georgian,
+        Language::Hye => Script::Armenian,
+        Language::Amh => Script::Ethiopic,
+        _ => Script::Latin,
+    }
+}
+
 fn compute_language_frequency_threshold(languages_frequency: &[(Language, usize)]) -> usize {
     let total: usize = languages_frequency.iter().map(|(_, c)| c).sum();
     total / 10 // 10% is a completely arbitrary value.
@@ -14,6 +14,7 @@ use std::collections::HashSet;
 use std::fs::File;
 use std::io::BufReader;
 
+use charabia::Language;
 use crossbeam_channel::Sender;
 use log::debug;
 use rayon::prelude::*;
@@ -52,6 +53,9 @@ pub(crate) fn data_from_obkv_documents(
     stop_words: Option<fst::Set<&[u8]>>,
     allowed_separators: Option<&[&str]>,
     dictionary: Option<&[&str]>,
+    case_sensitive: bool,
+    languages: Option<&[Language]>,
+    searchable_numbers: bool,
     max_positions_per_attributes: Option<u32>,
     exact_attributes: HashSet<FieldId>,
 ) -> Result<()> {
@@ -86,6 +90,9 @@ pub(crate) fn data_from_obkv_documents(
                     &stop_words,
                     &allowed_separators,
                     &dictionary,
+                    case_sensitive,
+                    languages,
+                    searchable_numbers,
                     max_positions_per_attributes,
                 )
             })
@@ -326,6 +333,9 @@ fn send_and_extract_flattened_documents_data(
     stop_words: &Option<fst::Set<&[u8]>>,
     allowed_separators: &Option<&[&str]>,
     dictionary: &Option<&[&str]>,
+    case_sensitive: bool,
+    languages: Option<&[Language]>,
+    searchable_numbers: bool,
     max_positions_per_attributes: Option<u32>,
 ) -> Result<(
     grenad::Reader<CursorClonableMmap>,
@@ -367,6 +377,9 @@ fn send_and_extract_flattened_documents_data(
                         stop_words.as_ref(),
                         *allowed_separators,
                         *dictionary,
+                        case_sensitive,
+                        languages,
+                        searchable_numbers,
                         max_positions_per_attributes,
                     )?;
 
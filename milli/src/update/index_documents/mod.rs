@@ -38,7 +38,7 @@ use crate::update::{
     self, DeletionStrategy, IndexerConfig, PrefixWordPairsProximityDocids, UpdateIndexingStep,
     WordPrefixDocids, WordPrefixIntegerDocids, WordsPrefixesFst,
 };
-use crate::{Index, Result, RoaringBitmapCodec};
+use crate::{is_faceted_by, Index, Result, RoaringBitmapCodec};
 
 static MERGED_DATABASE_COUNT: usize = 7;
 static PREFIX_DATABASE_COUNT: usize = 5;
@@ -70,6 +70,20 @@ impl Default for IndexDocumentsMethod {
     }
 }
 
+/// What to do when several documents of a batch share the same document id.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateDocuments {
+    /// The duplicates are collapsed into a single document according to the update method,
+    /// the last one replacing or being merged into the previous ones.
+    #[default]
+    Collapse,
+    /// Only the first document is kept, the next duplicates are ignored.
+    KeepFirst,
+    /// The batch is rejected.
+    Reject,
+}
+
 pub struct IndexDocuments<'t, 'u, 'i, 'a, FP, FA> {
     wtxn: &'t mut heed::RwTxn<'i, 'u>,
     index: &'i Index,
@@ -89,6 +103,10 @@ pub struct IndexDocumentsConfig {
     pub words_positions_level_group_size: Option<NonZeroU32>,
     pub words_positions_min_level_size: Option<NonZeroU32>,
     pub update_method: IndexDocumentsMethod,
+    pub duplicate_documents: DuplicateDocuments,
+    /// The field holding the version of the documents, a document is only applied
+    /// if its version matches the version of the stored document.
+    pub version_field: Option<String>,
     pub deletion_strategy: DeletionStrategy,
     pub autogenerate_docids: bool,
 }
@@ -111,6 +129,8 @@ where
             index,
             indexer_config,
             config.update_method,
+            config.duplicate_documents,
+            config.version_field.clone(),
             config.autogenerate_docids,
         )?);
 
@@ -170,6 +190,18 @@ where
         Ok((self, Ok(indexed_documents)))
     }
 
+    /// Returns the number of documents added to the builder whose id was already used
+    /// by a previous document of the batch.
+    pub fn duplicated_documents(&self) -> u64 {
+        self.transform.as_ref().map_or(0, |transform| transform.duplicated_documents)
+    }
+
+    /// Returns the number of documents added to the builder that were not applied because
+    /// their version didn't match the version of the stored document.
+    pub fn rejected_documents(&self) -> u64 {
+        self.transform.as_ref().map_or(0, |transform| transform.rejected_documents)
+    }
+
     /// Remove a batch of documents from the current builder.
     ///
     /// Returns the number of documents deleted from the builder.
@@ -258,7 +290,7 @@ where
 
         let backup_pool;
         let pool = match self.indexer_config.thread_pool {
-            Some(ref pool) => pool,
+            Some(ref pool) => &**pool,
             #[cfg(not(test))]
             None => {
                 // We initialize a bakcup pool with the default
@@ -288,8 +320,21 @@ where
         let primary_key_id = fields_ids_map.id(&primary_key).unwrap();
 
         // get searchable fields for word databases
-        let searchable_fields =
+        let mut searchable_fields =
             self.index.searchable_fields_ids(self.wtxn)?.map(HashSet::from_iter);
+        // the encrypted fields are never indexed, even when every field is searchable
+        let encrypted_fields = self.index.encrypted_fields(self.wtxn)?;
+        if searchable_fields.is_none() && !encrypted_fields.is_empty() {
+            searchable_fields = Some(
+                fields_ids_map
+                    .iter()
+                    .filter(|(_, name)| {
+                        !encrypted_fields.iter().any(|encrypted| is_faceted_by(name, encrypted))
+                    })
+                    .map(|(id, _)| id)
+                    .collect(),
+            );
+        }
         // get filterable fields for facet databases
         let faceted_fields = self.index.faceted_fields_ids(self.wtxn)?;
         // get the fid of the `_geo.lat` and `_geo.lng` fields.
@@ -322,6 +367,9 @@ where
         let dictionary = self.index.dictionary(self.wtxn)?;
         let dictionary: Option<Vec<_>> =
             dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        let case_sensitive = self.index.case_sensitive(self.wtxn)?;
+        let languages = self.index.languages(self.wtxn)?;
+        let searchable_numbers = self.index.searchable_numbers(self.wtxn)?;
         let exact_attributes = self.index.exact_attributes_ids(self.wtxn)?;
 
         let pool_params = GrenadParameters {
@@ -361,6 +409,9 @@ where
                     stop_words,
                     separators.as_deref(),
                     dictionary.as_deref(),
+                    case_sensitive,
+                    languages.as_deref(),
+                    searchable_numbers,
                     max_positions_per_attributes,
                     exact_attributes,
                 )
@@ -825,6 +876,67 @@ mod tests {
         drop(rtxn);
     }
 
+    #[test]
+    fn keep_first_duplicate_document() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.duplicate_documents = DuplicateDocuments::KeepFirst;
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kevin" },
+                { "id": 1, "name": "kevina" },
+                { "id": 1, "name": "benoit" }
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let count = index.number_of_documents(&rtxn).unwrap();
+        assert_eq!(count, 1);
+
+        // Check that this document is equal to the first one sent.
+        let docs = index.documents(&rtxn, Some(0)).unwrap();
+        let (_, doc) = docs[0];
+        let mut doc_iter = doc.iter();
+        assert_eq!(doc_iter.next(), Some((0, &b"1"[..])));
+        assert_eq!(doc_iter.next(), Some((1, &br#""kevin""#[..])));
+        assert_eq!(doc_iter.next(), None);
+        drop(rtxn);
+    }
+
+    #[test]
+    fn duplicate_documents_are_only_looked_for_within_a_payload() {
+        for duplicate_documents in [DuplicateDocuments::Reject, DuplicateDocuments::KeepFirst] {
+            let mut index = TempIndex::new();
+            index.index_documents_config.duplicate_documents = duplicate_documents;
+
+            let mut wtxn = index.write_txn().unwrap();
+            let builder = IndexDocuments::new(
+                &mut wtxn,
+                &index,
+                &index.indexer_config,
+                index.index_documents_config.clone(),
+                |_| (),
+                || false,
+            )
+            .unwrap();
+
+            let (builder, added) =
+                builder.add_documents(documents!([{ "id": 1, "name": "kevin" }])).unwrap();
+            insta::assert_display_snapshot!(added.unwrap(), @"1");
+            let (builder, added) =
+                builder.add_documents(documents!([{ "id": 1, "name": "kevina" }])).unwrap();
+            insta::assert_display_snapshot!(added.unwrap(), @"1");
+
+            builder.execute().unwrap();
+            wtxn.commit().unwrap();
+
+            // The second payload replaces the document of the first one.
+            db_snap!(index, documents, @r###"
+            {"id":1,"name":"kevina"}
+            "###);
+        }
+    }
+
     #[test]
     fn not_auto_generated_documents_ids() {
         let index = TempIndex::new();
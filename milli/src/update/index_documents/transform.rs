@@ -15,7 +15,7 @@ use smartstring::SmartString;
 use super::helpers::{
     create_sorter, create_writer, keep_latest_obkv, merge_obkvs_and_operations, MergeFn,
 };
-use super::{IndexDocumentsMethod, IndexerConfig};
+use super::{DuplicateDocuments, IndexDocumentsMethod, IndexerConfig};
 use crate::documents::{DocumentsBatchIndex, EnrichedDocument, EnrichedDocumentsBatchReader};
 use crate::error::{Error, InternalError, UserError};
 use crate::index::{db_name, main_key};
@@ -49,6 +49,15 @@ pub struct Transform<'a, 'i> {
     indexer_settings: &'a IndexerConfig,
     pub autogenerate_docids: bool,
     pub index_documents_method: IndexDocumentsMethod,
+    pub duplicate_documents: DuplicateDocuments,
+    /// The number of documents whose id was already used by a previous document of their payload.
+    pub duplicated_documents: u64,
+    /// The field holding the version of the documents, if any.
+    pub version_field: Option<String>,
+    /// The number of documents that were not applied because of a version mismatch.
+    pub rejected_documents: u64,
+    /// The versions given to the documents of the batch, by external id.
+    versions: FxHashMap<SmartString<smartstring::Compact>, u64>,
     available_documents_ids: AvailableDocumentsIds,
 
     // Both grenad follows the same format:
@@ -101,6 +110,8 @@ impl<'a, 'i> Transform<'a, 'i> {
         index: &'i Index,
         indexer_settings: &'a IndexerConfig,
         index_documents_method: IndexDocumentsMethod,
+        duplicate_documents: DuplicateDocuments,
+        version_field: Option<String>,
         autogenerate_docids: bool,
     ) -> Result<Self> {
         // We must choose the appropriate merge function for when two or more documents
@@ -144,6 +155,11 @@ impl<'a, 'i> Transform<'a, 'i> {
             original_sorter,
             flattened_sorter,
             index_documents_method,
+            duplicate_documents,
+            duplicated_documents: 0,
+            version_field,
+            rejected_documents: 0,
+            versions: FxHashMap::default(),
             replaced_documents_ids: RoaringBitmap::new(),
             new_documents_ids: RoaringBitmap::new(),
             new_external_documents_ids_builder: FxHashMap::default(),
@@ -170,12 +186,22 @@ impl<'a, 'i> Transform<'a, 'i> {
         let primary_key = cursor.primary_key().to_string();
         let primary_key_id =
             self.fields_ids_map.insert(&primary_key).ok_or(UserError::AttributeLimitReached)?;
+        let version_id = match &self.version_field {
+            Some(field) => {
+                Some(self.fields_ids_map.insert(field).ok_or(UserError::AttributeLimitReached)?)
+            }
+            None => None,
+        };
 
         let mut obkv_buffer = Vec::new();
         let mut document_sorter_buffer = Vec::new();
         let mut documents_count = 0;
         let mut docid_buffer: Vec<u8> = Vec::new();
+        let mut version_buffer: Vec<u8> = Vec::new();
         let mut field_buffer: Vec<(u16, Cow<[u8]>)> = Vec::new();
+        // The duplicates are only looked for within a payload, the documents of the next
+        // payloads of the batch replace or update the previous ones.
+        let mut payload_external_ids = HashSet::new();
         while let Some(enriched_document) = cursor.next_enriched_document()? {
             let EnrichedDocument { document, document_id } = enriched_document;
 
@@ -207,6 +233,39 @@ impl<'a, 'i> Transform<'a, 'i> {
                 field_buffer_cache.push((mapped_id, Cow::from(v)));
             }
 
+            // When the documents are versioned, a document specifying a version is only applied
+            // if it matches the version of the stored document or of the previous document of
+            // the batch with the same id. The version is then incremented.
+            if let Some(version_id) = version_id {
+                let current = match self.versions.get(external_id) {
+                    Some(version) => *version,
+                    None => {
+                        let docid = external_documents_ids.get(external_id);
+                        self.stored_version(wtxn, docid, version_id)?
+                    }
+                };
+                let version =
+                    field_buffer_cache.iter().find(|(field_id, _)| *field_id == version_id);
+                let accepted = match version {
+                    Some((_, version)) => serde_json::from_slice(version).ok() == Some(current),
+                    None => true,
+                };
+
+                if !accepted {
+                    self.rejected_documents += 1;
+                    field_buffer = drop_and_reuse(field_buffer_cache);
+                    docid_buffer.clear();
+                    continue;
+                }
+
+                let next_version = current + 1;
+                self.versions.insert(external_id.into(), next_version);
+                serde_json::to_writer(&mut version_buffer, &next_version)
+                    .map_err(InternalError::SerdeJson)?;
+                field_buffer_cache.retain(|(field_id, _)| *field_id != version_id);
+                field_buffer_cache.push((version_id, Cow::from(&version_buffer)));
+            }
+
             // Insertion in a obkv need to be done with keys ordered. For now they are ordered
             // according to the document addition key order, so we sort it according to the
             // fieldids map keys order.
@@ -219,6 +278,21 @@ impl<'a, 'i> Transform<'a, 'i> {
             }
 
             let mut original_docid = None;
+            let mut skip_insertion = false;
+
+            if !payload_external_ids.insert(external_id.to_string()) {
+                self.duplicated_documents += 1;
+                match self.duplicate_documents {
+                    DuplicateDocuments::Collapse => (),
+                    DuplicateDocuments::KeepFirst => skip_insertion = true,
+                    DuplicateDocuments::Reject => {
+                        return Err(UserError::DuplicateDocumentId {
+                            document_id: external_id.to_string(),
+                        }
+                        .into())
+                    }
+                }
+            }
 
             let docid = match self.new_external_documents_ids_builder.entry((*external_id).into()) {
                 Entry::Occupied(entry) => *entry.get() as u32,
@@ -241,7 +315,6 @@ impl<'a, 'i> Transform<'a, 'i> {
                 }
             };
 
-            let mut skip_insertion = false;
             if let Some(original_docid) = original_docid {
                 let original_key = BEU32::new(original_docid);
                 let base_obkv = self
@@ -313,6 +386,7 @@ impl<'a, 'i> Transform<'a, 'i> {
 
             field_buffer = drop_and_reuse(field_buffer_cache);
             docid_buffer.clear();
+            version_buffer.clear();
             obkv_buffer.clear();
         }
 
@@ -328,6 +402,27 @@ impl<'a, 'i> Transform<'a, 'i> {
         Ok(documents_count)
     }
 
+    /// Returns the version of the stored document, `0` if the document doesn't exist
+    /// or isn't versioned.
+    fn stored_version(
+        &self,
+        rtxn: &heed::RoTxn,
+        docid: Option<u32>,
+        version_id: FieldId,
+    ) -> Result<u64> {
+        let docid = match docid {
+            Some(docid) => docid,
+            None => return Ok(0),
+        };
+        let stored = self.index.documents.get(rtxn, &BEU32::new(docid))?.ok_or(
+            InternalError::DatabaseMissingEntry { db_name: db_name::DOCUMENTS, key: None },
+        )?;
+        Ok(stored
+            .get(version_id)
+            .and_then(|version| serde_json::from_slice(version).ok())
+            .unwrap_or(0))
+    }
+
     /// The counter part of `read_documents` that removes documents either from the transform or the database.
     /// It can be called before, after or in between two calls of the `read_documents`.
     ///
@@ -812,11 +907,17 @@ impl TransformOutput {
     // find and insert the new field ids
     pub fn compute_real_facets(&self, rtxn: &RoTxn, index: &Index) -> Result<HashSet<String>> {
         let user_defined_facets = index.user_defined_faceted_fields(rtxn)?;
+        // the filterable wildcard also applies to the fields introduced by these documents
+        let all_filterable = index.user_defined_filterable_fields(rtxn)?.contains("*");
+        let encrypted_fields = index.encrypted_fields(rtxn)?;
 
         Ok(self
             .fields_ids_map
             .names()
-            .filter(|&field| crate::is_faceted(field, &user_defined_facets))
+            .filter(|&field| {
+                crate::is_faceted(field, &user_defined_facets)
+                    || (all_filterable && !crate::is_faceted(field, &encrypted_fields))
+            })
             .map(|field| field.to_string())
             .collect())
     }
@@ -1,7 +1,7 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::result::Result as StdResult;
 
-use charabia::{Normalize, Tokenizer, TokenizerBuilder};
+use charabia::{Language, Normalize, Tokenizer, TokenizerBuilder};
 use deserr::{DeserializeError, Deserr};
 use itertools::Itertools;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -12,9 +12,9 @@ use super::IndexerConfig;
 use crate::criterion::Criterion;
 use crate::error::UserError;
 use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
-use crate::update::index_documents::IndexDocumentsMethod;
+use crate::update::index_documents::{DuplicateDocuments, IndexDocumentsMethod};
 use crate::update::{IndexDocuments, UpdateIndexingStep};
-use crate::{FieldsIdsMap, Index, OrderBy, Result};
+use crate::{with_original_case, FieldsIdsMap, Index, OrderBy, Result};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -111,12 +111,19 @@ pub struct Settings<'a, 't, 'u, 'i> {
     filterable_fields: Setting<HashSet<String>>,
     sortable_fields: Setting<HashSet<String>>,
     criteria: Setting<Vec<Criterion>>,
+    tie_breaking_sort: Setting<Vec<Criterion>>,
+    /// Attributes whose values are encrypted at rest by the caller.
+    encrypted_fields: Setting<BTreeSet<String>>,
     stop_words: Setting<BTreeSet<String>>,
     non_separator_tokens: Setting<BTreeSet<String>>,
     separator_tokens: Setting<BTreeSet<String>>,
     dictionary: Setting<BTreeSet<String>>,
+    case_sensitive: Setting<bool>,
+    languages: Setting<BTreeSet<String>>,
+    searchable_numbers: Setting<bool>,
     distinct_field: Setting<String>,
     synonyms: Setting<BTreeMap<String, Vec<String>>>,
+    bidirectional_synonyms: Setting<Vec<Vec<String>>>,
     primary_key: Setting<String>,
     authorize_typos: Setting<bool>,
     min_word_len_two_typos: Setting<u8>,
@@ -127,6 +134,7 @@ pub struct Settings<'a, 't, 'u, 'i> {
     max_values_per_facet: Setting<usize>,
     sort_facet_values_by: Setting<HashMap<String, OrderBy>>,
     pagination_max_total_hits: Setting<usize>,
+    pinned_in_memory: Setting<bool>,
 }
 
 impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
@@ -143,12 +151,18 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             filterable_fields: Setting::NotSet,
             sortable_fields: Setting::NotSet,
             criteria: Setting::NotSet,
+            tie_breaking_sort: Setting::NotSet,
+            encrypted_fields: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
+            case_sensitive: Setting::NotSet,
+            languages: Setting::NotSet,
+            searchable_numbers: Setting::NotSet,
             distinct_field: Setting::NotSet,
             synonyms: Setting::NotSet,
+            bidirectional_synonyms: Setting::NotSet,
             primary_key: Setting::NotSet,
             authorize_typos: Setting::NotSet,
             exact_words: Setting::NotSet,
@@ -158,6 +172,7 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             max_values_per_facet: Setting::NotSet,
             sort_facet_values_by: Setting::NotSet,
             pagination_max_total_hits: Setting::NotSet,
+            pinned_in_memory: Setting::NotSet,
             indexer_config,
         }
     }
@@ -202,6 +217,22 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.criteria = Setting::Set(criteria);
     }
 
+    pub fn reset_tie_breaking_sort(&mut self) {
+        self.tie_breaking_sort = Setting::Reset;
+    }
+
+    pub fn set_tie_breaking_sort(&mut self, criteria: Vec<Criterion>) {
+        self.tie_breaking_sort = Setting::Set(criteria);
+    }
+
+    pub fn reset_encrypted_fields(&mut self) {
+        self.encrypted_fields = Setting::Reset;
+    }
+
+    pub fn set_encrypted_fields(&mut self, names: BTreeSet<String>) {
+        self.encrypted_fields = Setting::Set(names);
+    }
+
     pub fn reset_stop_words(&mut self) {
         self.stop_words = Setting::Reset;
     }
@@ -235,6 +266,30 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         }
     }
 
+    pub fn reset_case_sensitive(&mut self) {
+        self.case_sensitive = Setting::Reset;
+    }
+
+    pub fn set_case_sensitive(&mut self, value: bool) {
+        self.case_sensitive = Setting::Set(value);
+    }
+
+    pub fn reset_searchable_numbers(&mut self) {
+        self.searchable_numbers = Setting::Reset;
+    }
+
+    pub fn set_searchable_numbers(&mut self, value: bool) {
+        self.searchable_numbers = Setting::Set(value);
+    }
+
+    pub fn reset_languages(&mut self) {
+        self.languages = Setting::Reset;
+    }
+
+    pub fn set_languages(&mut self, languages: BTreeSet<String>) {
+        self.languages = Setting::Set(languages);
+    }
+
     pub fn reset_dictionary(&mut self) {
         self.dictionary = Setting::Reset;
     }
@@ -260,6 +315,15 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.synonyms = if synonyms.is_empty() { Setting::Reset } else { Setting::Set(synonyms) }
     }
 
+    pub fn reset_bidirectional_synonyms(&mut self) {
+        self.bidirectional_synonyms = Setting::Reset;
+    }
+
+    pub fn set_bidirectional_synonyms(&mut self, groups: Vec<Vec<String>>) {
+        self.bidirectional_synonyms =
+            if groups.is_empty() { Setting::Reset } else { Setting::Set(groups) }
+    }
+
     pub fn reset_primary_key(&mut self) {
         self.primary_key = Setting::Reset;
     }
@@ -332,12 +396,20 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.pagination_max_total_hits = Setting::Reset;
     }
 
+    pub fn set_pinned_in_memory(&mut self, value: bool) {
+        self.pinned_in_memory = Setting::Set(value);
+    }
+
+    pub fn reset_pinned_in_memory(&mut self) {
+        self.pinned_in_memory = Setting::Reset;
+    }
+
     fn reindex<FP, FA>(
         &mut self,
         progress_callback: &FP,
         should_abort: &FA,
         old_fields_ids_map: FieldsIdsMap,
-    ) -> Result<()>
+    ) -> Result<u64>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
         FA: Fn() -> bool + Sync,
@@ -347,8 +419,9 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         let fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
         // if the settings are set before any document update, we don't need to do anything, and
         // will set the primary key during the first document addition.
-        if self.index.number_of_documents(self.wtxn)? == 0 {
-            return Ok(());
+        let number_of_documents = self.index.number_of_documents(self.wtxn)?;
+        if number_of_documents == 0 {
+            return Ok(0);
         }
 
         let transform = Transform::new(
@@ -356,6 +429,8 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             self.index,
             self.indexer_config,
             IndexDocumentsMethod::ReplaceDocuments,
+            DuplicateDocuments::default(),
+            None,
             false,
         )?;
 
@@ -378,7 +453,7 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         )?;
         indexing_builder.execute_raw(output)?;
 
-        Ok(())
+        Ok(number_of_documents)
     }
 
     fn update_displayed(&mut self) -> Result<bool> {
@@ -566,12 +641,112 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(changes)
     }
 
+    fn update_case_sensitive(&mut self) -> Result<bool> {
+        let changes = match self.case_sensitive {
+            Setting::Set(value) => {
+                let current = self.index.case_sensitive(self.wtxn)?;
+                if current != value {
+                    self.index.put_case_sensitive(self.wtxn, value)?;
+                    true
+                } else {
+                    false
+                }
+            }
+            Setting::Reset => {
+                let current = self.index.case_sensitive(self.wtxn)?;
+                self.index.delete_case_sensitive(self.wtxn)?;
+                current
+            }
+            Setting::NotSet => false,
+        };
+
+        // the synonyms must be updated if the case sensitivity has been updated.
+        if changes && self.synonyms == Setting::NotSet {
+            self.synonyms = Setting::Set(self.index.user_defined_synonyms(self.wtxn)?);
+        }
+
+        Ok(changes)
+    }
+
+    fn update_searchable_numbers(&mut self) -> Result<bool> {
+        match self.searchable_numbers {
+            Setting::Set(value) => {
+                let current = self.index.searchable_numbers(self.wtxn)?;
+                self.index.put_searchable_numbers(self.wtxn, value)?;
+                Ok(current != value)
+            }
+            Setting::Reset => {
+                let current = self.index.searchable_numbers(self.wtxn)?;
+                self.index.delete_searchable_numbers(self.wtxn)?;
+                Ok(!current)
+            }
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_languages(&mut self) -> Result<bool> {
+        match self.languages {
+            // an empty list doesn't restrict anything, it is a reset.
+            Setting::Set(ref languages) if languages.is_empty() => {
+                Ok(self.index.delete_languages(self.wtxn)?)
+            }
+            Setting::Set(ref languages) => {
+                if let Some(unknown) = languages
+                    .iter()
+                    .find(|name| Language::from_name(name.as_str()).name() != name.as_str())
+                {
+                    return Err(UserError::InvalidLanguage(unknown.clone()).into());
+                }
+
+                let current = self.index.languages_names(self.wtxn)?;
+
+                // Does the new list differ from the previous one?
+                if current.map_or(true, |current| &current != languages) {
+                    self.index.put_languages(self.wtxn, languages)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_languages(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_bidirectional_synonyms(&mut self) -> Result<bool> {
+        let changes = match self.bidirectional_synonyms {
+            Setting::Set(ref groups) => {
+                let current = self.index.bidirectional_synonyms(self.wtxn)?;
+
+                // Do the new groups differ from the previous ones?
+                if &current != groups {
+                    self.index.put_bidirectional_synonyms(self.wtxn, groups)?;
+                    true
+                } else {
+                    false
+                }
+            }
+            Setting::Reset => self.index.delete_bidirectional_synonyms(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        // the synonyms must be updated if the bidirectional synonyms have been updated.
+        if changes && self.synonyms == Setting::NotSet {
+            self.synonyms = Setting::Set(self.index.user_defined_synonyms(self.wtxn)?);
+        }
+
+        Ok(changes)
+    }
+
     fn update_synonyms(&mut self) -> Result<bool> {
         match self.synonyms {
             Setting::Set(ref user_synonyms) => {
-                fn normalize(tokenizer: &Tokenizer, text: &str) -> Vec<String> {
-                    tokenizer
-                        .tokenize(text)
+                fn normalize(
+                    tokenizer: &Tokenizer,
+                    text: &str,
+                    case_sensitive: bool,
+                ) -> Vec<String> {
+                    with_original_case(text, tokenizer.tokenize(text), case_sensitive)
                         .filter_map(|token| {
                             if token.is_word() && !token.lemma().is_empty() {
                                 Some(token.lemma().to_string())
@@ -602,15 +777,18 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
                     builder.words_dict(dictionary);
                 }
 
+                let case_sensitive = self.index.case_sensitive(self.wtxn)?;
+                builder.create_char_map(case_sensitive);
+
                 let tokenizer = builder.build();
 
                 let mut new_synonyms = HashMap::new();
                 for (word, synonyms) in user_synonyms {
                     // Normalize both the word and associated synonyms.
-                    let normalized_word = normalize(&tokenizer, word);
+                    let normalized_word = normalize(&tokenizer, word, case_sensitive);
                     let normalized_synonyms: Vec<_> = synonyms
                         .iter()
-                        .map(|synonym| normalize(&tokenizer, synonym))
+                        .map(|synonym| normalize(&tokenizer, synonym, case_sensitive))
                         .filter(|synonym| !synonym.is_empty())
                         .collect();
 
@@ -622,6 +800,27 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
                     }
                 }
 
+                // Every word of a bidirectional group is a synonym of all the other words of the group.
+                for group in self.index.bidirectional_synonyms(self.wtxn)? {
+                    let normalized_group: Vec<_> = group
+                        .iter()
+                        .map(|word| normalize(&tokenizer, word, case_sensitive))
+                        .filter(|word| !word.is_empty())
+                        .collect();
+
+                    for (i, normalized_word) in normalized_group.iter().enumerate() {
+                        let normalized_synonyms = normalized_group
+                            .iter()
+                            .enumerate()
+                            .filter(|(j, synonym)| *j != i && synonym != &normalized_word)
+                            .map(|(_, synonym)| synonym.clone());
+                        let entry =
+                            new_synonyms.entry(normalized_word.clone()).or_insert_with(Vec::new);
+                        entry.extend(normalized_synonyms);
+                    }
+                }
+                new_synonyms.retain(|_, synonyms| !synonyms.is_empty());
+
                 // Make sure that we don't have duplicate synonyms.
                 new_synonyms.iter_mut().for_each(|(_, synonyms)| {
                     synonyms.sort_unstable();
@@ -637,7 +836,15 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
                     Ok(false)
                 }
             }
-            Setting::Reset => Ok(self.index.delete_synonyms(self.wtxn)?),
+            Setting::Reset => {
+                if self.index.bidirectional_synonyms(self.wtxn)?.is_empty() {
+                    Ok(self.index.delete_synonyms(self.wtxn)?)
+                } else {
+                    // the bidirectional synonyms must be kept even without user defined synonyms.
+                    self.synonyms = Setting::Set(BTreeMap::new());
+                    self.update_synonyms()
+                }
+            }
             Setting::NotSet => Ok(false),
         }
     }
@@ -708,6 +915,105 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
+    fn update_tie_breaking_sort(&mut self) -> Result<()> {
+        match &self.tie_breaking_sort {
+            Setting::Set(criteria) => {
+                if let Some(invalid) = criteria
+                    .iter()
+                    .find(|criterion| !matches!(criterion, Criterion::Asc(_) | Criterion::Desc(_)))
+                {
+                    return Err(UserError::InvalidTieBreakingSort(invalid.to_string()).into());
+                }
+                self.index.put_tie_breaking_sort(self.wtxn, criteria)?;
+            }
+            Setting::Reset => {
+                self.index.delete_tie_breaking_sort(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
+    }
+
+    fn update_encrypted_fields(&mut self) -> Result<()> {
+        let new = match &self.encrypted_fields {
+            Setting::Set(fields) => fields.clone(),
+            Setting::Reset => BTreeSet::new(),
+            Setting::NotSet => return Ok(()),
+        };
+
+        // Only the top-level values of the documents are encrypted.
+        if let Some(field) = new.iter().find(|field| field.contains('.')) {
+            return Err(UserError::NestedEncryptedAttribute(field.clone()).into());
+        }
+
+        // The already stored values are not re-encrypted nor decrypted.
+        if new != self.index.encrypted_fields(self.wtxn)?
+            && self.index.number_of_documents(self.wtxn)? != 0
+        {
+            return Err(UserError::EncryptedAttributesOnNonEmptyIndex.into());
+        }
+
+        if new.is_empty() {
+            self.index.delete_encrypted_fields(self.wtxn)?;
+        } else {
+            self.index.put_encrypted_fields(self.wtxn, &new)?;
+        }
+        Ok(())
+    }
+
+    /// Ensures that no encrypted field is searchable, faceted or used as the primary key,
+    /// as their stored values are meaningless.
+    fn check_encrypted_fields(&self) -> Result<()> {
+        let encrypted_fields = self.index.encrypted_fields(self.wtxn)?;
+        if encrypted_fields.is_empty() {
+            return Ok(());
+        }
+
+        let mut used_fields: Vec<(String, &'static str)> = Vec::new();
+        if let Some(searchable) = self.index.user_defined_searchable_fields(self.wtxn)? {
+            used_fields
+                .extend(searchable.into_iter().map(|f| (f.to_string(), "searchableAttributes")));
+        }
+        used_fields.extend(
+            self.index
+                .filterable_fields(self.wtxn)?
+                .into_iter()
+                .map(|f| (f, "filterableAttributes")),
+        );
+        used_fields.extend(
+            self.index.sortable_fields(self.wtxn)?.into_iter().map(|f| (f, "sortableAttributes")),
+        );
+        if let Some(distinct) = self.index.distinct_field(self.wtxn)? {
+            used_fields.push((distinct.to_string(), "distinctAttribute"));
+        }
+        for criterion in self.index.criteria(self.wtxn)? {
+            if let Criterion::Asc(field) | Criterion::Desc(field) = criterion {
+                used_fields.push((field, "rankingRules"));
+            }
+        }
+        for criterion in self.index.tie_breaking_sort(self.wtxn)? {
+            if let Criterion::Asc(field) | Criterion::Desc(field) = criterion {
+                used_fields.push((field, "tieBreakingSort"));
+            }
+        }
+        if let Some(primary_key) = self.index.primary_key(self.wtxn)? {
+            used_fields.push((primary_key.to_string(), "primaryKey"));
+        }
+
+        for (field, setting) in used_fields {
+            if let Some(encrypted) =
+                encrypted_fields.iter().find(|encrypted| crate::is_faceted_by(&field, encrypted))
+            {
+                return Err(UserError::InvalidEncryptedAttribute {
+                    field: encrypted.clone(),
+                    setting,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
     fn update_primary_key(&mut self) -> Result<()> {
         match self.primary_key {
             Setting::Set(ref primary_key) => {
@@ -790,8 +1096,10 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
     fn update_exact_words(&mut self) -> Result<()> {
         match self.exact_words {
             Setting::Set(ref mut words) => {
-                fn normalize(tokenizer: &Tokenizer, text: &str) -> String {
-                    tokenizer.tokenize(text).map(|token| token.lemma().to_string()).collect()
+                fn normalize(tokenizer: &Tokenizer, text: &str, case_sensitive: bool) -> String {
+                    with_original_case(text, tokenizer.tokenize(text), case_sensitive)
+                        .map(|token| token.lemma().to_string())
+                        .collect()
                 }
 
                 let mut builder = TokenizerBuilder::new();
@@ -799,10 +1107,12 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
                 if let Some(ref stop_words) = stop_words {
                     builder.stop_words(stop_words);
                 }
+                let case_sensitive = self.index.case_sensitive(self.wtxn)?;
+                builder.create_char_map(case_sensitive);
                 let tokenizer = builder.build();
 
                 let mut words: Vec<_> =
-                    words.iter().map(|word| normalize(&tokenizer, word)).collect();
+                    words.iter().map(|word| normalize(&tokenizer, word, case_sensitive)).collect();
 
                 // normalization could reorder words
                 words.sort_unstable();
@@ -861,7 +1171,22 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
-    pub fn execute<FP, FA>(mut self, progress_callback: FP, should_abort: FA) -> Result<()>
+    fn update_pinned_in_memory(&mut self) -> Result<()> {
+        match self.pinned_in_memory {
+            Setting::Set(value) => {
+                self.index.put_pinned_in_memory(self.wtxn, value)?;
+            }
+            Setting::Reset => {
+                self.index.delete_pinned_in_memory(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    /// Applies the settings and returns the number of documents that had to be reindexed.
+    pub fn execute<FP, FA>(mut self, progress_callback: FP, should_abort: FA) -> Result<u64>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
         FA: Fn() -> bool + Sync,
@@ -876,6 +1201,7 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.update_sortable()?;
         self.update_distinct_field()?;
         self.update_criteria()?;
+        self.update_tie_breaking_sort()?;
         self.update_primary_key()?;
         self.update_authorize_typos()?;
         self.update_min_typo_word_len()?;
@@ -883,10 +1209,13 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.update_max_values_per_facet()?;
         self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
+        self.update_pinned_in_memory()?;
+        self.update_encrypted_fields()?;
 
         // If there is new faceted fields we indicate that we must reindex as we must
         // index new fields as facets. It means that the distinct attribute,
-        // an Asc/Desc criterion or a filtered attribute as be added or removed.
+        // an Asc/Desc criterion, a tie breaking sort or a filtered attribute as be added
+        // or removed.
         let new_faceted_fields = self.index.user_defined_faceted_fields(self.wtxn)?;
         let faceted_updated = old_faceted_fields != new_faceted_fields;
 
@@ -894,23 +1223,32 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         let non_separator_tokens_updated = self.update_non_separator_tokens()?;
         let separator_tokens_updated = self.update_separator_tokens()?;
         let dictionary_updated = self.update_dictionary()?;
+        let case_sensitive_updated = self.update_case_sensitive()?;
+        let languages_updated = self.update_languages()?;
+        let searchable_numbers_updated = self.update_searchable_numbers()?;
+        let bidirectional_synonyms_updated = self.update_bidirectional_synonyms()?;
         let synonyms_updated = self.update_synonyms()?;
         let searchable_updated = self.update_searchable()?;
         let exact_attributes_updated = self.update_exact_attributes()?;
+        self.check_encrypted_fields()?;
 
         if stop_words_updated
             || non_separator_tokens_updated
             || separator_tokens_updated
             || dictionary_updated
+            || case_sensitive_updated
+            || languages_updated
+            || searchable_numbers_updated
+            || bidirectional_synonyms_updated
             || faceted_updated
             || synonyms_updated
             || searchable_updated
             || exact_attributes_updated
         {
-            self.reindex(&progress_callback, &should_abort, old_fields_ids_map)?;
+            self.reindex(&progress_callback, &should_abort, old_fields_ids_map)
+        } else {
+            Ok(0)
         }
-
-        Ok(())
     }
 }
 
@@ -926,6 +1264,57 @@ mod tests {
     use crate::update::{ClearDocuments, DeleteDocuments};
     use crate::{Criterion, Filter, SearchResult};
 
+    #[test]
+    fn multiple_settings_are_applied_in_a_single_reindex() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kevin", "age": 23 },
+                { "id": 2, "name": "kevina", "age": 21 },
+                { "id": 3, "name": "benoit", "age": 34 }
+            ]))
+            .unwrap();
+
+        // Every indexing pass starts by announcing its first database merge.
+        let reindex_passes = AtomicUsize::new(0);
+        let mut wtxn = index.write_txn().unwrap();
+        let mut builder = Settings::new(&mut wtxn, &index.inner, &index.indexer_config);
+        builder.set_searchable_fields(vec![S("name")]);
+        builder.set_criteria(vec![Criterion::Words, Criterion::Asc(S("age"))]);
+        builder
+            .execute(
+                |step| {
+                    if let UpdateIndexingStep::MergeDataIntoFinalDatabase {
+                        databases_seen: 0,
+                        ..
+                    } = step
+                    {
+                        reindex_passes.fetch_add(1, Ordering::Relaxed);
+                    }
+                },
+                || false,
+            )
+            .unwrap();
+        wtxn.commit().unwrap();
+
+        assert_eq!(reindex_passes.into_inner(), 1);
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.searchable_fields(&rtxn).unwrap(), Some(vec!["name"]));
+        assert_eq!(
+            index.criteria(&rtxn).unwrap(),
+            vec![Criterion::Words, Criterion::Asc(S("age"))]
+        );
+        // the age is now faceted for the sort and the name is the only searchable field.
+        let result = index.search(&rtxn).query("23").execute().unwrap();
+        assert!(result.documents_ids.is_empty());
+        let result = index.search(&rtxn).query("benoit").execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 1);
+    }
+
     #[test]
     fn set_and_reset_searchable_fields() {
         let index = TempIndex::new();
@@ -1716,12 +2105,18 @@ mod tests {
                     filterable_fields,
                     sortable_fields,
                     criteria,
+                    tie_breaking_sort,
+                    encrypted_fields,
                     stop_words,
                     non_separator_tokens,
                     separator_tokens,
                     dictionary,
+                    case_sensitive,
+                    languages,
+                    searchable_numbers,
                     distinct_field,
                     synonyms,
+                    bidirectional_synonyms,
                     primary_key,
                     authorize_typos,
                     min_word_len_two_typos,
@@ -1731,18 +2126,25 @@ mod tests {
                     max_values_per_facet,
                     sort_facet_values_by,
                     pagination_max_total_hits,
+                    pinned_in_memory,
                 } = settings;
                 assert!(matches!(searchable_fields, Setting::NotSet));
                 assert!(matches!(displayed_fields, Setting::NotSet));
                 assert!(matches!(filterable_fields, Setting::NotSet));
                 assert!(matches!(sortable_fields, Setting::NotSet));
                 assert!(matches!(criteria, Setting::NotSet));
+                assert!(matches!(tie_breaking_sort, Setting::NotSet));
+                assert!(matches!(encrypted_fields, Setting::NotSet));
                 assert!(matches!(stop_words, Setting::NotSet));
                 assert!(matches!(non_separator_tokens, Setting::NotSet));
                 assert!(matches!(separator_tokens, Setting::NotSet));
                 assert!(matches!(dictionary, Setting::NotSet));
+                assert!(matches!(case_sensitive, Setting::NotSet));
+                assert!(matches!(languages, Setting::NotSet));
+                assert!(matches!(searchable_numbers, Setting::NotSet));
                 assert!(matches!(distinct_field, Setting::NotSet));
                 assert!(matches!(synonyms, Setting::NotSet));
+                assert!(matches!(bidirectional_synonyms, Setting::NotSet));
                 assert!(matches!(primary_key, Setting::NotSet));
                 assert!(matches!(authorize_typos, Setting::NotSet));
                 assert!(matches!(min_word_len_two_typos, Setting::NotSet));
@@ -1752,6 +2154,7 @@ mod tests {
                 assert!(matches!(max_values_per_facet, Setting::NotSet));
                 assert!(matches!(sort_facet_values_by, Setting::NotSet));
                 assert!(matches!(pagination_max_total_hits, Setting::NotSet));
+                assert!(matches!(pinned_in_memory, Setting::NotSet));
             })
             .unwrap();
     }